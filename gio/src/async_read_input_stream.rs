@@ -0,0 +1,343 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use futures_io::AsyncRead;
+use glib::translate::*;
+
+use crate::{
+    prelude::*, read_input_stream::std_error_to_gio_error, subclass::prelude::*, Cancellable,
+    InputStream, PollableInputStream,
+};
+
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Default)]
+    pub struct AsyncReadInputStream {
+        pub(super) read: RefCell<Option<Pin<Box<dyn AsyncRead + Send + 'static>>>>,
+        pub(super) waker: PollWaker,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for AsyncReadInputStream {
+        const NAME: &'static str = "AsyncReadInputStream";
+        type Type = super::AsyncReadInputStream;
+        type ParentType = InputStream;
+        type Interfaces = (PollableInputStream,);
+    }
+
+    impl ObjectImpl for AsyncReadInputStream {}
+
+    impl AsyncReadInputStream {
+        fn poll_read(
+            &self,
+            cx: &mut Context,
+            buffer: &mut [u8],
+        ) -> Poll<Result<usize, glib::Error>> {
+            let mut read = self.read.borrow_mut();
+            let read = match *read {
+                None => {
+                    return Poll::Ready(Err(glib::Error::new(
+                        crate::IOErrorEnum::Closed,
+                        "Already closed",
+                    )));
+                }
+                Some(ref mut read) => read,
+            };
+
+            loop {
+                match read.as_mut().poll_read(cx, buffer) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(res) => match std_error_to_gio_error(res) {
+                        None => continue,
+                        Some(res) => return Poll::Ready(res),
+                    },
+                }
+            }
+        }
+    }
+
+    impl InputStreamImpl for AsyncReadInputStream {
+        fn read(
+            &self,
+            buffer: &mut [u8],
+            cancellable: Option<&Cancellable>,
+        ) -> Result<usize, glib::Error> {
+            block_on(cancellable, |cx| self.poll_read(cx, buffer))
+        }
+
+        fn close(&self, _cancellable: Option<&Cancellable>) -> Result<(), glib::Error> {
+            let _ = self.read.take();
+            Ok(())
+        }
+    }
+
+    impl PollableInputStreamImpl for AsyncReadInputStream {
+        fn can_poll(&self) -> bool {
+            true
+        }
+
+        fn is_readable(&self) -> bool {
+            self.waker.is_ready()
+        }
+
+        fn create_source(&self, cancellable: Option<&Cancellable>) -> glib::Source {
+            self.waker
+                .create_source(self.obj().upcast_ref(), cancellable)
+        }
+
+        fn read_nonblocking(&self, buffer: &mut [u8]) -> Result<usize, glib::Error> {
+            self.waker.poll(|cx| self.poll_read(cx, buffer))
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// An [`InputStream`] reading from a [`futures_io::AsyncRead`].
+    ///
+    /// The stream implements [`PollableInputStream`], so asynchronous reads are driven by the
+    /// waker of the reader instead of a thread. Blocking reads park the calling thread until the
+    /// reader makes progress, which must then happen independently of that thread.
+    pub struct AsyncReadInputStream(ObjectSubclass<imp::AsyncReadInputStream>) @extends crate::InputStream, @implements crate::PollableInputStream;
+}
+
+impl AsyncReadInputStream {
+    pub fn new<R: AsyncRead + Send + 'static>(read: R) -> AsyncReadInputStream {
+        let obj: Self = glib::Object::new(&[]);
+
+        *obj.imp().read.borrow_mut() = Some(Box::pin(read));
+
+        obj
+    }
+}
+
+// Wakes up the sources of a pollable stream once the wrapped reader or writer is ready again.
+#[derive(Debug)]
+pub(crate) struct PollWaker(Arc<PollWakerInner>);
+
+#[derive(Debug)]
+struct PollWakerInner {
+    ready: AtomicBool,
+    // Cancelled whenever the stream becomes ready, used as the child source of the stream sources
+    cancellable: Cancellable,
+}
+
+impl Wake for PollWakerInner {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.ready.store(true, Ordering::SeqCst);
+        self.cancellable.cancel();
+    }
+}
+
+impl Default for PollWaker {
+    fn default() -> Self {
+        Self(Arc::new(PollWakerInner {
+            ready: AtomicBool::new(true),
+            cancellable: Cancellable::new(),
+        }))
+    }
+}
+
+impl PollWaker {
+    pub(crate) fn is_ready(&self) -> bool {
+        self.0.ready.load(Ordering::SeqCst)
+    }
+
+    // Polls once, mapping `Poll::Pending` to a `WouldBlock` error
+    pub(crate) fn poll<T>(
+        &self,
+        func: impl FnOnce(&mut Context) -> Poll<Result<T, glib::Error>>,
+    ) -> Result<T, glib::Error> {
+        if self.0.cancellable.is_cancelled() {
+            unsafe {
+                ffi::g_cancellable_reset(self.0.cancellable.to_glib_none().0);
+            }
+        }
+        self.0.ready.store(false, Ordering::SeqCst);
+
+        let waker = Waker::from(self.0.clone());
+        let mut cx = Context::from_waker(&waker);
+        match func(&mut cx) {
+            Poll::Ready(res) => {
+                self.0.ready.store(true, Ordering::SeqCst);
+                res
+            }
+            Poll::Pending => Err(glib::Error::new(
+                crate::IOErrorEnum::WouldBlock,
+                "Would Block",
+            )),
+        }
+    }
+
+    pub(crate) fn create_source(
+        &self,
+        stream: &glib::Object,
+        cancellable: Option<&Cancellable>,
+    ) -> glib::Source {
+        if self.is_ready() {
+            self.0.cancellable.cancel();
+        }
+
+        unsafe {
+            let child = ffi::g_cancellable_source_new(self.0.cancellable.to_glib_none().0);
+            let source = ffi::g_pollable_source_new_full(
+                stream.to_glib_none().0,
+                child,
+                cancellable.to_glib_none().0,
+            );
+            glib::ffi::g_source_unref(child);
+            from_glib_full(source)
+        }
+    }
+}
+
+// Polls until ready, parking the current thread in between
+pub(crate) fn block_on<T>(
+    cancellable: Option<&Cancellable>,
+    mut func: impl FnMut(&mut Context) -> Poll<Result<T, glib::Error>>,
+) -> Result<T, glib::Error> {
+    struct ThreadWaker(std::thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let thread = std::thread::current();
+    let waker = Waker::from(Arc::new(ThreadWaker(thread.clone())));
+    let mut cx = Context::from_waker(&waker);
+    let handler_id = cancellable.and_then(|c| c.connect_cancelled(move |_| thread.unpark()));
+
+    let res = loop {
+        if let Some(Err(err)) = cancellable.map(|c| c.set_error_if_cancelled()) {
+            break Err(err);
+        }
+        if let Poll::Ready(res) = func(&mut cx) {
+            break res;
+        }
+        std::thread::park();
+    };
+
+    if let (Some(cancellable), Some(handler_id)) = (cancellable, handler_id) {
+        cancellable.disconnect_cancelled(handler_id);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::run_async_local;
+
+    // Returns the data in chunks of up to two bytes, being pending before every chunk
+    struct Trickle {
+        data: Vec<u8>,
+        pos: usize,
+        pending: bool,
+    }
+
+    impl Trickle {
+        fn new(data: &[u8]) -> Self {
+            Self {
+                data: data.to_vec(),
+                pos: 0,
+                pending: true,
+            }
+        }
+    }
+
+    impl AsyncRead for Trickle {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context,
+            buf: &mut [u8],
+        ) -> Poll<std::io::Result<usize>> {
+            if self.pending {
+                self.pending = false;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.pending = true;
+            let len = buf.len().min(2).min(self.data.len() - self.pos);
+            buf[..len].copy_from_slice(&self.data[self.pos..self.pos + len]);
+            self.pos += len;
+            Poll::Ready(Ok(len))
+        }
+    }
+
+    #[test]
+    fn read() {
+        let stream = AsyncReadInputStream::new(Trickle::new(&[1, 2, 3, 4, 5]));
+
+        let mut buf = [0u8; 1024];
+        assert_eq!(stream.read(&mut buf[..], Cancellable::NONE), Ok(2));
+        assert_eq!(&buf[..2], &[1, 2]);
+
+        let stream = stream.upcast::<PollableInputStream>();
+        assert!(stream.can_poll());
+        assert!(stream.is_readable());
+        assert!(stream
+            .read_nonblocking(&mut buf[..], Cancellable::NONE)
+            .unwrap_err()
+            .matches(crate::IOErrorEnum::WouldBlock));
+        // The reader woke itself up
+        assert!(stream.is_readable());
+        assert_eq!(
+            stream.read_nonblocking(&mut buf[..], Cancellable::NONE),
+            Ok(2)
+        );
+        assert_eq!(&buf[..2], &[3, 4]);
+
+        let cancellable = Cancellable::new();
+        cancellable.cancel();
+        assert!(stream
+            .read(&mut buf[..], Some(&cancellable))
+            .unwrap_err()
+            .matches(crate::IOErrorEnum::Cancelled));
+
+        stream.close(Cancellable::NONE).unwrap();
+        assert!(stream
+            .read(&mut buf[..], Cancellable::NONE)
+            .unwrap_err()
+            .matches(crate::IOErrorEnum::Closed));
+    }
+
+    #[test]
+    fn read_async() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        let reader = Trickle::new(&data);
+
+        let buf = run_async_local(move |tx, l| {
+            let c = glib::MainContext::ref_thread_default();
+            c.spawn_local(async move {
+                let stream = AsyncReadInputStream::new(reader);
+                let (buf, len, err) = stream
+                    .read_all_future(vec![0; 1024], glib::PRIORITY_DEFAULT)
+                    .await
+                    .unwrap();
+                assert!(err.is_none());
+                tx.send(buf[..len].to_vec()).unwrap();
+                l.quit();
+            });
+        });
+
+        assert_eq!(buf, data);
+    }
+}
@@ -0,0 +1,235 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_io::AsyncWrite;
+
+use crate::{
+    async_read_input_stream::{block_on, PollWaker},
+    prelude::*,
+    read_input_stream::std_error_to_gio_error,
+    subclass::prelude::*,
+    Cancellable, OutputStream, PollableOutputStream,
+};
+
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    #[derive(Default)]
+    pub struct AsyncWriteOutputStream {
+        pub(super) write: RefCell<Option<Pin<Box<dyn AsyncWrite + Send + 'static>>>>,
+        pub(super) waker: PollWaker,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for AsyncWriteOutputStream {
+        const NAME: &'static str = "AsyncWriteOutputStream";
+        type Type = super::AsyncWriteOutputStream;
+        type ParentType = OutputStream;
+        type Interfaces = (PollableOutputStream,);
+    }
+
+    impl ObjectImpl for AsyncWriteOutputStream {}
+
+    impl AsyncWriteOutputStream {
+        fn poll_with<T>(
+            &self,
+            cx: &mut Context,
+            func: impl Fn(
+                Pin<&mut (dyn AsyncWrite + Send + 'static)>,
+                &mut Context,
+            ) -> Poll<std::io::Result<T>>,
+        ) -> Poll<Result<T, glib::Error>> {
+            let mut write = self.write.borrow_mut();
+            let write = match *write {
+                None => {
+                    return Poll::Ready(Err(glib::Error::new(
+                        crate::IOErrorEnum::Closed,
+                        "Already closed",
+                    )));
+                }
+                Some(ref mut write) => write,
+            };
+
+            loop {
+                match func(write.as_mut(), cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(res) => match std_error_to_gio_error(res) {
+                        None => continue,
+                        Some(res) => return Poll::Ready(res),
+                    },
+                }
+            }
+        }
+    }
+
+    impl OutputStreamImpl for AsyncWriteOutputStream {
+        fn write(
+            &self,
+            buffer: &[u8],
+            cancellable: Option<&Cancellable>,
+        ) -> Result<usize, glib::Error> {
+            block_on(cancellable, |cx| {
+                self.poll_with(cx, |write, cx| write.poll_write(cx, buffer))
+            })
+        }
+
+        fn close(&self, cancellable: Option<&Cancellable>) -> Result<(), glib::Error> {
+            let res = block_on(cancellable, |cx| {
+                self.poll_with(cx, |write, cx| write.poll_close(cx))
+            });
+            let _ = self.write.take();
+            res
+        }
+
+        fn flush(&self, cancellable: Option<&Cancellable>) -> Result<(), glib::Error> {
+            block_on(cancellable, |cx| {
+                self.poll_with(cx, |write, cx| write.poll_flush(cx))
+            })
+        }
+    }
+
+    impl PollableOutputStreamImpl for AsyncWriteOutputStream {
+        fn can_poll(&self) -> bool {
+            true
+        }
+
+        fn is_writable(&self) -> bool {
+            self.waker.is_ready()
+        }
+
+        fn create_source(&self, cancellable: Option<&Cancellable>) -> glib::Source {
+            self.waker
+                .create_source(self.obj().upcast_ref(), cancellable)
+        }
+
+        fn write_nonblocking(&self, buffer: &[u8]) -> Result<usize, glib::Error> {
+            self.waker
+                .poll(|cx| self.poll_with(cx, |write, cx| write.poll_write(cx, buffer)))
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// An [`OutputStream`] writing to a [`futures_io::AsyncWrite`].
+    ///
+    /// The stream implements [`PollableOutputStream`], so asynchronous writes are driven by the
+    /// waker of the writer instead of a thread. Blocking writes, flushes and closes park the
+    /// calling thread until the writer makes progress, which must then happen independently of
+    /// that thread.
+    pub struct AsyncWriteOutputStream(ObjectSubclass<imp::AsyncWriteOutputStream>) @extends crate::OutputStream, @implements crate::PollableOutputStream;
+}
+
+impl AsyncWriteOutputStream {
+    pub fn new<W: AsyncWrite + Send + 'static>(write: W) -> AsyncWriteOutputStream {
+        let obj: Self = glib::Object::new(&[]);
+
+        *obj.imp().write.borrow_mut() = Some(Box::pin(write));
+
+        obj
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::test_util::run_async_local;
+
+    // Accepts up to two bytes per write, being pending before every write
+    #[derive(Default)]
+    struct Trickle {
+        data: Arc<Mutex<Vec<u8>>>,
+        closed: Arc<Mutex<bool>>,
+        pending: bool,
+    }
+
+    impl AsyncWrite for Trickle {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context,
+            buf: &[u8],
+        ) -> Poll<std::io::Result<usize>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            let len = buf.len().min(2);
+            self.data.lock().unwrap().extend_from_slice(&buf[..len]);
+            Poll::Ready(Ok(len))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<std::io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<std::io::Result<()>> {
+            *self.closed.lock().unwrap() = true;
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn write() {
+        let writer = Trickle::default();
+        let (data, closed) = (writer.data.clone(), writer.closed.clone());
+        let stream = AsyncWriteOutputStream::new(writer);
+
+        assert_eq!(stream.write(&[1, 2, 3], Cancellable::NONE), Ok(2));
+
+        let stream = stream.upcast::<PollableOutputStream>();
+        assert!(stream.can_poll());
+        assert!(stream.is_writable());
+        assert!(stream
+            .write_nonblocking(&[3], Cancellable::NONE)
+            .unwrap_err()
+            .matches(crate::IOErrorEnum::WouldBlock));
+        // The writer woke itself up
+        assert!(stream.is_writable());
+        assert_eq!(stream.write_nonblocking(&[3], Cancellable::NONE), Ok(1));
+
+        stream.flush(Cancellable::NONE).unwrap();
+        stream.close(Cancellable::NONE).unwrap();
+        assert!(*closed.lock().unwrap());
+        assert_eq!(*data.lock().unwrap(), [1, 2, 3]);
+        assert!(stream
+            .write(&[4], Cancellable::NONE)
+            .unwrap_err()
+            .matches(crate::IOErrorEnum::Closed));
+    }
+
+    #[test]
+    fn write_async() {
+        let writer = Trickle::default();
+        let (data, closed) = (writer.data.clone(), writer.closed.clone());
+
+        let expected = (0..=255).collect::<Vec<u8>>();
+        let buf = expected.clone();
+        run_async_local(move |tx, l| {
+            let c = glib::MainContext::ref_thread_default();
+            c.spawn_local(async move {
+                let stream = AsyncWriteOutputStream::new(writer);
+                let (_, len, err) = stream
+                    .write_all_future(buf, glib::PRIORITY_DEFAULT)
+                    .await
+                    .unwrap();
+                assert_eq!(len, 256);
+                assert!(err.is_none());
+                stream.close_future(glib::PRIORITY_DEFAULT).await.unwrap();
+                tx.send(()).unwrap();
+                l.quit();
+            });
+        });
+
+        assert!(*closed.lock().unwrap());
+        assert_eq!(*data.lock().unwrap(), expected);
+    }
+}
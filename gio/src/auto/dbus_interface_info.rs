@@ -17,11 +17,6 @@ glib::wrapper! {
 }
 
 impl DBusInterfaceInfo {
-    //#[doc(alias = "g_dbus_interface_info_generate_xml")]
-    //pub fn generate_xml(&self, indent: u32, string_builder: /*Ignored*/&mut glib::String) {
-    //    unsafe { TODO: call ffi:g_dbus_interface_info_generate_xml() }
-    //}
-
     #[doc(alias = "g_dbus_interface_info_lookup_method")]
     pub fn lookup_method(&self, name: &str) -> Option<DBusMethodInfo> {
         unsafe {
@@ -907,6 +907,162 @@ impl From<DBusMessageFlags> for glib::Value {
     }
 }
 
+bitflags! {
+    #[doc(alias = "GDBusObjectManagerClientFlags")]
+    pub struct DBusObjectManagerClientFlags: u32 {
+        #[doc(alias = "G_DBUS_OBJECT_MANAGER_CLIENT_FLAGS_NONE")]
+        const NONE = ffi::G_DBUS_OBJECT_MANAGER_CLIENT_FLAGS_NONE as _;
+        #[doc(alias = "G_DBUS_OBJECT_MANAGER_CLIENT_FLAGS_DO_NOT_AUTO_START")]
+        const DO_NOT_AUTO_START = ffi::G_DBUS_OBJECT_MANAGER_CLIENT_FLAGS_DO_NOT_AUTO_START as _;
+    }
+}
+
+impl fmt::Display for DBusObjectManagerClientFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <Self as fmt::Debug>::fmt(self, f)
+    }
+}
+
+#[doc(hidden)]
+impl IntoGlib for DBusObjectManagerClientFlags {
+    type GlibType = ffi::GDBusObjectManagerClientFlags;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GDBusObjectManagerClientFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GDBusObjectManagerClientFlags> for DBusObjectManagerClientFlags {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GDBusObjectManagerClientFlags) -> Self {
+        Self::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for DBusObjectManagerClientFlags {
+    #[inline]
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::g_dbus_object_manager_client_flags_get_type()) }
+    }
+}
+
+impl glib::value::ValueType for DBusObjectManagerClientFlags {
+    type Type = Self;
+}
+
+unsafe impl<'a> FromValue<'a> for DBusObjectManagerClientFlags {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        from_glib(glib::gobject_ffi::g_value_get_flags(value.to_glib_none().0))
+    }
+}
+
+impl ToValue for DBusObjectManagerClientFlags {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<DBusObjectManagerClientFlags> for glib::Value {
+    #[inline]
+    fn from(v: DBusObjectManagerClientFlags) -> Self {
+        ToValue::to_value(&v)
+    }
+}
+
+bitflags! {
+    #[doc(alias = "GDBusPropertyInfoFlags")]
+    pub struct DBusPropertyInfoFlags: u32 {
+        #[doc(alias = "G_DBUS_PROPERTY_INFO_FLAGS_NONE")]
+        const NONE = ffi::G_DBUS_PROPERTY_INFO_FLAGS_NONE as _;
+        #[doc(alias = "G_DBUS_PROPERTY_INFO_FLAGS_READABLE")]
+        const READABLE = ffi::G_DBUS_PROPERTY_INFO_FLAGS_READABLE as _;
+        #[doc(alias = "G_DBUS_PROPERTY_INFO_FLAGS_WRITABLE")]
+        const WRITABLE = ffi::G_DBUS_PROPERTY_INFO_FLAGS_WRITABLE as _;
+    }
+}
+
+impl fmt::Display for DBusPropertyInfoFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <Self as fmt::Debug>::fmt(self, f)
+    }
+}
+
+#[doc(hidden)]
+impl IntoGlib for DBusPropertyInfoFlags {
+    type GlibType = ffi::GDBusPropertyInfoFlags;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GDBusPropertyInfoFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GDBusPropertyInfoFlags> for DBusPropertyInfoFlags {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GDBusPropertyInfoFlags) -> Self {
+        Self::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for DBusPropertyInfoFlags {
+    #[inline]
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::g_dbus_property_info_flags_get_type()) }
+    }
+}
+
+impl glib::value::ValueType for DBusPropertyInfoFlags {
+    type Type = Self;
+}
+
+unsafe impl<'a> FromValue<'a> for DBusPropertyInfoFlags {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        from_glib(glib::gobject_ffi::g_value_get_flags(value.to_glib_none().0))
+    }
+}
+
+impl ToValue for DBusPropertyInfoFlags {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<DBusPropertyInfoFlags> for glib::Value {
+    #[inline]
+    fn from(v: DBusPropertyInfoFlags) -> Self {
+        ToValue::to_value(&v)
+    }
+}
+
 bitflags! {
     #[doc(alias = "GDBusProxyFlags")]
     pub struct DBusProxyFlags: u32 {
@@ -2460,6 +2616,81 @@ impl From<SubprocessFlags> for glib::Value {
     }
 }
 
+bitflags! {
+    #[doc(alias = "GTestDBusFlags")]
+    pub struct TestDBusFlags: u32 {
+        #[doc(alias = "G_TEST_DBUS_NONE")]
+        const NONE = ffi::G_TEST_DBUS_NONE as _;
+    }
+}
+
+impl fmt::Display for TestDBusFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        <Self as fmt::Debug>::fmt(self, f)
+    }
+}
+
+#[doc(hidden)]
+impl IntoGlib for TestDBusFlags {
+    type GlibType = ffi::GTestDBusFlags;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GTestDBusFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GTestDBusFlags> for TestDBusFlags {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GTestDBusFlags) -> Self {
+        Self::from_bits_truncate(value)
+    }
+}
+
+impl StaticType for TestDBusFlags {
+    #[inline]
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::g_test_dbus_flags_get_type()) }
+    }
+}
+
+impl glib::value::ValueType for TestDBusFlags {
+    type Type = Self;
+}
+
+unsafe impl<'a> FromValue<'a> for TestDBusFlags {
+    type Checker = glib::value::GenericValueTypeChecker<Self>;
+
+    #[inline]
+    unsafe fn from_value(value: &'a glib::Value) -> Self {
+        from_glib(glib::gobject_ffi::g_value_get_flags(value.to_glib_none().0))
+    }
+}
+
+impl ToValue for TestDBusFlags {
+    #[inline]
+    fn to_value(&self) -> glib::Value {
+        let mut value = glib::Value::for_value_type::<Self>();
+        unsafe {
+            glib::gobject_ffi::g_value_set_flags(value.to_glib_none_mut().0, self.into_glib());
+        }
+        value
+    }
+
+    #[inline]
+    fn value_type(&self) -> glib::Type {
+        Self::static_type()
+    }
+}
+
+impl From<TestDBusFlags> for glib::Value {
+    #[inline]
+    fn from(v: TestDBusFlags) -> Self {
+        ToValue::to_value(&v)
+    }
+}
+
 bitflags! {
     #[doc(alias = "GTlsCertificateFlags")]
     pub struct TlsCertificateFlags: u32 {
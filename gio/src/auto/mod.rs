@@ -86,6 +86,21 @@ pub use self::dbus_method_invocation::DBusMethodInvocation;
 mod dbus_object;
 pub use self::dbus_object::DBusObject;
 
+mod dbus_object_manager;
+pub use self::dbus_object_manager::DBusObjectManager;
+
+mod dbus_object_manager_client;
+pub use self::dbus_object_manager_client::DBusObjectManagerClient;
+
+mod dbus_object_manager_server;
+pub use self::dbus_object_manager_server::DBusObjectManagerServer;
+
+mod dbus_object_proxy;
+pub use self::dbus_object_proxy::DBusObjectProxy;
+
+mod dbus_object_skeleton;
+pub use self::dbus_object_skeleton::DBusObjectSkeleton;
+
 mod dbus_proxy;
 pub use self::dbus_proxy::DBusProxy;
 
@@ -337,6 +352,9 @@ pub use self::subprocess_launcher::SubprocessLauncher;
 mod tcp_connection;
 pub use self::tcp_connection::TcpConnection;
 
+mod test_dbus;
+pub use self::test_dbus::TestDBus;
+
 mod themed_icon;
 pub use self::themed_icon::ThemedIcon;
 
@@ -544,6 +562,8 @@ pub use self::flags::DBusCapabilityFlags;
 pub use self::flags::DBusConnectionFlags;
 pub use self::flags::DBusInterfaceSkeletonFlags;
 pub use self::flags::DBusMessageFlags;
+pub use self::flags::DBusObjectManagerClientFlags;
+pub use self::flags::DBusPropertyInfoFlags;
 pub use self::flags::DBusProxyFlags;
 pub use self::flags::DBusSendMessageFlags;
 pub use self::flags::DBusServerFlags;
@@ -565,6 +585,7 @@ pub use self::flags::ResolverNameLookupFlags;
 pub use self::flags::ResourceLookupFlags;
 pub use self::flags::SettingsBindFlags;
 pub use self::flags::SubprocessFlags;
+pub use self::flags::TestDBusFlags;
 pub use self::flags::TlsCertificateFlags;
 pub use self::flags::TlsDatabaseVerifyFlags;
 pub use self::flags::TlsPasswordFlags;
@@ -767,6 +788,11 @@ pub mod traits {
     pub use super::dbus_interface::DBusInterfaceExt;
     pub use super::dbus_interface_skeleton::DBusInterfaceSkeletonExt;
     pub use super::dbus_object::DBusObjectExt;
+    pub use super::dbus_object_manager::DBusObjectManagerExt;
+    pub use super::dbus_object_manager_client::DBusObjectManagerClientExt;
+    pub use super::dbus_object_manager_server::DBusObjectManagerServerExt;
+    pub use super::dbus_object_proxy::DBusObjectProxyExt;
+    pub use super::dbus_object_skeleton::DBusObjectSkeletonExt;
     pub use super::dbus_proxy::DBusProxyExt;
     #[cfg(any(feature = "v2_72", feature = "dox"))]
     #[cfg_attr(feature = "dox", doc(cfg(feature = "v2_72")))]
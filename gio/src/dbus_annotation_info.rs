@@ -0,0 +1,54 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Helpers for the introspection data builders. The fields of the introspection structs are public
+// in C and everything in them is freed with `g_free()` or the `*_unref()` functions, so they can
+// be filled directly from Rust.
+
+use std::{ffi::CStr, mem, ptr};
+
+use glib::translate::*;
+
+// Allocates a zeroed introspection struct. The caller has to set `ref_count` to 1.
+pub(crate) unsafe fn new_info<T>() -> *mut T {
+    glib::ffi::g_malloc0(mem::size_of::<T>()) as *mut T
+}
+
+pub(crate) unsafe fn borrow_str<'a>(ptr: *const libc::c_char) -> &'a str {
+    assert!(!ptr.is_null());
+    CStr::from_ptr(ptr).to_str().unwrap()
+}
+
+// Creates a `NULL`-terminated array of annotations, or `NULL` if there are none, like the parser.
+pub(crate) fn annotations_to_glib_full(
+    annotations: &[(String, String)],
+) -> *mut *mut ffi::GDBusAnnotationInfo {
+    if annotations.is_empty() {
+        return ptr::null_mut();
+    }
+
+    unsafe {
+        let array = glib::ffi::g_malloc0(
+            mem::size_of::<*mut ffi::GDBusAnnotationInfo>() * (annotations.len() + 1),
+        ) as *mut *mut ffi::GDBusAnnotationInfo;
+        for (i, (key, value)) in annotations.iter().enumerate() {
+            let info = new_info::<ffi::GDBusAnnotationInfo>();
+            (*info).ref_count = 1;
+            (*info).key = key.to_glib_full();
+            (*info).value = value.to_glib_full();
+            *array.add(i) = info;
+        }
+        array
+    }
+}
+
+pub(crate) unsafe fn lookup_annotation<'a>(
+    annotations: *mut *mut ffi::GDBusAnnotationInfo,
+    key: &str,
+) -> Option<&'a str> {
+    let value = ffi::g_dbus_annotation_info_lookup(annotations, key.to_glib_none().0);
+    if value.is_null() {
+        None
+    } else {
+        Some(borrow_str(value))
+    }
+}
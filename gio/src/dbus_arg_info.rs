@@ -0,0 +1,42 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{translate::*, VariantTy};
+
+use crate::{
+    dbus_annotation_info::{borrow_str, lookup_annotation, new_info},
+    DBusArgInfo,
+};
+
+impl DBusArgInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates introspection data for an argument `name` of type `signature`.
+    ///
+    /// Arguments are usually added directly with the builders of
+    /// [`DBusMethodInfo`](crate::DBusMethodInfo) and [`DBusSignalInfo`](crate::DBusSignalInfo).
+    pub fn new(name: &str, signature: &VariantTy) -> Self {
+        unsafe {
+            let info = new_info::<ffi::GDBusArgInfo>();
+            (*info).ref_count = 1;
+            (*info).name = name.to_glib_full();
+            (*info).signature = signature.as_str().to_glib_full();
+            from_glib_full(info)
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe { borrow_str((*self.as_ptr()).name) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the D-Bus signature of the argument.
+    pub fn signature(&self) -> &str {
+        unsafe { borrow_str((*self.as_ptr()).signature) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the annotation `key`, if any.
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, key: &str) -> Option<&str> {
+        unsafe { lookup_annotation((*self.as_ptr()).annotations, key) }
+    }
+}
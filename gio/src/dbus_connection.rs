@@ -1,12 +1,20 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::{boxed::Box as Box_, num::NonZeroU32};
+use std::{
+    boxed::Box as Box_,
+    collections::HashMap,
+    future::Future,
+    num::NonZeroU32,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
-use glib::{prelude::*, translate::*};
+use glib::{prelude::*, translate::*, SignalHandlerId};
 
 use crate::{
-    ActionGroup, DBusConnection, DBusInterfaceInfo, DBusMessage, DBusMethodInvocation,
-    DBusSignalFlags, MenuModel,
+    dbus_signal_stream::convert_parameters, prelude::*, ActionGroup, Cancellable,
+    CancellableFuture, DBusCallFlags, DBusConnection, DBusConnectionFlags, DBusInterfaceInfo,
+    DBusMessage, DBusMethodInvocation, DBusSignal, DBusSignalFlags, DBusSignalStream, MenuModel,
 };
 
 #[derive(Debug, Eq, PartialEq)]
@@ -52,12 +60,18 @@ impl DBusConnection {
                 interface_info.to_glib_none().0,
                 glib::Closure::new(move |args| {
                     let conn = args[0].get::<DBusConnection>().unwrap();
-                    let sender = args[1].get::<&str>().unwrap();
+                    // The sender is NULL on peer-to-peer connections
+                    let sender = args[1].get::<Option<&str>>().unwrap().unwrap_or_default();
                     let object_path = args[2].get::<&str>().unwrap();
                     let interface_name = args[3].get::<&str>().unwrap();
                     let method_name = args[4].get::<&str>().unwrap();
                     let parameters = args[5].get::<glib::Variant>().unwrap();
-                    let invocation = args[6].get::<DBusMethodInvocation>().unwrap();
+                    // The handler owns a reference to the invocation in addition to the one
+                    // held by the value, take it over instead of leaking it
+                    let invocation: DBusMethodInvocation = from_glib_full(
+                        glib::gobject_ffi::g_value_get_object(args[6].to_glib_none().0)
+                            as *mut ffi::GDBusMethodInvocation,
+                    );
                     method_call(
                         conn,
                         sender,
@@ -73,7 +87,8 @@ impl DBusConnection {
                 .0,
                 glib::Closure::new(move |args| {
                     let conn = args[0].get::<DBusConnection>().unwrap();
-                    let sender = args[1].get::<&str>().unwrap();
+                    // The sender is NULL on peer-to-peer connections
+                    let sender = args[1].get::<Option<&str>>().unwrap().unwrap_or_default();
                     let object_path = args[2].get::<&str>().unwrap();
                     let interface_name = args[3].get::<&str>().unwrap();
                     let property_name = args[4].get::<&str>().unwrap();
@@ -85,7 +100,8 @@ impl DBusConnection {
                 .0,
                 glib::Closure::new(move |args| {
                     let conn = args[0].get::<DBusConnection>().unwrap();
-                    let sender = args[1].get::<&str>().unwrap();
+                    // The sender is NULL on peer-to-peer connections
+                    let sender = args[1].get::<Option<&str>>().unwrap().unwrap_or_default();
                     let object_path = args[2].get::<&str>().unwrap();
                     let interface_name = args[3].get::<&str>().unwrap();
                     let property_name = args[4].get::<&str>().unwrap();
@@ -112,6 +128,127 @@ impl DBusConnection {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Registers an object at `object_path` whose method calls are handled asynchronously.
+    ///
+    /// This is like [`register_object`](Self::register_object) but `method_call` returns a
+    /// future that resolves to the reply of the method call. The future is spawned on the
+    /// thread-default main context of the calling thread, which is also the context all method
+    /// calls are dispatched on, and the method invocation is completed with its result once it
+    /// resolves. The reply must be a tuple containing the out arguments of the method, e.g. `()`
+    /// if there are none.
+    ///
+    /// The [`Cancellable`] passed to `method_call` is cancelled and the future is dropped without
+    /// replying if the caller disconnects from the bus or the connection is closed before the
+    /// future resolved.
+    #[doc(alias = "g_dbus_connection_register_object_with_closures")]
+    pub fn register_object_async<MethodCall, MethodCallFuture, GetProperty, SetProperty>(
+        &self,
+        object_path: &str,
+        interface_info: &DBusInterfaceInfo,
+        method_call: MethodCall,
+        get_property: GetProperty,
+        set_property: SetProperty,
+    ) -> Result<RegistrationId, glib::Error>
+    where
+        MethodCall: Fn(
+                DBusConnection,
+                &str,
+                &str,
+                &str,
+                &str,
+                glib::Variant,
+                Cancellable,
+            ) -> MethodCallFuture
+            + Send
+            + Sync
+            + 'static,
+        MethodCallFuture: Future<Output = Result<glib::Variant, glib::Error>> + 'static,
+        GetProperty:
+            Fn(DBusConnection, &str, &str, &str, &str) -> glib::Variant + Send + Sync + 'static,
+        SetProperty: Fn(DBusConnection, &str, &str, &str, &str, glib::Variant) -> bool
+            + Send
+            + Sync
+            + 'static,
+    {
+        let watches = CallerWatches::default();
+        self.register_object(
+            object_path,
+            interface_info,
+            move |connection,
+                  sender,
+                  object_path,
+                  interface_name,
+                  method_name,
+                  parameters,
+                  invocation| {
+                let cancellable = Cancellable::new();
+                let watch = CallerWatch::new(&connection, sender, &cancellable, &watches);
+                let future = method_call(
+                    connection,
+                    sender,
+                    object_path,
+                    interface_name,
+                    method_name,
+                    parameters,
+                    cancellable.clone(),
+                );
+
+                glib::MainContext::ref_thread_default().spawn_local(async move {
+                    let res = CancellableFuture::new(future, cancellable).await;
+                    drop(watch);
+                    match res {
+                        Ok(Ok(reply)) => invocation.return_value(Some(&reply)),
+                        Ok(Err(err)) => invocation.return_gerror(err),
+                        // The caller is gone, nobody is waiting for the reply anymore
+                        Err(_) => (),
+                    }
+                });
+            },
+            get_property,
+            set_property,
+        )
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates two peer-to-peer connections that are connected to each other over a socket pair.
+    ///
+    /// The first connection is the server side of the connection and the second one the client
+    /// side. No message bus is involved, so both connections have no unique name and messages have
+    /// no sender.
+    ///
+    /// This is useful for private communication with a helper process, by passing one end to the
+    /// other process, or for testing a D-Bus service and its clients in the same process.
+    #[cfg(any(unix, feature = "dox"))]
+    #[cfg_attr(feature = "dox", doc(cfg(unix)))]
+    pub fn new_pair_future() -> Pin<
+        Box_<dyn Future<Output = Result<(DBusConnection, DBusConnection), glib::Error>> + 'static>,
+    > {
+        Box_::pin(async move {
+            let (server, client) = std::os::unix::net::UnixStream::pair()
+                .map_err(|err| glib::Error::new(crate::IOErrorEnum::Failed, &err.to_string()))?;
+            let server = unsafe { crate::Socket::from_fd(server)? };
+            let client = unsafe { crate::Socket::from_fd(client)? };
+
+            let guid = crate::dbus_generate_guid();
+            let server = DBusConnection::new_future(
+                &server.connection_factory_create_connection(),
+                Some(&guid),
+                DBusConnectionFlags::AUTHENTICATION_SERVER,
+                None,
+            );
+            let client = DBusConnection::new_future(
+                &client.connection_factory_create_connection(),
+                None,
+                DBusConnectionFlags::AUTHENTICATION_CLIENT,
+                None,
+            );
+
+            // Both sides have to make progress for the authentication to succeed
+            futures_util::future::try_join(server, client).await
+        })
+    }
+
     #[doc(alias = "g_dbus_connection_unregister_object")]
     pub fn unregister_object(
         &self,
@@ -263,7 +400,8 @@ impl DBusConnection {
             user_data: glib::ffi::gpointer,
         ) {
             let connection = from_glib_borrow(connection);
-            let sender_name: Borrowed<glib::GString> = from_glib_borrow(sender_name);
+            // The sender is NULL on peer-to-peer connections
+            let sender_name: Borrowed<Option<glib::GString>> = from_glib_borrow(sender_name);
             let object_path: Borrowed<glib::GString> = from_glib_borrow(object_path);
             let interface_name: Borrowed<glib::GString> = from_glib_borrow(interface_name);
             let signal_name: Borrowed<glib::GString> = from_glib_borrow(signal_name);
@@ -271,7 +409,7 @@ impl DBusConnection {
             let callback: &P = &*(user_data as *mut _);
             (*callback)(
                 &connection,
-                sender_name.as_str(),
+                sender_name.as_ref().as_deref().unwrap_or_default(),
                 object_path.as_str(),
                 interface_name.as_str(),
                 signal_name.as_str(),
@@ -305,6 +443,46 @@ impl DBusConnection {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Subscribes to signals matching the given filters and returns them as a stream.
+    ///
+    /// The parameters of the signals are converted to `T`, which is usually a tuple. The
+    /// subscription is removed when the stream is dropped.
+    ///
+    /// The signals are received on the thread-default main context of the calling thread.
+    #[doc(alias = "g_dbus_connection_signal_subscribe")]
+    pub fn receive_signal<T: glib::FromVariant + Send + 'static>(
+        &self,
+        sender: Option<&str>,
+        interface_name: Option<&str>,
+        member: Option<&str>,
+        object_path: Option<&str>,
+        arg0: Option<&str>,
+        flags: DBusSignalFlags,
+    ) -> DBusSignalStream<DBusSignal<T>> {
+        DBusSignalStream::for_connection(self, |tx| {
+            self.signal_subscribe(
+                sender,
+                interface_name,
+                member,
+                object_path,
+                arg0,
+                flags,
+                move |_connection, sender, object_path, interface_name, signal_name, parameters| {
+                    if let Some(parameters) = convert_parameters(signal_name, parameters) {
+                        let _ = tx.unbounded_send(DBusSignal {
+                            sender: sender.to_owned(),
+                            object_path: object_path.to_owned(),
+                            interface_name: interface_name.to_owned(),
+                            signal_name: signal_name.to_owned(),
+                            parameters,
+                        });
+                    }
+                },
+            )
+        })
+    }
+
     #[doc(alias = "g_dbus_connection_signal_unsubscribe")]
     pub fn signal_unsubscribe(&self, subscription_id: SignalSubscriptionId) {
         unsafe {
@@ -315,3 +493,142 @@ impl DBusConnection {
         }
     }
 }
+
+// `NameOwnerChanged` subscriptions of the callers with pending method calls of a registered
+// object, shared by all calls of the same caller.
+type CallerWatches = Arc<Mutex<HashMap<String, CallerSubscription>>>;
+
+struct CallerSubscription {
+    subscription_id: SignalSubscriptionId,
+    // Cancellables of the pending method calls of the caller
+    cancellables: Arc<Mutex<Vec<Cancellable>>>,
+}
+
+// Cancels a method call once its caller disappeared from the bus or the connection was closed.
+struct CallerWatch {
+    connection: DBusConnection,
+    cancellable: Cancellable,
+    sender: Option<(String, CallerWatches)>,
+    closed_handler_id: Option<SignalHandlerId>,
+}
+
+impl CallerWatch {
+    fn new(
+        connection: &DBusConnection,
+        sender: &str,
+        cancellable: &Cancellable,
+        watches: &CallerWatches,
+    ) -> Self {
+        // Peer-to-peer connections have no sender and no message bus
+        let sender = if !sender.is_empty() && connection.unique_name().is_some() {
+            let mut subscriptions = watches.lock().unwrap();
+            let subscription = subscriptions.entry(sender.to_owned()).or_insert_with(|| {
+                let cancellables = Arc::new(Mutex::new(Vec::<Cancellable>::new()));
+                let subscription_id = connection.signal_subscribe(
+                    Some("org.freedesktop.DBus"),
+                    Some("org.freedesktop.DBus"),
+                    Some("NameOwnerChanged"),
+                    Some("/org/freedesktop/DBus"),
+                    Some(sender),
+                    DBusSignalFlags::NONE,
+                    {
+                        let cancellables = cancellables.clone();
+                        move |_connection,
+                              _sender,
+                              _object_path,
+                              _interface_name,
+                              _signal_name,
+                              parameters| {
+                            if let Some((_name, _old_owner, new_owner)) =
+                                parameters.get::<(String, String, String)>()
+                            {
+                                if new_owner.is_empty() {
+                                    let cancellables = cancellables.lock().unwrap().clone();
+                                    for cancellable in cancellables {
+                                        cancellable.cancel();
+                                    }
+                                }
+                            }
+                        }
+                    },
+                );
+
+                // The caller might have vanished before the subscription was added, the bus
+                // handles messages in order so afterwards the subscription catches it
+                connection.call(
+                    Some("org.freedesktop.DBus"),
+                    "/org/freedesktop/DBus",
+                    "org.freedesktop.DBus",
+                    "GetNameOwner",
+                    Some(&(sender,).to_variant()),
+                    None,
+                    DBusCallFlags::NONE,
+                    -1,
+                    Cancellable::NONE,
+                    {
+                        let cancellables = cancellables.clone();
+                        move |res| {
+                            if res.is_err() {
+                                let cancellables = cancellables.lock().unwrap().clone();
+                                for cancellable in cancellables {
+                                    cancellable.cancel();
+                                }
+                            }
+                        }
+                    },
+                );
+
+                CallerSubscription {
+                    subscription_id,
+                    cancellables,
+                }
+            });
+            subscription
+                .cancellables
+                .lock()
+                .unwrap()
+                .push(cancellable.clone());
+            Some((sender.to_owned(), watches.clone()))
+        } else {
+            None
+        };
+
+        let closed_handler_id = connection.connect_closed({
+            let cancellable = cancellable.clone();
+            move |_connection, _remote_peer_vanished, _error| {
+                cancellable.cancel();
+            }
+        });
+
+        Self {
+            connection: connection.clone(),
+            cancellable: cancellable.clone(),
+            sender,
+            closed_handler_id: Some(closed_handler_id),
+        }
+    }
+}
+
+impl Drop for CallerWatch {
+    fn drop(&mut self) {
+        if let Some((sender, watches)) = self.sender.take() {
+            let mut subscriptions = watches.lock().unwrap();
+            if let Some(subscription) = subscriptions.get(&sender) {
+                let mut cancellables = subscription.cancellables.lock().unwrap();
+                cancellables.retain(|cancellable| cancellable != &self.cancellable);
+                let unused = cancellables.is_empty();
+                drop(cancellables);
+
+                // The last pending call of the caller is done
+                if unused {
+                    let subscription = subscriptions.remove(&sender).unwrap();
+                    self.connection
+                        .signal_unsubscribe(subscription.subscription_id);
+                }
+            }
+        }
+        if let Some(closed_handler_id) = self.closed_handler_id.take() {
+            self.connection.disconnect(closed_handler_id);
+        }
+    }
+}
@@ -0,0 +1,210 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{collections::HashMap, rc::Rc};
+
+use glib::{prelude::*, thread_guard::ThreadGuard};
+
+use crate::{DBusConnection, DBusInterfaceInfo, DBusMethodInvocation, RegistrationId};
+
+// Wrapper for values that are only ever accessed from the thread they were created on, which is
+// the thread the object was registered on. GDBus calls all closures passed to
+// `register_object()` on the thread-default main context of that thread.
+struct LocalGuard<T>(ThreadGuard<T>);
+
+unsafe impl<T> Sync for LocalGuard<T> {}
+
+impl<T> LocalGuard<T> {
+    fn new(value: T) -> Self {
+        Self(ThreadGuard::new(value))
+    }
+
+    fn get_ref(&self) -> &T {
+        self.0.get_ref()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// An object that is exported on a [`DBusConnection`].
+///
+/// This is mostly used by the skeletons generated by [`dbus_interface`](crate::dbus_interface)
+/// but can also be used directly with an interface implementation that is not thread-safe.
+///
+/// The object is unregistered when this value is dropped.
+#[derive(Debug)]
+#[must_use = "The object is unregistered when the registration is dropped"]
+pub struct DBusObjectRegistration {
+    connection: DBusConnection,
+    object_path: String,
+    interface_name: String,
+    id: Option<RegistrationId>,
+}
+
+impl DBusObjectRegistration {
+    // rustdoc-stripper-ignore-next
+    /// Registers `imp` at `object_path` on `connection` for the interface `interface_info`.
+    ///
+    /// All callbacks are called on the thread-default main context of the calling thread, which
+    /// allows using an implementation that is not `Send` or `Sync`. `method_call` gets the
+    /// implementation as `Rc` so that it can be moved into a future for replying asynchronously.
+    ///
+    /// After `set_property` returned `true` the
+    /// `org.freedesktop.DBus.Properties.PropertiesChanged` signal is emitted with the new value.
+    #[doc(alias = "g_dbus_connection_register_object")]
+    pub fn register<T, M, G, S>(
+        connection: &DBusConnection,
+        object_path: &str,
+        interface_info: &DBusInterfaceInfo,
+        imp: T,
+        method_call: M,
+        get_property: G,
+        set_property: S,
+    ) -> Result<Self, glib::Error>
+    where
+        T: 'static,
+        M: Fn(&Rc<T>, &str, glib::Variant, DBusMethodInvocation) + 'static,
+        G: Fn(&T, &str) -> glib::Variant + 'static,
+        S: Fn(&T, &str, glib::Variant) -> bool + 'static,
+    {
+        let imp = Rc::new(imp);
+        let method_call = LocalGuard::new((imp.clone(), method_call));
+        let get_property = LocalGuard::new((imp.clone(), get_property));
+        let set_property = LocalGuard::new((imp, set_property));
+
+        let id = connection.register_object(
+            object_path,
+            interface_info,
+            move |_connection,
+                  _sender,
+                  _object_path,
+                  _interface_name,
+                  method_name,
+                  parameters,
+                  invocation| {
+                let (imp, method_call) = method_call.get_ref();
+                method_call(imp, method_name, parameters, invocation);
+            },
+            move |_connection, _sender, _object_path, _interface_name, property_name| {
+                let (imp, get_property) = get_property.get_ref();
+                get_property(imp, property_name)
+            },
+            move |connection, _sender, object_path, interface_name, property_name, value| {
+                let (imp, set_property) = set_property.get_ref();
+                if !set_property(imp, property_name, value.clone()) {
+                    return false;
+                }
+                if let Err(err) = emit_property_changed(
+                    &connection,
+                    object_path,
+                    interface_name,
+                    property_name,
+                    value,
+                ) {
+                    glib::g_warning!(
+                        "GIO-Rust",
+                        "Failed to emit PropertiesChanged for {}: {}",
+                        property_name,
+                        err
+                    );
+                }
+                true
+            },
+        )?;
+
+        Ok(Self {
+            connection: connection.clone(),
+            object_path: object_path.to_owned(),
+            interface_name: interface_info.name().to_owned(),
+            id: Some(id),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the connection the object is registered on.
+    pub fn connection(&self) -> &DBusConnection {
+        &self.connection
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the path the object is registered at.
+    pub fn object_path(&self) -> &str {
+        &self.object_path
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the name of the registered interface.
+    pub fn interface_name(&self) -> &str {
+        &self.interface_name
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Emits the signal `signal_name` of the registered interface from the object.
+    #[doc(alias = "g_dbus_connection_emit_signal")]
+    pub fn emit_signal(
+        &self,
+        signal_name: &str,
+        parameters: Option<&glib::Variant>,
+    ) -> Result<(), glib::Error> {
+        self.connection.emit_signal(
+            None,
+            &self.object_path,
+            &self.interface_name,
+            signal_name,
+            parameters,
+        )
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Emits `org.freedesktop.DBus.Properties.PropertiesChanged` for the property
+    /// `property_name` of the registered interface with its new `value`.
+    pub fn emit_property_changed(
+        &self,
+        property_name: &str,
+        value: glib::Variant,
+    ) -> Result<(), glib::Error> {
+        emit_property_changed(
+            &self.connection,
+            &self.object_path,
+            &self.interface_name,
+            property_name,
+            value,
+        )
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Unregisters the object.
+    #[doc(alias = "g_dbus_connection_unregister_object")]
+    pub fn unregister(mut self) -> Result<(), glib::BoolError> {
+        let id = self.id.take().unwrap();
+        self.connection.unregister_object(id)
+    }
+}
+
+impl Drop for DBusObjectRegistration {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            let _ = self.connection.unregister_object(id);
+        }
+    }
+}
+
+fn emit_property_changed(
+    connection: &DBusConnection,
+    object_path: &str,
+    interface_name: &str,
+    property_name: &str,
+    value: glib::Variant,
+) -> Result<(), glib::Error> {
+    let changed = HashMap::from([(property_name.to_owned(), value)]);
+    let parameters = glib::Variant::tuple_from_iter([
+        interface_name.to_variant(),
+        changed.to_variant(),
+        Vec::<String>::new().to_variant(),
+    ]);
+    connection.emit_signal(
+        None,
+        object_path,
+        "org.freedesktop.DBus.Properties",
+        "PropertiesChanged",
+        Some(&parameters),
+    )
+}
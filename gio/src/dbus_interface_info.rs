@@ -0,0 +1,144 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ffi::CStr;
+
+use glib::{collections::PtrSlice, translate::*};
+
+use crate::{
+    dbus_annotation_info::{annotations_to_glib_full, lookup_annotation, new_info},
+    dbus_introspection::assert_interface_name,
+    DBusInterfaceInfo, DBusMethodInfo, DBusPropertyInfo, DBusSignalInfo,
+};
+
+impl DBusInterfaceInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new builder for the introspection data of the interface `name`.
+    ///
+    /// ```
+    /// let info = gio::DBusInterfaceInfo::builder("org.gtk_rs.Example")
+    ///     .method(
+    ///         gio::DBusMethodInfo::builder("Hello")
+    ///             .in_arg::<String>("name")
+    ///             .out_arg::<String>("greeting")
+    ///             .build(),
+    ///     )
+    ///     .signal(gio::DBusSignalInfo::builder("Greeted").arg::<String>("name").build())
+    ///     .property(gio::DBusPropertyInfo::builder::<u32>("Count").build())
+    ///     .build();
+    ///
+    /// assert!(info.lookup_method("Hello").is_some());
+    /// ```
+    pub fn builder(name: &str) -> DBusInterfaceInfoBuilder {
+        DBusInterfaceInfoBuilder::new(name)
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let name = (*c_obj).name;
+            assert!(!name.is_null());
+            let c_str = CStr::from_ptr(name);
+            c_str.to_str().unwrap()
+        }
+    }
+
+    pub fn methods(&self) -> &[DBusMethodInfo] {
+        unsafe { PtrSlice::from_glib_borrow((*self.as_ptr()).methods) }
+    }
+
+    pub fn signals(&self) -> &[DBusSignalInfo] {
+        unsafe { PtrSlice::from_glib_borrow((*self.as_ptr()).signals) }
+    }
+
+    pub fn properties(&self) -> &[DBusPropertyInfo] {
+        unsafe { PtrSlice::from_glib_borrow((*self.as_ptr()).properties) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the annotation `key`, if any.
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, key: &str) -> Option<&str> {
+        unsafe { lookup_annotation((*self.as_ptr()).annotations, key) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Serializes the interface to an `<interface>` element of introspection XML, indented by
+    /// `indent` spaces.
+    #[doc(alias = "g_dbus_interface_info_generate_xml")]
+    pub fn generate_xml(&self, indent: u32) -> glib::GString {
+        let mut xml = glib::GStringBuilder::default();
+        unsafe {
+            ffi::g_dbus_interface_info_generate_xml(
+                self.to_glib_none().0,
+                indent,
+                xml.to_glib_none_mut().0,
+            );
+        }
+        xml.into_string()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`DBusInterfaceInfo`].
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusInterfaceInfoBuilder {
+    name: String,
+    methods: Vec<DBusMethodInfo>,
+    signals: Vec<DBusSignalInfo>,
+    properties: Vec<DBusPropertyInfo>,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusInterfaceInfoBuilder {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            methods: Vec::new(),
+            signals: Vec::new(),
+            properties: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    pub fn method(mut self, method: DBusMethodInfo) -> Self {
+        self.methods.push(method);
+        self
+    }
+
+    pub fn signal(mut self, signal: DBusSignalInfo) -> Self {
+        self.signals.push(signal);
+        self
+    }
+
+    pub fn property(mut self, property: DBusPropertyInfo) -> Self {
+        self.properties.push(property);
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the interface.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name of the interface is not a valid D-Bus interface name.
+    pub fn build(self) -> DBusInterfaceInfo {
+        assert_interface_name(&self.name);
+
+        unsafe {
+            let info = new_info::<ffi::GDBusInterfaceInfo>();
+            (*info).ref_count = 1;
+            (*info).name = self.name.to_glib_full();
+            (*info).methods = PtrSlice::from(self.methods).into_raw();
+            (*info).signals = PtrSlice::from(self.signals).into_raw();
+            (*info).properties = PtrSlice::from(self.properties).into_raw();
+            (*info).annotations = annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
+}
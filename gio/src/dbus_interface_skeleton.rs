@@ -0,0 +1,88 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::collections::HashMap;
+
+use glib::{prelude::*, Variant};
+
+use crate::{prelude::*, DBusInterfaceSkeleton};
+
+pub trait DBusInterfaceSkeletonExtManual {
+    // rustdoc-stripper-ignore-next
+    /// Emits the signal `signal_name` of the interface on all connections the skeleton is
+    /// exported on.
+    #[doc(alias = "g_dbus_connection_emit_signal")]
+    fn emit_signal(
+        &self,
+        signal_name: &str,
+        parameters: Option<&Variant>,
+    ) -> Result<(), glib::Error>;
+
+    // rustdoc-stripper-ignore-next
+    /// Emits `org.freedesktop.DBus.Properties.PropertiesChanged` with the new values of the
+    /// `changed` properties and the names of the `invalidated` ones on all connections the
+    /// skeleton is exported on.
+    ///
+    /// Nothing is emitted if both are empty.
+    fn emit_properties_changed(
+        &self,
+        changed: &[(&str, Variant)],
+        invalidated: &[&str],
+    ) -> Result<(), glib::Error>;
+}
+
+impl<O: IsA<DBusInterfaceSkeleton>> DBusInterfaceSkeletonExtManual for O {
+    fn emit_signal(
+        &self,
+        signal_name: &str,
+        parameters: Option<&Variant>,
+    ) -> Result<(), glib::Error> {
+        let skeleton = self.as_ref();
+        let object_path = match skeleton.object_path() {
+            Some(object_path) => object_path,
+            None => return Ok(()),
+        };
+        let info = DBusInterfaceSkeletonExt::info(skeleton);
+
+        for connection in skeleton.connections() {
+            connection.emit_signal(None, &object_path, info.name(), signal_name, parameters)?;
+        }
+        Ok(())
+    }
+
+    fn emit_properties_changed(
+        &self,
+        changed: &[(&str, Variant)],
+        invalidated: &[&str],
+    ) -> Result<(), glib::Error> {
+        if changed.is_empty() && invalidated.is_empty() {
+            return Ok(());
+        }
+
+        let skeleton = self.as_ref();
+        let object_path = match skeleton.object_path() {
+            Some(object_path) => object_path,
+            None => return Ok(()),
+        };
+        let info = DBusInterfaceSkeletonExt::info(skeleton);
+
+        let changed = changed
+            .iter()
+            .map(|(name, value)| (*name, value.clone()))
+            .collect::<HashMap<_, _>>();
+        let parameters = Variant::tuple_from_iter([
+            info.name().to_variant(),
+            changed.to_variant(),
+            invalidated.to_variant(),
+        ]);
+        for connection in skeleton.connections() {
+            connection.emit_signal(
+                None,
+                &object_path,
+                "org.freedesktop.DBus.Properties",
+                "PropertiesChanged",
+                Some(&parameters),
+            )?;
+        }
+        Ok(())
+    }
+}
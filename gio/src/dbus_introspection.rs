@@ -0,0 +1,49 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Checks of the names and types used by the introspection data builders. The builders fill in the
+// introspection structs directly, so GDBus never validates them.
+
+use crate::DBusArgInfo;
+
+// Panics if `name` is not a valid D-Bus interface name.
+pub(crate) fn assert_interface_name(name: &str) {
+    assert!(
+        crate::dbus_is_interface_name(name),
+        "Invalid D-Bus interface name {name:?}"
+    );
+}
+
+// Panics if `path` is neither an object path nor a valid path relative to the parent node.
+pub(crate) fn assert_node_path(path: &str) {
+    let valid = if path.starts_with('/') {
+        glib::Variant::is_object_path(path)
+    } else {
+        glib::Variant::is_object_path(&format!("/{path}"))
+    };
+    assert!(valid, "Invalid D-Bus node path {path:?}");
+}
+
+// Panics if `name` is not a valid name for the D-Bus member, which is described by `member`.
+pub(crate) fn assert_member_name(member: &str, name: &str) {
+    assert!(
+        crate::dbus_is_member_name(name),
+        "Invalid D-Bus name {name:?} of {member}"
+    );
+}
+
+// Panics if `signature` is not a D-Bus type, e.g. a maybe type, which can't be sent over D-Bus.
+pub(crate) fn assert_signature(member: &str, signature: &str) {
+    assert!(
+        glib::Variant::is_signature(signature),
+        "Type {signature} of {member} is not a D-Bus type"
+    );
+}
+
+// Panics if the names or types of the arguments of `member` are invalid.
+pub(crate) fn assert_args(member: &str, args: &[DBusArgInfo]) {
+    for (i, arg) in args.iter().enumerate() {
+        let name = format!("argument {i} of {member}");
+        assert_member_name(&name, arg.name());
+        assert_signature(&name, arg.signature());
+    }
+}
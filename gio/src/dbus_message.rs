@@ -1,11 +1,491 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
-use crate::DBusMessage;
+use glib::{
+    prelude::*,
+    translate::*,
+    variant::{ObjectPath, Signature},
+    Variant, VariantTy, VariantType,
+};
+
+use crate::{
+    DBusMessage, DBusMessageByteOrder, DBusMessageFlags, DBusMessageHeaderField, DBusMessageType,
+    IOErrorEnum,
+};
+
+impl DBusMessage {
+    #[doc(alias = "g_dbus_message_get_header_fields")]
+    #[doc(alias = "get_header_fields")]
+    pub fn header_fields(&self) -> Vec<DBusMessageHeaderField> {
+        unsafe {
+            let fields = ffi::g_dbus_message_get_header_fields(self.to_glib_none().0);
+            let mut ret = Vec::new();
+            let mut field = fields;
+            while *field != ffi::G_DBUS_MESSAGE_HEADER_FIELD_INVALID as u8 {
+                ret.push(from_glib(*field as ffi::GDBusMessageHeaderField));
+                field = field.add(1);
+            }
+            glib::ffi::g_free(fields as *mut _);
+            ret
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all header fields of the message with their typed values.
+    pub fn headers(&self) -> BTreeMap<DBusMessageHeaderField, DBusMessageHeader> {
+        self.header_fields()
+            .into_iter()
+            .filter_map(|field| Some((field, self.typed_header(field)?)))
+            .collect()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the typed value of the header field `header_field`, if it is set.
+    #[doc(alias = "g_dbus_message_get_header")]
+    pub fn typed_header(&self, header_field: DBusMessageHeaderField) -> Option<DBusMessageHeader> {
+        self.header(header_field)
+            .map(|value| DBusMessageHeader::from_variant(header_field, value))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the header field corresponding to `header`.
+    #[doc(alias = "g_dbus_message_set_header")]
+    pub fn set_typed_header(&self, header: &DBusMessageHeader) {
+        self.set_header(header.field(), Some(&header.to_variant()));
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a method call of `method` on the object at `path`.
+    pub fn method_call_builder(path: &str, method: &str) -> DBusMessageBuilder {
+        DBusMessageBuilder::new(Kind::MethodCall {
+            path: path.to_owned(),
+            method: method.to_owned(),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for the signal `signal` of `interface` emitted by the object at `path`.
+    pub fn signal_builder(path: &str, interface: &str, signal: &str) -> DBusMessageBuilder {
+        DBusMessageBuilder::new(Kind::Signal {
+            path: path.to_owned(),
+            interface: interface.to_owned(),
+            signal: signal.to_owned(),
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a successful reply to the method call `self`.
+    #[doc(alias = "g_dbus_message_new_method_reply")]
+    pub fn method_reply_builder(&self) -> DBusMessageBuilder {
+        DBusMessageBuilder::new(Kind::Reply { call: self.clone() })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for an error reply with `error_name` to the method call `self`.
+    ///
+    /// The body of the message defaults to `error_message`.
+    #[doc(alias = "g_dbus_message_new_method_error_literal")]
+    pub fn method_error_builder(
+        &self,
+        error_name: &str,
+        error_message: &str,
+    ) -> DBusMessageBuilder {
+        DBusMessageBuilder::new(Kind::Error {
+            call: self.clone(),
+            error_name: error_name.to_owned(),
+            error_message: error_message.to_owned(),
+        })
+    }
+}
 
 impl fmt::Display for DBusMessage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.print(0))
     }
 }
+
+// rustdoc-stripper-ignore-next
+/// A typed D-Bus message header field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DBusMessageHeader {
+    Path(ObjectPath),
+    Interface(String),
+    Member(String),
+    ErrorName(String),
+    ReplySerial(u32),
+    Destination(String),
+    Sender(String),
+    Signature(Signature),
+    NumUnixFds(u32),
+    // rustdoc-stripper-ignore-next
+    /// A header field that is unknown or has a value of an unexpected type.
+    Other(DBusMessageHeaderField, Variant),
+}
+
+impl DBusMessageHeader {
+    // rustdoc-stripper-ignore-next
+    /// Converts the raw `value` of the header field `field`.
+    ///
+    /// Values that don't have the type required by the D-Bus specification are returned as
+    /// [`DBusMessageHeader::Other`].
+    pub fn from_variant(field: DBusMessageHeaderField, value: Variant) -> Self {
+        let header = match field {
+            DBusMessageHeaderField::Path => value.get().map(Self::Path),
+            DBusMessageHeaderField::Interface => value.get().map(Self::Interface),
+            DBusMessageHeaderField::Member => value.get().map(Self::Member),
+            DBusMessageHeaderField::ErrorName => value.get().map(Self::ErrorName),
+            DBusMessageHeaderField::ReplySerial => value.get().map(Self::ReplySerial),
+            DBusMessageHeaderField::Destination => value.get().map(Self::Destination),
+            DBusMessageHeaderField::Sender => value.get().map(Self::Sender),
+            DBusMessageHeaderField::Signature => value.get().map(Self::Signature),
+            DBusMessageHeaderField::NumUnixFds => value.get().map(Self::NumUnixFds),
+            _ => None,
+        };
+        header.unwrap_or(Self::Other(field, value))
+    }
+
+    pub fn field(&self) -> DBusMessageHeaderField {
+        match self {
+            Self::Path(_) => DBusMessageHeaderField::Path,
+            Self::Interface(_) => DBusMessageHeaderField::Interface,
+            Self::Member(_) => DBusMessageHeaderField::Member,
+            Self::ErrorName(_) => DBusMessageHeaderField::ErrorName,
+            Self::ReplySerial(_) => DBusMessageHeaderField::ReplySerial,
+            Self::Destination(_) => DBusMessageHeaderField::Destination,
+            Self::Sender(_) => DBusMessageHeaderField::Sender,
+            Self::Signature(_) => DBusMessageHeaderField::Signature,
+            Self::NumUnixFds(_) => DBusMessageHeaderField::NumUnixFds,
+            Self::Other(field, _) => *field,
+        }
+    }
+
+    pub fn to_variant(&self) -> Variant {
+        match self {
+            Self::Path(path) => path.to_variant(),
+            Self::Interface(s)
+            | Self::Member(s)
+            | Self::ErrorName(s)
+            | Self::Destination(s)
+            | Self::Sender(s) => s.to_variant(),
+            Self::ReplySerial(v) | Self::NumUnixFds(v) => v.to_variant(),
+            Self::Signature(signature) => signature.to_variant(),
+            Self::Other(_, value) => value.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    MethodCall {
+        path: String,
+        method: String,
+    },
+    Signal {
+        path: String,
+        interface: String,
+        signal: String,
+    },
+    Reply {
+        call: DBusMessage,
+    },
+    Error {
+        call: DBusMessage,
+        error_name: String,
+        error_message: String,
+    },
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`DBusMessage`]s.
+///
+/// The header fields and the body are validated when the message is built.
+#[derive(Debug, Clone)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusMessageBuilder {
+    kind: Kind,
+    interface: Option<String>,
+    destination: Option<String>,
+    sender: Option<String>,
+    flags: DBusMessageFlags,
+    byte_order: Option<DBusMessageByteOrder>,
+    serial: Option<u32>,
+    body: Option<Variant>,
+    signature: Option<VariantType>,
+}
+
+impl DBusMessageBuilder {
+    fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            interface: None,
+            destination: None,
+            sender: None,
+            flags: DBusMessageFlags::NONE,
+            byte_order: None,
+            serial: None,
+            body: None,
+            signature: None,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the interface of a method call.
+    pub fn interface(mut self, interface: &str) -> Self {
+        self.interface = Some(interface.to_owned());
+        self
+    }
+
+    pub fn destination(mut self, destination: &str) -> Self {
+        self.destination = Some(destination.to_owned());
+        self
+    }
+
+    pub fn sender(mut self, sender: &str) -> Self {
+        self.sender = Some(sender.to_owned());
+        self
+    }
+
+    pub fn flags(mut self, flags: DBusMessageFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn byte_order(mut self, byte_order: DBusMessageByteOrder) -> Self {
+        self.byte_order = Some(byte_order);
+        self
+    }
+
+    pub fn serial(mut self, serial: u32) -> Self {
+        self.serial = Some(serial);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the body of the message, which must be a tuple.
+    pub fn body(mut self, body: &Variant) -> Self {
+        self.body = Some(body.clone());
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the tuple type the body of the message is required to have.
+    pub fn signature(mut self, signature: &VariantTy) -> Self {
+        self.signature = Some(signature.to_owned());
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the message.
+    ///
+    /// This fails with [`IOErrorEnum::InvalidArgument`] if any of the names or paths are not
+    /// valid, if the body is not a tuple, or if it does not match the signature.
+    pub fn build(self) -> Result<DBusMessage, glib::Error> {
+        let message = match self.kind {
+            Kind::MethodCall { path, method } => {
+                check(Variant::is_object_path(&path), "object path", &path)?;
+                check(crate::dbus_is_member_name(&method), "member name", &method)?;
+                if let Some(ref interface) = self.interface {
+                    check(
+                        crate::dbus_is_interface_name(interface),
+                        "interface name",
+                        interface,
+                    )?;
+                }
+                DBusMessage::new_method_call(None, &path, self.interface.as_deref(), &method)
+            }
+            Kind::Signal {
+                path,
+                interface,
+                signal,
+            } => {
+                check(Variant::is_object_path(&path), "object path", &path)?;
+                check(
+                    crate::dbus_is_interface_name(&interface),
+                    "interface name",
+                    &interface,
+                )?;
+                check(crate::dbus_is_member_name(&signal), "member name", &signal)?;
+                DBusMessage::new_signal(&path, &interface, &signal)
+            }
+            Kind::Reply { call } => {
+                check_method_call(&call)?;
+                call.new_method_reply()
+            }
+            Kind::Error {
+                call,
+                error_name,
+                error_message,
+            } => {
+                check_method_call(&call)?;
+                // Error names follow the same rules as interface names
+                check(
+                    crate::dbus_is_interface_name(&error_name),
+                    "error name",
+                    &error_name,
+                )?;
+                call.new_method_error_literal(&error_name, &error_message)
+            }
+        };
+
+        if let Some(ref destination) = self.destination {
+            check(crate::dbus_is_name(destination), "bus name", destination)?;
+            message.set_destination(Some(destination));
+        }
+        if let Some(ref sender) = self.sender {
+            check(crate::dbus_is_name(sender), "bus name", sender)?;
+            message.set_sender(Some(sender));
+        }
+        message.set_flags(self.flags);
+        if let Some(byte_order) = self.byte_order {
+            message.set_byte_order(byte_order);
+        }
+        if let Some(serial) = self.serial {
+            message.set_serial(serial);
+        }
+
+        if let Some(ref body) = self.body {
+            if !body.type_().is_tuple() {
+                return Err(glib::Error::new(
+                    IOErrorEnum::InvalidArgument,
+                    &format!("Message body of type '{}' is not a tuple", body.type_()),
+                ));
+            }
+            message.set_body(body);
+        }
+        if let Some(ref signature) = self.signature {
+            let body_type = message
+                .body()
+                .map(|body| body.type_().to_owned())
+                .unwrap_or_else(|| VariantTy::UNIT.to_owned());
+            if body_type != *signature {
+                return Err(glib::Error::new(
+                    IOErrorEnum::InvalidArgument,
+                    &format!(
+                        "Message body of type '{}' does not match signature '{}'",
+                        body_type, signature
+                    ),
+                ));
+            }
+        }
+
+        Ok(message)
+    }
+}
+
+fn check(valid: bool, what: &str, value: &str) -> Result<(), glib::Error> {
+    if valid {
+        Ok(())
+    } else {
+        Err(glib::Error::new(
+            IOErrorEnum::InvalidArgument,
+            &format!("'{value}' is not a valid D-Bus {what}"),
+        ))
+    }
+}
+
+fn check_method_call(call: &DBusMessage) -> Result<(), glib::Error> {
+    if call.message_type() == DBusMessageType::MethodCall {
+        Ok(())
+    } else {
+        Err(glib::Error::new(
+            IOErrorEnum::InvalidArgument,
+            "Replies can only be created for method calls",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DBusCapabilityFlags;
+
+    #[test]
+    fn blob_roundtrip() {
+        let message = DBusMessage::method_call_builder("/org/gtk_rs/Test", "Frobnicate")
+            .interface("org.gtk_rs.Test")
+            .destination("org.gtk_rs.Service")
+            .serial(42)
+            .body(&(1u32, "foo").to_variant())
+            .signature(VariantTy::new("(us)").unwrap())
+            .build()
+            .unwrap();
+
+        let blob = message.to_blob(DBusCapabilityFlags::NONE).unwrap();
+        assert_eq!(
+            DBusMessage::bytes_needed(&blob).unwrap(),
+            blob.len() as isize
+        );
+        let decoded = DBusMessage::from_blob(&blob, DBusCapabilityFlags::NONE).unwrap();
+
+        assert_eq!(decoded.message_type(), DBusMessageType::MethodCall);
+        assert_eq!(decoded.serial(), 42);
+        assert_eq!(
+            decoded.body().unwrap().get::<(u32, String)>(),
+            Some((1, "foo".into()))
+        );
+        assert_eq!(decoded.to_string(), message.to_string());
+
+        let headers = decoded.headers();
+        assert_eq!(
+            headers[&DBusMessageHeaderField::Path],
+            DBusMessageHeader::Path(ObjectPath::try_from("/org/gtk_rs/Test").unwrap())
+        );
+        assert_eq!(
+            headers[&DBusMessageHeaderField::Member],
+            DBusMessageHeader::Member("Frobnicate".into())
+        );
+        assert_eq!(
+            headers[&DBusMessageHeaderField::Interface],
+            DBusMessageHeader::Interface("org.gtk_rs.Test".into())
+        );
+        assert_eq!(
+            headers[&DBusMessageHeaderField::Destination],
+            DBusMessageHeader::Destination("org.gtk_rs.Service".into())
+        );
+        assert_eq!(
+            headers[&DBusMessageHeaderField::Signature],
+            DBusMessageHeader::Signature(Signature::try_from("us").unwrap())
+        );
+
+        let error = decoded
+            .method_error_builder("org.gtk_rs.Error.Failed", "Nope")
+            .build()
+            .unwrap();
+        assert_eq!(error.message_type(), DBusMessageType::Error);
+        assert_eq!(
+            error.typed_header(DBusMessageHeaderField::ReplySerial),
+            Some(DBusMessageHeader::ReplySerial(42))
+        );
+        assert_eq!(
+            error.error_name().as_deref(),
+            Some("org.gtk_rs.Error.Failed")
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(DBusMessage::method_call_builder("not a path", "Frobnicate")
+            .build()
+            .is_err());
+        assert!(
+            DBusMessage::signal_builder("/", "org.gtk_rs.Test", "Changed")
+                .body(&1u32.to_variant())
+                .build()
+                .is_err()
+        );
+        assert!(
+            DBusMessage::signal_builder("/", "org.gtk_rs.Test", "Changed")
+                .body(&(1u32,).to_variant())
+                .signature(VariantTy::new("(s)").unwrap())
+                .build()
+                .is_err()
+        );
+
+        let signal = DBusMessage::signal_builder("/", "org.gtk_rs.Test", "Changed")
+            .build()
+            .unwrap();
+        assert!(signal.method_reply_builder().build().is_err());
+    }
+}
@@ -0,0 +1,114 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{collections::PtrSlice, translate::*, StaticVariantType, VariantTy};
+
+use crate::{
+    dbus_annotation_info::{annotations_to_glib_full, borrow_str, lookup_annotation, new_info},
+    dbus_introspection::{assert_args, assert_member_name},
+    DBusArgInfo, DBusMethodInfo,
+};
+
+impl DBusMethodInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new builder for the introspection data of the method `name`.
+    pub fn builder(name: &str) -> DBusMethodInfoBuilder {
+        DBusMethodInfoBuilder::new(name)
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe { borrow_str((*self.as_ptr()).name) }
+    }
+
+    pub fn in_args(&self) -> &[DBusArgInfo] {
+        unsafe { PtrSlice::from_glib_borrow((*self.as_ptr()).in_args) }
+    }
+
+    pub fn out_args(&self) -> &[DBusArgInfo] {
+        unsafe { PtrSlice::from_glib_borrow((*self.as_ptr()).out_args) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the annotation `key`, if any.
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, key: &str) -> Option<&str> {
+        unsafe { lookup_annotation((*self.as_ptr()).annotations, key) }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`DBusMethodInfo`].
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusMethodInfoBuilder {
+    name: String,
+    in_args: Vec<DBusArgInfo>,
+    out_args: Vec<DBusArgInfo>,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusMethodInfoBuilder {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            in_args: Vec::new(),
+            out_args: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an input argument with the D-Bus type of `T`.
+    pub fn in_arg<T: StaticVariantType>(self, name: &str) -> Self {
+        self.in_arg_with_type(name, &T::static_variant_type())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an input argument of type `signature`.
+    pub fn in_arg_with_type(mut self, name: &str, signature: &VariantTy) -> Self {
+        self.in_args.push(DBusArgInfo::new(name, signature));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an output argument with the D-Bus type of `T`.
+    pub fn out_arg<T: StaticVariantType>(self, name: &str) -> Self {
+        self.out_arg_with_type(name, &T::static_variant_type())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an output argument of type `signature`.
+    pub fn out_arg_with_type(mut self, name: &str, signature: &VariantTy) -> Self {
+        self.out_args.push(DBusArgInfo::new(name, signature));
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name of the method or one of its arguments is not a valid D-Bus member name,
+    /// or if the type of an argument can't be sent over D-Bus, e.g. because it contains a maybe
+    /// type.
+    pub fn build(self) -> DBusMethodInfo {
+        let method = format!("method {}", self.name);
+        assert_member_name(&method, &self.name);
+        assert_args(&method, &self.in_args);
+        assert_args(&method, &self.out_args);
+
+        unsafe {
+            let info = new_info::<ffi::GDBusMethodInfo>();
+            (*info).ref_count = 1;
+            (*info).name = self.name.to_glib_full();
+            (*info).in_args = PtrSlice::from(self.in_args).into_raw();
+            (*info).out_args = PtrSlice::from(self.out_args).into_raw();
+            (*info).annotations = annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
+}
@@ -2,9 +2,21 @@
 
 use std::ffi::CStr;
 
-use crate::{DBusInterfaceInfo, DBusNodeInfo};
+use glib::{collections::PtrSlice, translate::*};
+
+use crate::{
+    dbus_annotation_info::{annotations_to_glib_full, lookup_annotation, new_info},
+    dbus_introspection::assert_node_path,
+    DBusInterfaceInfo, DBusNodeInfo,
+};
 
 impl DBusNodeInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new builder for introspection data of a node.
+    pub fn builder() -> DBusNodeInfoBuilder {
+        DBusNodeInfoBuilder::default()
+    }
+
     pub fn path(&self) -> Option<&str> {
         unsafe {
             let c_obj = self.as_ptr();
@@ -25,7 +37,7 @@ impl DBusNodeInfo {
                 return &[];
             }
 
-            glib::collections::PtrSlice::from_glib_borrow(c_ii)
+            PtrSlice::from_glib_borrow(c_ii)
         }
     }
 
@@ -36,7 +48,221 @@ impl DBusNodeInfo {
             if c_ni.is_null() {
                 return &[];
             }
-            glib::collections::PtrSlice::from_glib_borrow(c_ni)
+            PtrSlice::from_glib_borrow(c_ni)
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the annotation `key`, if any.
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, key: &str) -> Option<&str> {
+        unsafe { lookup_annotation((*self.as_ptr()).annotations, key) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Serializes the node to introspection XML, indented by `indent` spaces.
+    ///
+    /// The result can be parsed again with [`DBusNodeInfo::for_xml`].
+    #[doc(alias = "g_dbus_node_info_generate_xml")]
+    pub fn generate_xml(&self, indent: u32) -> glib::GString {
+        let mut xml = glib::GStringBuilder::default();
+        unsafe {
+            ffi::g_dbus_node_info_generate_xml(
+                self.to_glib_none().0,
+                indent,
+                xml.to_glib_none_mut().0,
+            );
+        }
+        xml.into_string()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`DBusNodeInfo`].
+#[derive(Debug, Default)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusNodeInfoBuilder {
+    path: Option<String>,
+    interfaces: Vec<DBusInterfaceInfo>,
+    nodes: Vec<DBusNodeInfo>,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusNodeInfoBuilder {
+    // rustdoc-stripper-ignore-next
+    /// Sets the path of the node.
+    ///
+    /// This is absolute for the root node of introspection data returned by a service and
+    /// relative to the parent for child nodes.
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_owned());
+        self
+    }
+
+    pub fn interface(mut self, interface: DBusInterfaceInfo) -> Self {
+        self.interfaces.push(interface);
+        self
+    }
+
+    pub fn node(mut self, node: DBusNodeInfo) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path of the node is set but is neither an object path nor a valid relative
+    /// path.
+    pub fn build(self) -> DBusNodeInfo {
+        if let Some(ref path) = self.path {
+            assert_node_path(path);
+        }
+
+        unsafe {
+            let info = new_info::<ffi::GDBusNodeInfo>();
+            (*info).ref_count = 1;
+            (*info).path = self.path.to_glib_full();
+            (*info).interfaces = PtrSlice::from(self.interfaces).into_raw();
+            (*info).nodes = PtrSlice::from(self.nodes).into_raw();
+            (*info).annotations = annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DBusMethodInfo, DBusPropertyInfo, DBusPropertyInfoFlags, DBusSignalInfo};
+
+    #[test]
+    fn builder_roundtrip() {
+        let interface = DBusInterfaceInfo::builder("org.gtk_rs.Test")
+            .method(
+                DBusMethodInfo::builder("Hello")
+                    .in_arg::<String>("name")
+                    .in_arg_with_type("flags", glib::VariantTy::UINT32)
+                    .out_arg::<Vec<String>>("greetings")
+                    .annotation("org.freedesktop.DBus.Deprecated", "true")
+                    .build(),
+            )
+            .method(DBusMethodInfo::builder("Quit").build())
+            .signal(
+                DBusSignalInfo::builder("Changed")
+                    .arg::<(i32, bool)>("value")
+                    .build(),
+            )
+            .property(
+                DBusPropertyInfo::builder::<u64>("Count")
+                    .flags(DBusPropertyInfoFlags::READABLE | DBusPropertyInfoFlags::WRITABLE)
+                    .build(),
+            )
+            .annotation("org.gtk_rs.Annotation", "value")
+            .build();
+        let node = DBusNodeInfo::builder()
+            .path("/org/gtk_rs/Test")
+            .interface(interface)
+            .node(DBusNodeInfo::builder().path("child").build())
+            .build();
+
+        let xml = node.generate_xml(0);
+        let parsed = DBusNodeInfo::for_xml(&xml).unwrap();
+
+        for node in [&node, &parsed] {
+            assert_eq!(node.path(), Some("/org/gtk_rs/Test"));
+            assert_eq!(node.nodes().len(), 1);
+            assert_eq!(node.nodes()[0].path(), Some("child"));
+
+            let interface = node.lookup_interface("org.gtk_rs.Test").unwrap();
+            assert_eq!(interface.annotation("org.gtk_rs.Annotation"), Some("value"));
+            assert_eq!(interface.methods().len(), 2);
+
+            let method = interface.lookup_method("Hello").unwrap();
+            let in_args = method
+                .in_args()
+                .iter()
+                .map(|arg| (arg.name(), arg.signature()))
+                .collect::<Vec<_>>();
+            assert_eq!(in_args, [("name", "s"), ("flags", "u")]);
+            assert_eq!(method.out_args()[0].signature(), "as");
+            assert_eq!(
+                method.annotation("org.freedesktop.DBus.Deprecated"),
+                Some("true")
+            );
+            assert!(interface
+                .lookup_method("Quit")
+                .unwrap()
+                .in_args()
+                .is_empty());
+
+            let signal = interface.lookup_signal("Changed").unwrap();
+            assert_eq!(signal.args()[0].signature(), "(ib)");
+
+            let property = interface.lookup_property("Count").unwrap();
+            assert_eq!(property.signature(), "t");
+            assert_eq!(
+                property.flags(),
+                DBusPropertyInfoFlags::READABLE | DBusPropertyInfoFlags::WRITABLE
+            );
+        }
+
+        // Interfaces are nested one level deeper than the node
+        assert!(xml.contains(node.interfaces()[0].generate_xml(2).as_str()));
+    }
+
+    #[test]
+    #[should_panic = "Type ms of argument 0 of method Hello is not a D-Bus type"]
+    fn builder_maybe_arg() {
+        let _ = DBusMethodInfo::builder("Hello")
+            .in_arg::<Option<String>>("name")
+            .build();
+    }
+
+    #[test]
+    #[should_panic = "Invalid D-Bus name \"first name\" of argument 0 of signal Changed"]
+    fn builder_invalid_arg_name() {
+        let _ = DBusSignalInfo::builder("Changed")
+            .arg::<String>("first name")
+            .build();
+    }
+
+    #[test]
+    #[should_panic = "Invalid D-Bus name \"Say.Hello\" of method Say.Hello"]
+    fn builder_invalid_method_name() {
+        let _ = DBusMethodInfo::builder("Say.Hello").build();
+    }
+
+    #[test]
+    #[should_panic = "Invalid D-Bus interface name \"gtk_rs\""]
+    fn builder_invalid_interface_name() {
+        let _ = DBusInterfaceInfo::builder("gtk_rs").build();
+    }
+
+    #[test]
+    #[should_panic = "Invalid D-Bus node path \"/org/gtk_rs/\""]
+    fn builder_invalid_node_path() {
+        let _ = DBusNodeInfo::builder().path("/org/gtk_rs/").build();
+    }
+
+    #[test]
+    #[should_panic = "Invalid D-Bus node path \"child-node\""]
+    fn builder_invalid_child_path() {
+        let _ = DBusNodeInfo::builder()
+            .node(DBusNodeInfo::builder().path("child-node").build())
+            .build();
+    }
+
+    #[test]
+    #[should_panic = "Type mv of property Value is not a D-Bus type"]
+    fn builder_maybe_property() {
+        let _ = DBusPropertyInfo::builder::<Option<glib::Variant>>("Value").build();
+    }
+}
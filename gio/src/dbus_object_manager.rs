@@ -0,0 +1,126 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use glib::SignalHandlerId;
+
+use crate::{prelude::*, DBusInterface, DBusObject, DBusObjectManager};
+
+// rustdoc-stripper-ignore-next
+/// A change of the objects of a [`DBusObjectManager`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DBusObjectManagerEvent {
+    #[doc(alias = "object-added")]
+    ObjectAdded(DBusObject),
+    #[doc(alias = "object-removed")]
+    ObjectRemoved(DBusObject),
+    #[doc(alias = "interface-added")]
+    InterfaceAdded(DBusObject, DBusInterface),
+    #[doc(alias = "interface-removed")]
+    InterfaceRemoved(DBusObject, DBusInterface),
+}
+
+pub trait DBusObjectManagerExtManual {
+    // rustdoc-stripper-ignore-next
+    /// Returns a stream of the objects and interfaces that are added to or removed from the
+    /// manager from now on.
+    ///
+    /// Interfaces of an added object are only reported with [`ObjectAdded`], and interfaces of a
+    /// removed object only with [`ObjectRemoved`].
+    ///
+    /// [`DBusObjectManagerServer`](crate::DBusObjectManagerServer) doesn't emit the signals of
+    /// [`DBusObjectManager`] when objects are exported or unexported, so its stream never yields
+    /// any events. The changes are reported by the clients of the server.
+    ///
+    /// [`ObjectAdded`]: DBusObjectManagerEvent::ObjectAdded
+    /// [`ObjectRemoved`]: DBusObjectManagerEvent::ObjectRemoved
+    fn receive_events(&self) -> DBusObjectManagerEvents;
+}
+
+impl<O: IsA<DBusObjectManager>> DBusObjectManagerExtManual for O {
+    fn receive_events(&self) -> DBusObjectManagerEvents {
+        let manager = self.as_ref();
+        let (sender, receiver) = mpsc::unbounded();
+
+        let ids = [
+            manager.connect_object_added({
+                let sender = sender.clone();
+                move |_, object| {
+                    let _ =
+                        sender.unbounded_send(DBusObjectManagerEvent::ObjectAdded(object.clone()));
+                }
+            }),
+            manager.connect_object_removed({
+                let sender = sender.clone();
+                move |_, object| {
+                    let _ = sender
+                        .unbounded_send(DBusObjectManagerEvent::ObjectRemoved(object.clone()));
+                }
+            }),
+            manager.connect_interface_added({
+                let sender = sender.clone();
+                move |_, object, interface| {
+                    let _ = sender.unbounded_send(DBusObjectManagerEvent::InterfaceAdded(
+                        object.clone(),
+                        interface.clone(),
+                    ));
+                }
+            }),
+            manager.connect_interface_removed(move |_, object, interface| {
+                let _ = sender.unbounded_send(DBusObjectManagerEvent::InterfaceRemoved(
+                    object.clone(),
+                    interface.clone(),
+                ));
+            }),
+        ];
+
+        DBusObjectManagerEvents {
+            manager: manager.clone(),
+            ids: Vec::from(ids),
+            receiver,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A stream of [`DBusObjectManagerEvent`]s.
+///
+/// Created by [`DBusObjectManagerExtManual::receive_events`]. The signal handlers are
+/// disconnected when the stream is dropped.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct DBusObjectManagerEvents {
+    manager: DBusObjectManager,
+    ids: Vec<SignalHandlerId>,
+    receiver: mpsc::UnboundedReceiver<DBusObjectManagerEvent>,
+}
+
+impl Stream for DBusObjectManagerEvents {
+    type Item = DBusObjectManagerEvent;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<DBusObjectManagerEvent>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl FusedStream for DBusObjectManagerEvents {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_terminated()
+    }
+}
+
+impl Drop for DBusObjectManagerEvents {
+    fn drop(&mut self) {
+        for id in self.ids.drain(..) {
+            self.manager.disconnect(id);
+        }
+    }
+}
@@ -0,0 +1,300 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{boxed::Box as Box_, future::Future, pin::Pin, ptr};
+
+use glib::{prelude::*, translate::*};
+
+use crate::{
+    BusType, Cancellable, DBusConnection, DBusObjectManagerClient, DBusObjectManagerClientFlags,
+};
+
+// rustdoc-stripper-ignore-next
+/// Function that returns the type to use for the proxy of an object or interface.
+///
+/// It is called with the object path and the interface name, or `None` for the
+/// [`DBusObjectProxy`](crate::DBusObjectProxy) of the object itself. The returned type must be a
+/// subclass of `DBusObjectProxy` or [`DBusProxy`](crate::DBusProxy) respectively.
+///
+/// The function may be called from a different thread during the asynchronous construction.
+pub type DBusProxyTypeFn = Box_<
+    dyn Fn(&DBusObjectManagerClient, &str, Option<&str>) -> glib::Type + Send + Sync + 'static,
+>;
+
+unsafe extern "C" fn get_proxy_type_trampoline(
+    manager: *mut ffi::GDBusObjectManagerClient,
+    object_path: *const libc::c_char,
+    interface_name: *const libc::c_char,
+    user_data: glib::ffi::gpointer,
+) -> glib::ffi::GType {
+    let f: &DBusProxyTypeFn = &*(user_data as *const DBusProxyTypeFn);
+    let interface_name: Borrowed<Option<glib::GString>> = from_glib_borrow(interface_name);
+    f(
+        &from_glib_borrow(manager),
+        &glib::GString::from_glib_borrow(object_path),
+        interface_name.as_ref().as_deref(),
+    )
+    .into_glib()
+}
+
+unsafe extern "C" fn get_proxy_type_destroy(user_data: glib::ffi::gpointer) {
+    let _ = Box_::from_raw(user_data as *mut DBusProxyTypeFn);
+}
+
+fn get_proxy_type_to_glib(
+    get_proxy_type_func: Option<DBusProxyTypeFn>,
+) -> (
+    ffi::GDBusProxyTypeFunc,
+    glib::ffi::gpointer,
+    glib::ffi::GDestroyNotify,
+) {
+    match get_proxy_type_func {
+        Some(f) => (
+            Some(get_proxy_type_trampoline),
+            Box_::into_raw(Box_::new(f)) as glib::ffi::gpointer,
+            Some(get_proxy_type_destroy),
+        ),
+        None => (None, ptr::null_mut(), None),
+    }
+}
+
+impl DBusObjectManagerClient {
+    #[doc(alias = "g_dbus_object_manager_client_new_sync")]
+    pub fn new_sync(
+        connection: &DBusConnection,
+        flags: DBusObjectManagerClientFlags,
+        name: Option<&str>,
+        object_path: &str,
+        get_proxy_type_func: Option<DBusProxyTypeFn>,
+        cancellable: Option<&impl IsA<Cancellable>>,
+    ) -> Result<DBusObjectManagerClient, glib::Error> {
+        let (func, user_data, destroy) = get_proxy_type_to_glib(get_proxy_type_func);
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::g_dbus_object_manager_client_new_sync(
+                connection.to_glib_none().0,
+                flags.into_glib(),
+                name.to_glib_none().0,
+                object_path.to_glib_none().0,
+                func,
+                user_data,
+                destroy,
+                cancellable.map(|p| p.as_ref()).to_glib_none().0,
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    #[doc(alias = "g_dbus_object_manager_client_new")]
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<P: FnOnce(Result<DBusObjectManagerClient, glib::Error>) + 'static>(
+        connection: &DBusConnection,
+        flags: DBusObjectManagerClientFlags,
+        name: Option<&str>,
+        object_path: &str,
+        get_proxy_type_func: Option<DBusProxyTypeFn>,
+        cancellable: Option<&impl IsA<Cancellable>>,
+        callback: P,
+    ) {
+        let main_context = glib::MainContext::ref_thread_default();
+        let is_main_context_owner = main_context.is_owner();
+        let has_acquired_main_context = (!is_main_context_owner)
+            .then(|| main_context.acquire().ok())
+            .flatten();
+        assert!(
+            is_main_context_owner || has_acquired_main_context.is_some(),
+            "Async operations only allowed if the thread is owning the MainContext"
+        );
+
+        let user_data: Box_<glib::thread_guard::ThreadGuard<P>> =
+            Box_::new(glib::thread_guard::ThreadGuard::new(callback));
+        unsafe extern "C" fn new_trampoline<
+            P: FnOnce(Result<DBusObjectManagerClient, glib::Error>) + 'static,
+        >(
+            _source_object: *mut glib::gobject_ffi::GObject,
+            res: *mut crate::ffi::GAsyncResult,
+            user_data: glib::ffi::gpointer,
+        ) {
+            let mut error = ptr::null_mut();
+            let ret = ffi::g_dbus_object_manager_client_new_finish(res, &mut error);
+            let result = if error.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            };
+            let callback: Box_<glib::thread_guard::ThreadGuard<P>> =
+                Box_::from_raw(user_data as *mut _);
+            let callback: P = callback.into_inner();
+            callback(result);
+        }
+        let callback = new_trampoline::<P>;
+        let (func, func_data, destroy) = get_proxy_type_to_glib(get_proxy_type_func);
+        unsafe {
+            ffi::g_dbus_object_manager_client_new(
+                connection.to_glib_none().0,
+                flags.into_glib(),
+                name.to_glib_none().0,
+                object_path.to_glib_none().0,
+                func,
+                func_data,
+                destroy,
+                cancellable.map(|p| p.as_ref()).to_glib_none().0,
+                Some(callback),
+                Box_::into_raw(user_data) as *mut _,
+            );
+        }
+    }
+
+    pub fn new_future(
+        connection: &DBusConnection,
+        flags: DBusObjectManagerClientFlags,
+        name: Option<&str>,
+        object_path: &str,
+        get_proxy_type_func: Option<DBusProxyTypeFn>,
+    ) -> Pin<Box_<dyn Future<Output = Result<DBusObjectManagerClient, glib::Error>> + 'static>>
+    {
+        let connection = connection.clone();
+        let name = name.map(ToOwned::to_owned);
+        let object_path = String::from(object_path);
+        Box_::pin(crate::GioFuture::new(
+            &(),
+            move |_obj, cancellable, send| {
+                Self::new(
+                    &connection,
+                    flags,
+                    name.as_deref(),
+                    &object_path,
+                    get_proxy_type_func,
+                    Some(cancellable),
+                    move |res| {
+                        send.resolve(res);
+                    },
+                );
+            },
+        ))
+    }
+
+    #[doc(alias = "g_dbus_object_manager_client_new_for_bus_sync")]
+    #[doc(alias = "new_for_bus_sync")]
+    pub fn for_bus_sync(
+        bus_type: BusType,
+        flags: DBusObjectManagerClientFlags,
+        name: &str,
+        object_path: &str,
+        get_proxy_type_func: Option<DBusProxyTypeFn>,
+        cancellable: Option<&impl IsA<Cancellable>>,
+    ) -> Result<DBusObjectManagerClient, glib::Error> {
+        let (func, user_data, destroy) = get_proxy_type_to_glib(get_proxy_type_func);
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::g_dbus_object_manager_client_new_for_bus_sync(
+                bus_type.into_glib(),
+                flags.into_glib(),
+                name.to_glib_none().0,
+                object_path.to_glib_none().0,
+                func,
+                user_data,
+                destroy,
+                cancellable.map(|p| p.as_ref()).to_glib_none().0,
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    #[doc(alias = "g_dbus_object_manager_client_new_for_bus")]
+    #[doc(alias = "new_for_bus")]
+    pub fn for_bus<P: FnOnce(Result<DBusObjectManagerClient, glib::Error>) + 'static>(
+        bus_type: BusType,
+        flags: DBusObjectManagerClientFlags,
+        name: &str,
+        object_path: &str,
+        get_proxy_type_func: Option<DBusProxyTypeFn>,
+        cancellable: Option<&impl IsA<Cancellable>>,
+        callback: P,
+    ) {
+        let main_context = glib::MainContext::ref_thread_default();
+        let is_main_context_owner = main_context.is_owner();
+        let has_acquired_main_context = (!is_main_context_owner)
+            .then(|| main_context.acquire().ok())
+            .flatten();
+        assert!(
+            is_main_context_owner || has_acquired_main_context.is_some(),
+            "Async operations only allowed if the thread is owning the MainContext"
+        );
+
+        let user_data: Box_<glib::thread_guard::ThreadGuard<P>> =
+            Box_::new(glib::thread_guard::ThreadGuard::new(callback));
+        unsafe extern "C" fn for_bus_trampoline<
+            P: FnOnce(Result<DBusObjectManagerClient, glib::Error>) + 'static,
+        >(
+            _source_object: *mut glib::gobject_ffi::GObject,
+            res: *mut crate::ffi::GAsyncResult,
+            user_data: glib::ffi::gpointer,
+        ) {
+            let mut error = ptr::null_mut();
+            let ret = ffi::g_dbus_object_manager_client_new_for_bus_finish(res, &mut error);
+            let result = if error.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            };
+            let callback: Box_<glib::thread_guard::ThreadGuard<P>> =
+                Box_::from_raw(user_data as *mut _);
+            let callback: P = callback.into_inner();
+            callback(result);
+        }
+        let callback = for_bus_trampoline::<P>;
+        let (func, func_data, destroy) = get_proxy_type_to_glib(get_proxy_type_func);
+        unsafe {
+            ffi::g_dbus_object_manager_client_new_for_bus(
+                bus_type.into_glib(),
+                flags.into_glib(),
+                name.to_glib_none().0,
+                object_path.to_glib_none().0,
+                func,
+                func_data,
+                destroy,
+                cancellable.map(|p| p.as_ref()).to_glib_none().0,
+                Some(callback),
+                Box_::into_raw(user_data) as *mut _,
+            );
+        }
+    }
+
+    pub fn for_bus_future(
+        bus_type: BusType,
+        flags: DBusObjectManagerClientFlags,
+        name: &str,
+        object_path: &str,
+        get_proxy_type_func: Option<DBusProxyTypeFn>,
+    ) -> Pin<Box_<dyn Future<Output = Result<DBusObjectManagerClient, glib::Error>> + 'static>>
+    {
+        let name = String::from(name);
+        let object_path = String::from(object_path);
+        Box_::pin(crate::GioFuture::new(
+            &(),
+            move |_obj, cancellable, send| {
+                Self::for_bus(
+                    bus_type,
+                    flags,
+                    &name,
+                    &object_path,
+                    get_proxy_type_func,
+                    Some(cancellable),
+                    move |res| {
+                        send.resolve(res);
+                    },
+                );
+            },
+        ))
+    }
+}
@@ -0,0 +1,113 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{translate::*, StaticVariantType, VariantTy, VariantType};
+
+use crate::{
+    dbus_annotation_info::{annotations_to_glib_full, borrow_str, lookup_annotation, new_info},
+    dbus_introspection::{assert_member_name, assert_signature},
+    DBusPropertyInfo, DBusPropertyInfoFlags,
+};
+
+impl DBusPropertyInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new builder for the introspection data of the property `name` with the D-Bus
+    /// type of `T`.
+    pub fn builder<T: StaticVariantType>(name: &str) -> DBusPropertyInfoBuilder {
+        DBusPropertyInfoBuilder::new(name, &T::static_variant_type())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new builder for the introspection data of the property `name` of type
+    /// `signature`.
+    pub fn builder_with_type(name: &str, signature: &VariantTy) -> DBusPropertyInfoBuilder {
+        DBusPropertyInfoBuilder::new(name, signature)
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe { borrow_str((*self.as_ptr()).name) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the D-Bus signature of the property.
+    pub fn signature(&self) -> &str {
+        unsafe { borrow_str((*self.as_ptr()).signature) }
+    }
+
+    pub fn flags(&self) -> DBusPropertyInfoFlags {
+        unsafe { from_glib((*self.as_ptr()).flags) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the annotation `key`, if any.
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, key: &str) -> Option<&str> {
+        unsafe { lookup_annotation((*self.as_ptr()).annotations, key) }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`DBusPropertyInfo`].
+///
+/// Properties are readable by default.
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusPropertyInfoBuilder {
+    name: String,
+    signature: VariantType,
+    flags: DBusPropertyInfoFlags,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusPropertyInfoBuilder {
+    fn new(name: &str, signature: &VariantTy) -> Self {
+        Self {
+            name: name.to_owned(),
+            signature: signature.to_owned(),
+            flags: DBusPropertyInfoFlags::READABLE,
+            annotations: Vec::new(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets whether the property can be read and/or written.
+    ///
+    /// At least one of `READABLE` and `WRITABLE` must be set.
+    pub fn flags(mut self, flags: DBusPropertyInfoFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the property.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property is neither readable nor writable, if its name is not a valid D-Bus
+    /// member name or if its type can't be sent over D-Bus, e.g. because it contains a maybe type.
+    pub fn build(self) -> DBusPropertyInfo {
+        let property = format!("property {}", self.name);
+        assert_member_name(&property, &self.name);
+        assert_signature(&property, self.signature.as_str());
+        assert!(
+            self.flags
+                .intersects(DBusPropertyInfoFlags::READABLE | DBusPropertyInfoFlags::WRITABLE),
+            "Property {} must be readable or writable",
+            self.name
+        );
+
+        unsafe {
+            let info = new_info::<ffi::GDBusPropertyInfo>();
+            (*info).ref_count = 1;
+            (*info).name = self.name.to_glib_full();
+            (*info).signature = self.signature.as_str().to_glib_full();
+            (*info).flags = self.flags.into_glib();
+            (*info).annotations = annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
+}
@@ -4,7 +4,7 @@ use std::{boxed::Box as Box_, mem::transmute};
 
 use glib::{prelude::*, signal::connect_raw, translate::*, SignalHandlerId};
 
-use crate::DBusProxy;
+use crate::{dbus_signal_stream::convert_parameters, DBusProxy, DBusSignalStream};
 
 pub trait DBusProxyExtManual: 'static {
     #[cfg(feature = "v2_72")]
@@ -21,6 +21,17 @@ pub trait DBusProxyExtManual: 'static {
         &self,
         f: F,
     ) -> SignalHandlerId;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a stream of the parameters of the signal `signal_name` emitted by the remote
+    /// object.
+    ///
+    /// The parameters are converted to `T`, which is usually a tuple. The signal handler is
+    /// disconnected when the stream is dropped.
+    fn receive_signal<T: glib::FromVariant + Send + 'static>(
+        &self,
+        signal_name: &str,
+    ) -> DBusSignalStream<T>;
 }
 
 impl<O: IsA<DBusProxy>> DBusProxyExtManual for O {
@@ -108,4 +119,32 @@ impl<O: IsA<DBusProxy>> DBusProxyExtManual for O {
             )
         }
     }
+
+    fn receive_signal<T: glib::FromVariant + Send + 'static>(
+        &self,
+        signal_name: &str,
+    ) -> DBusSignalStream<T> {
+        let proxy = self.upcast_ref::<DBusProxy>();
+        DBusSignalStream::for_proxy(proxy, |tx| {
+            let name = signal_name.to_owned();
+            let handler = move |_: &DBusProxy,
+                                _: Option<&str>,
+                                signal_name: &str,
+                                parameters: &glib::Variant| {
+                if signal_name == name {
+                    if let Some(parameters) = convert_parameters(signal_name, parameters) {
+                        let _ = tx.unbounded_send(parameters);
+                    }
+                }
+            };
+            #[cfg(feature = "v2_72")]
+            {
+                proxy.connect_g_signal(Some(signal_name), handler)
+            }
+            #[cfg(not(feature = "v2_72"))]
+            {
+                proxy.connect_g_signal(handler)
+            }
+        })
+    }
 }
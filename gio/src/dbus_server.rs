@@ -0,0 +1,80 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use glib::{prelude::*, SignalHandlerId};
+
+use crate::{DBusConnection, DBusServer};
+
+impl DBusServer {
+    // rustdoc-stripper-ignore-next
+    /// Starts the server and returns a stream of the connections it accepts.
+    ///
+    /// Every accepted connection is kept alive by the stream until it is taken out of it. The
+    /// server keeps running after the stream is dropped but new connections are not accepted
+    /// anymore, unless another handler for [`new-connection`](Self::connect_new_connection)
+    /// claims them.
+    #[doc(alias = "g_dbus_server_start")]
+    #[doc(alias = "new-connection")]
+    pub fn incoming(&self) -> DBusServerIncoming {
+        let (sender, receiver) = mpsc::unbounded();
+        let id = self.connect_new_connection(move |_server, connection| {
+            // Returning `false` rejects the connection if the stream is gone
+            sender.unbounded_send(connection.clone()).is_ok()
+        });
+        self.start();
+
+        DBusServerIncoming {
+            server: self.clone(),
+            id: Some(id),
+            receiver,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A stream of the connections accepted by a [`DBusServer`].
+///
+/// Created by [`DBusServer::incoming`].
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct DBusServerIncoming {
+    server: DBusServer,
+    id: Option<SignalHandlerId>,
+    receiver: mpsc::UnboundedReceiver<DBusConnection>,
+}
+
+impl DBusServerIncoming {
+    // rustdoc-stripper-ignore-next
+    /// Returns the server the connections are accepted from.
+    pub fn server(&self) -> &DBusServer {
+        &self.server
+    }
+}
+
+impl Stream for DBusServerIncoming {
+    type Item = DBusConnection;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<DBusConnection>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl FusedStream for DBusServerIncoming {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_terminated()
+    }
+}
+
+impl Drop for DBusServerIncoming {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            self.server.disconnect(id);
+        }
+    }
+}
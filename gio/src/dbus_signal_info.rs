@@ -0,0 +1,93 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{collections::PtrSlice, translate::*, StaticVariantType, VariantTy};
+
+use crate::{
+    dbus_annotation_info::{annotations_to_glib_full, borrow_str, lookup_annotation, new_info},
+    dbus_introspection::{assert_args, assert_member_name},
+    DBusArgInfo, DBusSignalInfo,
+};
+
+impl DBusSignalInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new builder for the introspection data of the signal `name`.
+    pub fn builder(name: &str) -> DBusSignalInfoBuilder {
+        DBusSignalInfoBuilder::new(name)
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe { borrow_str((*self.as_ptr()).name) }
+    }
+
+    pub fn args(&self) -> &[DBusArgInfo] {
+        unsafe { PtrSlice::from_glib_borrow((*self.as_ptr()).args) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the annotation `key`, if any.
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, key: &str) -> Option<&str> {
+        unsafe { lookup_annotation((*self.as_ptr()).annotations, key) }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`DBusSignalInfo`].
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusSignalInfoBuilder {
+    name: String,
+    args: Vec<DBusArgInfo>,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusSignalInfoBuilder {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            args: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an argument with the D-Bus type of `T`.
+    pub fn arg<T: StaticVariantType>(self, name: &str) -> Self {
+        self.arg_with_type(name, &T::static_variant_type())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds an argument of type `signature`.
+    pub fn arg_with_type(mut self, name: &str, signature: &VariantTy) -> Self {
+        self.args.push(DBusArgInfo::new(name, signature));
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the signal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name of the signal or one of its arguments is not a valid D-Bus member name,
+    /// or if the type of an argument can't be sent over D-Bus, e.g. because it contains a maybe
+    /// type.
+    pub fn build(self) -> DBusSignalInfo {
+        let signal = format!("signal {}", self.name);
+        assert_member_name(&signal, &self.name);
+        assert_args(&signal, &self.args);
+
+        unsafe {
+            let info = new_info::<ffi::GDBusSignalInfo>();
+            (*info).ref_count = 1;
+            (*info).name = self.name.to_glib_full();
+            (*info).args = PtrSlice::from(self.args).into_raw();
+            (*info).annotations = annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use glib::{prelude::*, SignalHandlerId};
+
+use crate::{DBusConnection, DBusProxy, SignalSubscriptionId};
+
+// rustdoc-stripper-ignore-next
+/// A D-Bus signal received by [`DBusConnection::receive_signal`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DBusSignal<T> {
+    pub sender: String,
+    pub object_path: String,
+    pub interface_name: String,
+    pub signal_name: String,
+    pub parameters: T,
+}
+
+#[derive(Debug)]
+enum Subscription {
+    Connection(DBusConnection, Option<SignalSubscriptionId>),
+    Proxy(DBusProxy, Option<SignalHandlerId>),
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        match self {
+            Subscription::Connection(connection, id) => {
+                if let Some(id) = id.take() {
+                    connection.signal_unsubscribe(id);
+                }
+            }
+            Subscription::Proxy(proxy, id) => {
+                if let Some(id) = id.take() {
+                    proxy.disconnect(id);
+                }
+            }
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A stream of D-Bus signals.
+///
+/// The signal subscription is removed when the stream is dropped.
+///
+/// Signals whose parameters can't be converted to the requested type are skipped with a
+/// warning.
+#[must_use = "streams do nothing unless polled"]
+pub struct DBusSignalStream<T> {
+    receiver: mpsc::UnboundedReceiver<T>,
+    subscription: Subscription,
+}
+
+impl<T> DBusSignalStream<T> {
+    pub(crate) fn for_connection(
+        connection: &DBusConnection,
+        subscribe: impl FnOnce(mpsc::UnboundedSender<T>) -> SignalSubscriptionId,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        let id = subscribe(sender);
+        Self {
+            receiver,
+            subscription: Subscription::Connection(connection.clone(), Some(id)),
+        }
+    }
+
+    pub(crate) fn for_proxy(
+        proxy: &DBusProxy,
+        connect: impl FnOnce(mpsc::UnboundedSender<T>) -> SignalHandlerId,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        let id = connect(sender);
+        Self {
+            receiver,
+            subscription: Subscription::Proxy(proxy.clone(), Some(id)),
+        }
+    }
+}
+
+impl<T> fmt::Debug for DBusSignalStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DBusSignalStream")
+            .field("subscription", &self.subscription)
+            .finish()
+    }
+}
+
+impl<T> Stream for DBusSignalStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<T>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl<T> FusedStream for DBusSignalStream<T> {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_terminated()
+    }
+}
+
+pub(crate) fn convert_parameters<T: glib::FromVariant>(
+    signal_name: &str,
+    parameters: &glib::Variant,
+) -> Option<T> {
+    match parameters.try_get::<T>() {
+        Ok(parameters) => Some(parameters),
+        Err(err) => {
+            glib::g_warning!(
+                "GIO-Rust",
+                "Ignoring D-Bus signal {} with unexpected parameters: {}",
+                signal_name,
+                err
+            );
+            None
+        }
+    }
+}
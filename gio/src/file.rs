@@ -8,7 +8,7 @@ use glib::{prelude::*, translate::*};
 
 #[cfg(any(feature = "v2_74", feature = "dox"))]
 use crate::FileIOStream;
-use crate::{Cancellable, File, FileCreateFlags, FileEnumerator, FileQueryInfoFlags};
+use crate::{Cancellable, File, FileCreateFlags, FileEnumerator, FileQueryInfoFlags, FileWalk};
 
 impl File {
     #[cfg(any(feature = "v2_74", feature = "dox"))]
@@ -200,7 +200,7 @@ pub trait FileExtManual: Sized {
         Q: FnOnce(Result<FileEnumerator, glib::Error>) + 'static,
     >(
         &self,
-        attributes: &'static str,
+        attributes: &str,
         flags: FileQueryInfoFlags,
         io_priority: glib::Priority,
         cancellable: Option<&P>,
@@ -209,7 +209,7 @@ pub trait FileExtManual: Sized {
 
     fn enumerate_children_future(
         &self,
-        attributes: &'static str,
+        attributes: &str,
         flags: FileQueryInfoFlags,
         io_priority: glib::Priority,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<FileEnumerator, glib::Error>> + 'static>>;
@@ -316,6 +316,12 @@ pub trait FileExtManual: Sized {
         Pin<Box<dyn std::future::Future<Output = Result<(), glib::Error>> + 'static>>,
         Pin<Box<dyn futures_core::stream::Stream<Item = (i64, i64)> + 'static>>,
     );
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a stream that recursively walks the directory tree below this file.
+    ///
+    /// See [`FileWalk`] for the available options.
+    fn walk(&self) -> FileWalk;
 }
 
 impl<O: IsA<File>> FileExtManual for O {
@@ -432,7 +438,7 @@ impl<O: IsA<File>> FileExtManual for O {
         Q: FnOnce(Result<FileEnumerator, glib::Error>) + 'static,
     >(
         &self,
-        attributes: &'static str,
+        attributes: &str,
         flags: FileQueryInfoFlags,
         io_priority: glib::Priority,
         cancellable: Option<&P>,
@@ -486,16 +492,17 @@ impl<O: IsA<File>> FileExtManual for O {
 
     fn enumerate_children_future(
         &self,
-        attributes: &'static str,
+        attributes: &str,
         flags: FileQueryInfoFlags,
         io_priority: glib::Priority,
     ) -> Pin<Box<dyn std::future::Future<Output = Result<FileEnumerator, glib::Error>> + 'static>>
     {
+        let attributes = attributes.to_owned();
         Box::pin(crate::GioFuture::new(
             self,
             move |obj, cancellable, send| {
                 obj.enumerate_children_async(
-                    attributes,
+                    &attributes,
                     flags,
                     io_priority,
                     Some(cancellable),
@@ -1137,4 +1144,8 @@ impl<O: IsA<File>> FileExtManual for O {
             },
         ))
     }
+
+    fn walk(&self) -> FileWalk {
+        FileWalk::new(self.as_ref().clone())
+    }
 }
@@ -1,8 +1,17 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::iter::FusedIterator;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    iter::FusedIterator,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
-use crate::{prelude::*, FileEnumerator, FileInfo};
+use futures_channel::oneshot;
+use futures_core::stream::{FusedStream, Stream};
+
+use crate::{prelude::*, Cancellable, FileEnumerator, FileInfo};
 
 impl Iterator for FileEnumerator {
     type Item = Result<FileInfo, glib::Error>;
@@ -16,3 +25,224 @@ impl Iterator for FileEnumerator {
 }
 
 impl FusedIterator for FileEnumerator {}
+
+impl FileEnumerator {
+    // rustdoc-stripper-ignore-next
+    /// Converts the enumerator into a stream of the remaining files.
+    ///
+    /// The files are requested asynchronously in batches of `batch_size`. The stream ends after
+    /// the first error. Once the stream is dropped the enumerator is closed asynchronously.
+    pub fn into_stream(self, batch_size: i32, priority: glib::Priority) -> FileEnumeratorStream {
+        assert!(batch_size > 0, "batch_size must be positive");
+        FileEnumeratorStream {
+            enumerator: self,
+            batch_size,
+            priority,
+            cancellable: Cancellable::new(),
+            request: None,
+            files: VecDeque::new(),
+            terminated: false,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A stream of the files of a [`FileEnumerator`].
+///
+/// Created by [`FileEnumerator::into_stream`].
+#[must_use = "streams do nothing unless polled"]
+pub struct FileEnumeratorStream {
+    enumerator: FileEnumerator,
+    batch_size: i32,
+    priority: glib::Priority,
+    cancellable: Cancellable,
+    // The result of the pending request for the next files
+    request: Option<oneshot::Receiver<Result<Vec<FileInfo>, glib::Error>>>,
+    files: VecDeque<FileInfo>,
+    terminated: bool,
+}
+
+impl std::fmt::Debug for FileEnumeratorStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("FileEnumeratorStream")
+            .field("enumerator", &self.enumerator)
+            .field("batch_size", &self.batch_size)
+            .field("priority", &self.priority)
+            .field("files", &self.files)
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
+
+impl FileEnumeratorStream {
+    // rustdoc-stripper-ignore-next
+    /// Returns the enumerator, e.g. to get the [`child`](FileEnumeratorExt::child) for a
+    /// returned [`FileInfo`].
+    pub fn enumerator(&self) -> &FileEnumerator {
+        &self.enumerator
+    }
+}
+
+impl Stream for FileEnumeratorStream {
+    type Item = Result<FileInfo, glib::Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Result<FileInfo, glib::Error>>> {
+        loop {
+            if let Some(info) = self.files.pop_front() {
+                return Poll::Ready(Some(Ok(info)));
+            }
+            if self.terminated {
+                return Poll::Ready(None);
+            }
+
+            let this = &mut *self;
+            let request = this.request.get_or_insert_with(|| {
+                let (sender, receiver) = oneshot::channel();
+                let enumerator = this.enumerator.clone();
+                let priority = this.priority;
+                this.enumerator.next_files_async(
+                    this.batch_size,
+                    priority,
+                    Some(&this.cancellable),
+                    move |res| {
+                        // The stream was dropped while the request was pending and the
+                        // enumerator couldn't be closed until now
+                        if sender.send(res).is_err() {
+                            enumerator.close_async(priority, Cancellable::NONE, |_| ());
+                        }
+                    },
+                );
+                receiver
+            });
+            let res = match Pin::new(request).poll(cx) {
+                Poll::Ready(res) => res.expect("Request for the next files was dropped"),
+                Poll::Pending => return Poll::Pending,
+            };
+            self.request = None;
+            match res {
+                Ok(files) if files.is_empty() => self.terminated = true,
+                Ok(files) => self.files.extend(files),
+                Err(err) => {
+                    self.terminated = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.terminated {
+            (self.files.len(), Some(self.files.len()))
+        } else {
+            (self.files.len(), None)
+        }
+    }
+}
+
+impl FusedStream for FileEnumeratorStream {
+    fn is_terminated(&self) -> bool {
+        self.terminated && self.files.is_empty()
+    }
+}
+
+impl Drop for FileEnumeratorStream {
+    fn drop(&mut self) {
+        match self.request.take().map(|mut request| request.try_recv()) {
+            // A pending request is cancelled and closes the enumerator once it completed
+            Some(Ok(None)) => self.cancellable.cancel(),
+            _ if !self.enumerator.is_closed() => {
+                self.enumerator
+                    .close_async(self.priority, Cancellable::NONE, |_| ());
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use futures_util::{FutureExt, StreamExt};
+
+    use super::*;
+    use crate::{test_util::run_async_local, File, FileQueryInfoFlags};
+
+    #[test]
+    fn into_stream() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let expected = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<BTreeSet<_>>();
+
+        let (names, closed) = run_async_local(move |tx, l| {
+            let c = glib::MainContext::ref_thread_default();
+            c.spawn_local(async move {
+                let enumerator = File::for_path(&dir)
+                    .enumerate_children_future(
+                        "standard::name",
+                        FileQueryInfoFlags::NONE,
+                        glib::PRIORITY_DEFAULT,
+                    )
+                    .await
+                    .unwrap();
+                let mut stream = enumerator.into_stream(3, glib::PRIORITY_DEFAULT);
+                let mut names = BTreeSet::new();
+                while let Some(info) = stream.next().await {
+                    names.insert(info.unwrap().name().into_os_string().into_string().unwrap());
+                }
+                assert!(stream.is_terminated());
+
+                let enumerator = stream.enumerator().clone();
+                drop(stream);
+                while !enumerator.is_closed() {
+                    glib::timeout_future(std::time::Duration::from_millis(1)).await;
+                }
+                tx.send((names, enumerator.is_closed())).unwrap();
+                l.quit();
+            });
+        });
+
+        assert_eq!(names, expected);
+        assert!(closed);
+    }
+
+    #[test]
+    fn drop_pending() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+
+        let closed = run_async_local(move |tx, l| {
+            let c = glib::MainContext::ref_thread_default();
+            c.spawn_local(async move {
+                let enumerator = File::for_path(&dir)
+                    .enumerate_children_future(
+                        "standard::name",
+                        FileQueryInfoFlags::NONE,
+                        glib::PRIORITY_DEFAULT,
+                    )
+                    .await
+                    .unwrap();
+                let mut stream = enumerator.clone().into_stream(3, glib::PRIORITY_DEFAULT);
+                assert!(stream.next().now_or_never().is_none());
+                assert!(enumerator.has_pending());
+
+                // The enumerator is still referenced here, so it is not closed by disposing it
+                drop(stream);
+                for _ in 0..1000 {
+                    if enumerator.is_closed() {
+                        break;
+                    }
+                    glib::timeout_future(std::time::Duration::from_millis(5)).await;
+                }
+                tx.send(enumerator.is_closed()).unwrap();
+                l.quit();
+            });
+        });
+
+        assert!(closed);
+    }
+}
@@ -0,0 +1,451 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use glib::SignalHandlerId;
+
+use crate::{prelude::*, File, FileMonitor, FileMonitorEvent};
+
+// rustdoc-stripper-ignore-next
+/// A change reported by a [`FileMonitor`].
+///
+/// Which of the move related changes are reported depends on the
+/// [`FileMonitorFlags`](crate::FileMonitorFlags) the monitor was created with.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum FileChange {
+    Changed(File),
+    // rustdoc-stripper-ignore-next
+    /// A hint that a series of changes to the file is complete.
+    ChangesDone(File),
+    Deleted(File),
+    Created(File),
+    AttributeChanged(File),
+    PreUnmount(File),
+    Unmounted(File),
+    // rustdoc-stripper-ignore-next
+    /// The file was moved to `to`, only reported with `SEND_MOVED`.
+    Moved {
+        from: File,
+        to: Option<File>,
+    },
+    // rustdoc-stripper-ignore-next
+    /// The file was renamed within the monitored directory.
+    Renamed {
+        from: File,
+        to: File,
+    },
+    // rustdoc-stripper-ignore-next
+    /// The file was moved into the monitored directory, from `from` if known.
+    MovedIn {
+        file: File,
+        from: Option<File>,
+    },
+    // rustdoc-stripper-ignore-next
+    /// The file was moved out of the monitored directory, to `to` if known.
+    MovedOut {
+        file: File,
+        to: Option<File>,
+    },
+}
+
+impl FileChange {
+    fn new(file: &File, other_file: Option<&File>, event: FileMonitorEvent) -> Option<Self> {
+        let file = file.clone();
+        let other_file = other_file.cloned();
+        Some(match event {
+            FileMonitorEvent::Changed => Self::Changed(file),
+            FileMonitorEvent::ChangesDoneHint => Self::ChangesDone(file),
+            FileMonitorEvent::Deleted => Self::Deleted(file),
+            FileMonitorEvent::Created => Self::Created(file),
+            FileMonitorEvent::AttributeChanged => Self::AttributeChanged(file),
+            FileMonitorEvent::PreUnmount => Self::PreUnmount(file),
+            FileMonitorEvent::Unmounted => Self::Unmounted(file),
+            FileMonitorEvent::Moved => Self::Moved {
+                from: file,
+                to: other_file,
+            },
+            FileMonitorEvent::Renamed => Self::Renamed {
+                from: file,
+                to: other_file?,
+            },
+            FileMonitorEvent::MovedIn => Self::MovedIn {
+                file,
+                from: other_file,
+            },
+            FileMonitorEvent::MovedOut => Self::MovedOut {
+                file,
+                to: other_file,
+            },
+            _ => return None,
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the file the change happened to, the source for moves.
+    pub fn file(&self) -> &File {
+        match self {
+            Self::Changed(file)
+            | Self::ChangesDone(file)
+            | Self::Deleted(file)
+            | Self::Created(file)
+            | Self::AttributeChanged(file)
+            | Self::PreUnmount(file)
+            | Self::Unmounted(file)
+            | Self::Moved { from: file, .. }
+            | Self::Renamed { from: file, .. }
+            | Self::MovedIn { file, .. }
+            | Self::MovedOut { file, .. } => file,
+        }
+    }
+
+    // Combines two changes of the same file, `None` if they cancel out.
+    fn coalesce(self, next: Self) -> Option<Self> {
+        match (self, next) {
+            (Self::Created(_), Self::Deleted(_)) => None,
+            (
+                Self::Created(file),
+                Self::Changed(_) | Self::ChangesDone(_) | Self::AttributeChanged(_),
+            ) => Some(Self::Created(file)),
+            (Self::Deleted(_), Self::Created(file)) => Some(Self::Changed(file)),
+            (Self::Changed(file), Self::ChangesDone(_) | Self::AttributeChanged(_)) => {
+                Some(Self::Changed(file))
+            }
+            (_, next) => Some(next),
+        }
+    }
+
+    fn is_coalescable(&self) -> bool {
+        matches!(
+            self,
+            Self::Changed(_)
+                | Self::ChangesDone(_)
+                | Self::Deleted(_)
+                | Self::Created(_)
+                | Self::AttributeChanged(_)
+        )
+    }
+}
+
+impl FileMonitor {
+    // rustdoc-stripper-ignore-next
+    /// Converts the monitor into a stream of the changes it reports.
+    ///
+    /// The monitor is cancelled when the stream is dropped.
+    #[doc(alias = "changed")]
+    pub fn into_stream(self) -> FileMonitorStream {
+        let (sender, receiver) = mpsc::unbounded();
+        let id = self.connect_changed(move |_monitor, file, other_file, event| {
+            if let Some(change) = FileChange::new(file, other_file, event) {
+                let _ = sender.unbounded_send(change);
+            }
+        });
+
+        FileMonitorStream {
+            monitor: self,
+            id: Some(id),
+            receiver,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A stream of the changes reported by a [`FileMonitor`].
+///
+/// Created by [`FileMonitor::into_stream`].
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct FileMonitorStream {
+    monitor: FileMonitor,
+    id: Option<SignalHandlerId>,
+    receiver: mpsc::UnboundedReceiver<FileChange>,
+}
+
+impl FileMonitorStream {
+    // rustdoc-stripper-ignore-next
+    /// Returns the monitor the changes are reported by.
+    pub fn monitor(&self) -> &FileMonitor {
+        &self.monitor
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Coalesces the changes of each file over `window`.
+    ///
+    /// A change is only returned once no further change to the same file was reported for
+    /// `window`. Until then it is combined with the following changes, e.g. a file that was
+    /// created and then modified is reported as [`FileChange::Created`], and a file that was
+    /// created and then deleted again is not reported at all. Moves, renames and unmounts are
+    /// returned right away, after the pending changes of the files involved.
+    pub fn debounce(self, window: Duration) -> FileMonitorDebounce {
+        FileMonitorDebounce {
+            stream: self,
+            window,
+            pending: Vec::new(),
+            ready: VecDeque::new(),
+            timer: None,
+            terminated: false,
+        }
+    }
+}
+
+impl Stream for FileMonitorStream {
+    type Item = FileChange;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<FileChange>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl FusedStream for FileMonitorStream {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_terminated()
+    }
+}
+
+impl Drop for FileMonitorStream {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            self.monitor.disconnect(id);
+        }
+        self.monitor.cancel();
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A stream of the coalesced changes reported by a [`FileMonitor`].
+///
+/// Created by [`FileMonitorStream::debounce`].
+#[must_use = "streams do nothing unless polled"]
+pub struct FileMonitorDebounce {
+    stream: FileMonitorStream,
+    window: Duration,
+    // Changes waiting for the window to pass, in the order of their deadlines
+    pending: Vec<(FileChange, Instant)>,
+    ready: VecDeque<FileChange>,
+    timer: Option<Pin<Box<dyn Future<Output = ()> + 'static>>>,
+    terminated: bool,
+}
+
+impl std::fmt::Debug for FileMonitorDebounce {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("FileMonitorDebounce")
+            .field("stream", &self.stream)
+            .field("window", &self.window)
+            .field("pending", &self.pending)
+            .field("ready", &self.ready)
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
+
+impl FileMonitorDebounce {
+    // rustdoc-stripper-ignore-next
+    /// Returns the monitor the changes are reported by.
+    pub fn monitor(&self) -> &FileMonitor {
+        self.stream.monitor()
+    }
+
+    fn flush(&mut self, file: &File) {
+        if let Some(pos) = self.pending.iter().position(|(c, _)| c.file().equal(file)) {
+            let (change, _) = self.pending.remove(pos);
+            self.ready.push_back(change);
+        }
+    }
+
+    fn push(&mut self, change: FileChange) {
+        if !change.is_coalescable() {
+            let files = match &change {
+                FileChange::Moved { from, to } => [Some(from.clone()), to.clone()],
+                FileChange::Renamed { from, to } => [Some(from.clone()), Some(to.clone())],
+                FileChange::MovedIn { file, from: other }
+                | FileChange::MovedOut { file, to: other } => [Some(file.clone()), other.clone()],
+                change => [Some(change.file().clone()), None],
+            };
+            for file in files.iter().flatten() {
+                self.flush(file);
+            }
+            self.ready.push_back(change);
+            return;
+        }
+
+        let deadline = Instant::now() + self.window;
+        let previous = self
+            .pending
+            .iter()
+            .position(|(c, _)| c.file().equal(change.file()))
+            .map(|pos| self.pending.remove(pos).0);
+        let change = match previous {
+            Some(previous) => previous.coalesce(change),
+            None => Some(change),
+        };
+        if let Some(change) = change {
+            self.pending.push((change, deadline));
+        }
+    }
+}
+
+impl Stream for FileMonitorDebounce {
+    type Item = FileChange;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<FileChange>> {
+        let this = &mut *self;
+
+        while !this.terminated {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(change)) => this.push(change),
+                Poll::Ready(None) => {
+                    this.terminated = true;
+                    let pending = std::mem::take(&mut this.pending);
+                    this.ready
+                        .extend(pending.into_iter().map(|(change, _)| change));
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        loop {
+            if let Some(change) = this.ready.pop_front() {
+                return Poll::Ready(Some(change));
+            }
+            let deadline = match this.pending.first() {
+                Some((_, deadline)) => *deadline,
+                None if this.terminated => return Poll::Ready(None),
+                None => {
+                    this.timer = None;
+                    return Poll::Pending;
+                }
+            };
+
+            let now = Instant::now();
+            if deadline <= now {
+                this.timer = None;
+                let (change, _) = this.pending.remove(0);
+                return Poll::Ready(Some(change));
+            }
+            // The timer is recreated as the earliest deadline might have changed
+            let mut timer = glib::timeout_future(deadline - now);
+            match timer.as_mut().poll(cx) {
+                Poll::Ready(()) => continue,
+                Poll::Pending => {
+                    this.timer = Some(timer);
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+impl FusedStream for FileMonitorDebounce {
+    fn is_terminated(&self) -> bool {
+        self.terminated && self.pending.is_empty() && self.ready.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use futures_util::StreamExt;
+
+    use super::*;
+    use crate::{
+        test_util::{run_async_local, TempDir},
+        FileMonitorFlags,
+    };
+
+    // The events are emitted by the tests, nothing else changes in the monitored directory
+    fn monitor(dir: &Path) -> FileMonitor {
+        File::for_path(dir)
+            .monitor_directory(FileMonitorFlags::WATCH_MOVES, crate::Cancellable::NONE)
+            .unwrap()
+    }
+
+    fn describe(change: &FileChange) -> String {
+        let name = |file: &File| file.basename().unwrap().to_str().unwrap().to_owned();
+        match change {
+            FileChange::Changed(file) => format!("changed {}", name(file)),
+            FileChange::Created(file) => format!("created {}", name(file)),
+            FileChange::Deleted(file) => format!("deleted {}", name(file)),
+            FileChange::Renamed { from, to } => format!("renamed {} {}", name(from), name(to)),
+            // Fails the comparison, a panic in the spawned future would stall the main loop
+            change => format!("{change:?}"),
+        }
+    }
+
+    #[test]
+    fn into_stream() {
+        let dir = TempDir::new();
+        let path = dir.path().to_owned();
+        let (changes, cancelled) = run_async_local(move |tx, l| {
+            let c = glib::MainContext::ref_thread_default();
+            c.spawn_local(async move {
+                let child = |name| File::for_path(&path).child(name);
+                let mut stream = monitor(&path).into_stream();
+                let monitor = stream.monitor().clone();
+                monitor.emit_event(&child("a"), &child("a"), FileMonitorEvent::Created);
+                monitor.emit_event(&child("a"), &child("b"), FileMonitorEvent::Renamed);
+                monitor.emit_event(&child("b"), &child("b"), FileMonitorEvent::Deleted);
+
+                let mut changes = Vec::new();
+                for _ in 0..3 {
+                    changes.push(describe(&stream.next().await.unwrap()));
+                }
+                drop(stream);
+                tx.send((changes, monitor.is_cancelled())).unwrap();
+                l.quit();
+            });
+        });
+
+        assert_eq!(changes, ["created a", "renamed a b", "deleted b"]);
+        assert!(cancelled);
+    }
+
+    #[test]
+    fn debounce() {
+        let dir = TempDir::new();
+        let path = dir.path().to_owned();
+        let changes = run_async_local(move |tx, l| {
+            let c = glib::MainContext::ref_thread_default();
+            c.spawn_local(async move {
+                let child = |name| File::for_path(&path).child(name);
+                let mut stream = monitor(&path)
+                    .into_stream()
+                    .debounce(Duration::from_millis(20));
+                let monitor = stream.monitor().clone();
+                for (name, event) in [
+                    ("a", FileMonitorEvent::Created),
+                    ("b", FileMonitorEvent::Changed),
+                    ("a", FileMonitorEvent::Changed),
+                    ("c", FileMonitorEvent::Created),
+                    ("b", FileMonitorEvent::ChangesDoneHint),
+                    ("c", FileMonitorEvent::Deleted),
+                    ("a", FileMonitorEvent::ChangesDoneHint),
+                ] {
+                    monitor.emit_event(&child(name), &child(name), event);
+                }
+                monitor.emit_event(&child("d"), &child("d"), FileMonitorEvent::Changed);
+                monitor.emit_event(&child("d"), &child("e"), FileMonitorEvent::Renamed);
+
+                let mut changes = Vec::new();
+                for _ in 0..4 {
+                    changes.push(describe(&stream.next().await.unwrap()));
+                }
+                tx.send(changes).unwrap();
+                l.quit();
+            });
+        });
+
+        assert_eq!(
+            changes,
+            ["changed d", "renamed d e", "changed b", "created a"]
+        );
+    }
+}
@@ -0,0 +1,518 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::stream::{FusedStream, Stream};
+
+use crate::{
+    prelude::*, File, FileEnumerator, FileEnumeratorStream, FileInfo, FileQueryInfoFlags, FileType,
+    IOErrorEnum,
+};
+
+// Number of files requested from an enumerator at once
+const BATCH_SIZE: i32 = 64;
+
+type Filter = Box<dyn Fn(&File, &FileInfo, usize) -> bool + 'static>;
+type InfoFuture = Pin<Box<dyn Future<Output = Result<FileInfo, glib::Error>> + 'static>>;
+type EnumeratorFuture =
+    Pin<Box<dyn Future<Output = Result<FileEnumerator, glib::Error>> + 'static>>;
+
+// A directory to walk, with the identities of itself and its ancestors when following symlinks
+struct Dir {
+    file: File,
+    depth: usize,
+    ids: Vec<String>,
+}
+
+enum ActiveDir {
+    Opening(Dir, EnumeratorFuture),
+    Reading(Dir, FileEnumeratorStream),
+}
+
+enum Event {
+    Opened(Result<FileEnumerator, glib::Error>),
+    File(File, FileInfo, usize, Vec<String>),
+    Error(File, glib::Error),
+    Done,
+    Pending,
+}
+
+enum State {
+    Start(File),
+    Root(File, InfoFuture),
+    Walking,
+}
+
+// rustdoc-stripper-ignore-next
+/// A recursive walk over a directory tree.
+///
+/// Created by [`FileExtManual::walk`], the options have to be set before the stream is first
+/// polled. The stream yields every file together with its [`FileInfo`] and its depth, starting
+/// with the root at depth 0. Errors are yielded together with the file they occurred for, the
+/// walk then continues with the remaining files.
+///
+/// Dropping the stream cancels all pending operations.
+#[must_use = "streams do nothing unless polled"]
+pub struct FileWalk {
+    attributes: String,
+    max_depth: usize,
+    follow_symlinks: bool,
+    filter: Option<Filter>,
+    max_concurrency: usize,
+    priority: glib::Priority,
+    state: State,
+    pending: Vec<Dir>,
+    active: Vec<ActiveDir>,
+}
+
+impl std::fmt::Debug for FileWalk {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("FileWalk")
+            .field("attributes", &self.attributes)
+            .field("max_depth", &self.max_depth)
+            .field("follow_symlinks", &self.follow_symlinks)
+            .field("max_concurrency", &self.max_concurrency)
+            .field("priority", &self.priority)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileWalk {
+    pub(crate) fn new(root: File) -> Self {
+        Self {
+            attributes: String::from("standard::*"),
+            max_depth: usize::MAX,
+            follow_symlinks: false,
+            filter: None,
+            max_concurrency: 1,
+            priority: glib::PRIORITY_DEFAULT,
+            state: State::Start(root),
+            pending: Vec::new(),
+            active: Vec::new(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the attributes to query for every file, `standard::*` by default.
+    ///
+    /// `standard::name` and `standard::type` are always queried.
+    pub fn attributes(mut self, attributes: &str) -> Self {
+        self.attributes = attributes.to_owned();
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the maximum depth of the returned files, the root has depth 0.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets whether to descend into symbolic links to directories, `false` by default.
+    ///
+    /// With `true` the returned information is about the link targets. Links that lead back to
+    /// one of their ancestors are reported as [`IOErrorEnum::TooManyLinks`] errors.
+    pub fn follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Only returns the files for which `filter` returns `true`.
+    ///
+    /// Directories that are filtered out are not descended into.
+    pub fn filter<F: Fn(&File, &FileInfo, usize) -> bool + 'static>(mut self, filter: F) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the number of directories that are read concurrently, 1 by default.
+    pub fn max_concurrency(mut self, max_concurrency: usize) -> Self {
+        assert!(max_concurrency > 0, "max_concurrency must be positive");
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the I/O priority of the requests.
+    pub fn priority(mut self, priority: glib::Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    fn query_attributes(&self) -> String {
+        let mut attributes = format!("{},standard::name,standard::type", self.attributes);
+        if self.follow_symlinks {
+            attributes.push_str(",standard::is-symlink,id::file");
+        }
+        attributes
+    }
+
+    fn flags(&self) -> FileQueryInfoFlags {
+        if self.follow_symlinks {
+            FileQueryInfoFlags::NONE
+        } else {
+            FileQueryInfoFlags::NOFOLLOW_SYMLINKS
+        }
+    }
+
+    // Filters `file` and queues it to be walked if it is a directory.
+    fn visit(
+        &mut self,
+        file: &File,
+        info: &FileInfo,
+        depth: usize,
+        ids: &[String],
+    ) -> Result<bool, glib::Error> {
+        if let Some(ref filter) = self.filter {
+            if !filter(file, info, depth) {
+                return Ok(false);
+            }
+        }
+        if depth >= self.max_depth || info.file_type() != FileType::Directory {
+            return Ok(true);
+        }
+
+        let mut ids = ids.to_vec();
+        if self.follow_symlinks {
+            let id = info
+                .attribute_string("id::file")
+                .map_or_else(|| file.uri().to_string(), |id| id.to_string());
+            if info.is_symlink() && ids.contains(&id) {
+                return Err(glib::Error::new(
+                    IOErrorEnum::TooManyLinks,
+                    &format!("Filesystem loop at {}", file.uri()),
+                ));
+            }
+            ids.push(id);
+        }
+        self.pending.push(Dir {
+            file: file.clone(),
+            depth,
+            ids,
+        });
+        Ok(true)
+    }
+}
+
+impl Stream for FileWalk {
+    type Item = Result<(File, FileInfo, usize), (File, glib::Error)>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        match std::mem::replace(&mut this.state, State::Walking) {
+            State::Start(root) => {
+                let future =
+                    root.query_info_future(&this.query_attributes(), this.flags(), this.priority);
+                this.state = State::Root(root, future);
+                return Pin::new(this).poll_next(cx);
+            }
+            State::Root(root, mut future) => {
+                let res = match future.as_mut().poll(cx) {
+                    Poll::Ready(res) => res,
+                    Poll::Pending => {
+                        this.state = State::Root(root, future);
+                        return Poll::Pending;
+                    }
+                };
+                return Poll::Ready(match res {
+                    Ok(info) => match this.visit(&root, &info, 0, &[]) {
+                        Ok(true) => Some(Ok((root, info, 0))),
+                        Ok(false) => None,
+                        Err(err) => Some(Err((root, err))),
+                    },
+                    Err(err) => Some(Err((root, err))),
+                });
+            }
+            State::Walking => (),
+        }
+
+        loop {
+            while this.active.len() < this.max_concurrency {
+                let dir = match this.pending.pop() {
+                    Some(dir) => dir,
+                    None => break,
+                };
+                let future = dir.file.enumerate_children_future(
+                    &this.query_attributes(),
+                    this.flags(),
+                    this.priority,
+                );
+                this.active.push(ActiveDir::Opening(dir, future));
+            }
+            if this.active.is_empty() {
+                return Poll::Ready(None);
+            }
+
+            let mut i = 0;
+            let mut progress = false;
+            while i < this.active.len() {
+                let event = match &mut this.active[i] {
+                    ActiveDir::Opening(_, future) => match future.as_mut().poll(cx) {
+                        Poll::Ready(res) => Event::Opened(res),
+                        Poll::Pending => Event::Pending,
+                    },
+                    ActiveDir::Reading(dir, stream) => match Pin::new(&mut *stream).poll_next(cx) {
+                        Poll::Ready(Some(Ok(info))) => Event::File(
+                            stream.enumerator().child(&info),
+                            info,
+                            dir.depth + 1,
+                            dir.ids.clone(),
+                        ),
+                        Poll::Ready(Some(Err(err))) => Event::Error(dir.file.clone(), err),
+                        Poll::Ready(None) => Event::Done,
+                        Poll::Pending => Event::Pending,
+                    },
+                };
+
+                match event {
+                    Event::Opened(res) => {
+                        let dir = match this.active.swap_remove(i) {
+                            ActiveDir::Opening(dir, _) => dir,
+                            ActiveDir::Reading(..) => unreachable!(),
+                        };
+                        match res {
+                            Ok(enumerator) => {
+                                let stream = enumerator.into_stream(BATCH_SIZE, this.priority);
+                                this.active.push(ActiveDir::Reading(dir, stream));
+                                progress = true;
+                            }
+                            Err(err) => return Poll::Ready(Some(Err((dir.file, err)))),
+                        }
+                    }
+                    Event::File(file, info, depth, ids) => {
+                        match this.visit(&file, &info, depth, &ids) {
+                            Ok(true) => return Poll::Ready(Some(Ok((file, info, depth)))),
+                            Ok(false) => progress = true,
+                            Err(err) => return Poll::Ready(Some(Err((file, err)))),
+                        }
+                    }
+                    Event::Error(file, err) => return Poll::Ready(Some(Err((file, err)))),
+                    Event::Done => {
+                        this.active.swap_remove(i);
+                        progress = true;
+                    }
+                    Event::Pending => i += 1,
+                }
+            }
+
+            if !progress {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl FusedStream for FileWalk {
+    fn is_terminated(&self) -> bool {
+        matches!(self.state, State::Walking) && self.pending.is_empty() && self.active.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeSet,
+        path::{Path, PathBuf},
+    };
+
+    use futures_util::StreamExt;
+
+    use super::*;
+    use crate::test_util::{run_async_local, TempDir};
+
+    type Results = (
+        BTreeSet<(String, usize)>,
+        BTreeSet<(String, Option<IOErrorEnum>)>,
+    );
+
+    // Walks `root` and returns the files and the errors by their path relative to it.
+    fn walk_results(
+        root: PathBuf,
+        walk: impl FnOnce(File) -> FileWalk + Send + 'static,
+    ) -> Results {
+        run_async_local(move |tx, l| {
+            let c = glib::MainContext::ref_thread_default();
+            c.spawn_local(async move {
+                let root = File::for_path(root);
+                let relative = |file: &File| {
+                    let path = root.relative_path(file).unwrap_or_default();
+                    path.to_str().unwrap().to_owned()
+                };
+                let mut files = BTreeSet::new();
+                let mut errors = BTreeSet::new();
+                let mut walk = walk(root.clone());
+                while let Some(res) = walk.next().await {
+                    match res {
+                        Ok((file, _info, depth)) => files.insert((relative(&file), depth)),
+                        Err((file, err)) => errors.insert((relative(&file), err.kind())),
+                    };
+                }
+                tx.send((files, errors)).unwrap();
+                l.quit();
+            });
+        })
+    }
+
+    fn walk_names(
+        walk: impl FnOnce(File) -> FileWalk + Send + 'static,
+    ) -> BTreeSet<(String, usize)> {
+        let (names, errors) = walk_results(Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), walk);
+        assert!(errors.is_empty());
+        names
+    }
+
+    fn expected(
+        dir: &Path,
+        relative: &str,
+        depth: usize,
+        max_depth: usize,
+        names: &mut BTreeSet<(String, usize)>,
+    ) {
+        if depth > max_depth {
+            return;
+        }
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let entry = entry.unwrap();
+            let name = format!("{relative}{}", entry.file_name().to_str().unwrap());
+            names.insert((name.clone(), depth));
+            if entry.file_type().unwrap().is_dir() {
+                expected(
+                    &entry.path(),
+                    &format!("{name}/"),
+                    depth + 1,
+                    max_depth,
+                    names,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn walk() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut names = BTreeSet::new();
+        names.insert((String::new(), 0));
+        expected(&src, "", 1, usize::MAX, &mut names);
+
+        assert_eq!(walk_names(|root| root.walk()), names);
+        assert_eq!(
+            walk_names(|root| root.walk().max_concurrency(4).attributes("standard::size")),
+            names
+        );
+
+        let max_depth_1 = names
+            .iter()
+            .filter(|(_, depth)| *depth <= 1)
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        assert_eq!(walk_names(|root| root.walk().max_depth(1)), max_depth_1);
+
+        let without_auto = names
+            .iter()
+            .filter(|(name, _)| !name.starts_with("auto"))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        assert_eq!(
+            walk_names(|root| root
+                .walk()
+                .filter(|file, _, _| !file.uri().ends_with("/auto"))),
+            without_auto
+        );
+    }
+
+    #[test]
+    fn walk_errors() {
+        let errors = run_async_local(|tx, l| {
+            let c = glib::MainContext::ref_thread_default();
+            c.spawn_local(async move {
+                let root = File::for_path(Path::new(env!("CARGO_MANIFEST_DIR")).join("missing"));
+                let errors = root
+                    .walk()
+                    .map(|res| res.unwrap_err().1.matches(IOErrorEnum::NotFound))
+                    .collect::<Vec<_>>()
+                    .await;
+                tx.send(errors).unwrap();
+                l.quit();
+            });
+        });
+        assert_eq!(errors, [true]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn walk_symlinks() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let dir = TempDir::new();
+        let root = dir.path();
+        std::fs::create_dir(root.join("a")).unwrap();
+        std::fs::write(root.join("a/file"), b"").unwrap();
+        symlink("..", root.join("a/loop")).unwrap();
+        symlink("a", root.join("link")).unwrap();
+        std::fs::create_dir(root.join("locked")).unwrap();
+        std::fs::set_permissions(root.join("locked"), PermissionsExt::from_mode(0o000)).unwrap();
+
+        let names = |names: &[(&str, usize)]| {
+            names
+                .iter()
+                .map(|(name, depth)| (name.to_string(), *depth))
+                .collect::<BTreeSet<_>>()
+        };
+        // Errors don't stop the walk. The locked directory can still be read as root.
+        let errors = |errors: &[(&str, IOErrorEnum)]| {
+            let mut errors = errors
+                .iter()
+                .map(|(name, kind)| (name.to_string(), Some(*kind)))
+                .collect::<BTreeSet<_>>();
+            if std::fs::read_dir(root.join("locked")).is_err() {
+                errors.insert(("locked".into(), Some(IOErrorEnum::PermissionDenied)));
+            }
+            errors
+        };
+
+        let (files, errs) = walk_results(root.to_owned(), |root| root.walk());
+        assert_eq!(
+            files,
+            names(&[
+                ("", 0),
+                ("a", 1),
+                ("a/file", 2),
+                ("a/loop", 2),
+                ("link", 1),
+                ("locked", 1),
+            ])
+        );
+        assert_eq!(errs, errors(&[]));
+
+        // Links back to an ancestor are reported instead of being followed
+        let (files, errs) = walk_results(root.to_owned(), |root| root.walk().follow_symlinks(true));
+        assert_eq!(
+            files,
+            names(&[
+                ("", 0),
+                ("a", 1),
+                ("a/file", 2),
+                ("link", 1),
+                ("link/file", 2),
+                ("locked", 1),
+            ])
+        );
+        assert_eq!(
+            errs,
+            errors(&[
+                ("a/loop", IOErrorEnum::TooManyLinks),
+                ("link/loop", IOErrorEnum::TooManyLinks),
+            ])
+        );
+
+        std::fs::set_permissions(root.join("locked"), PermissionsExt::from_mode(0o755)).unwrap();
+    }
+}
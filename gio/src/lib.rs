@@ -11,6 +11,7 @@
 
 pub use ffi;
 pub use glib;
+pub use glib_macros::{dbus_interface, settings};
 
 mod action_entry;
 mod action_map;
@@ -27,14 +28,38 @@ mod data_input_stream;
 mod datagram_based;
 mod dbus;
 pub use self::dbus::*;
+mod dbus_annotation_info;
+mod dbus_arg_info;
 mod dbus_connection;
 pub use self::dbus_connection::{
     ActionGroupExportId, FilterId, MenuModelExportId, RegistrationId, SignalSubscriptionId,
     WatcherId,
 };
+mod dbus_interface;
+pub use self::dbus_interface::DBusObjectRegistration;
+mod dbus_interface_info;
+pub use self::dbus_interface_info::DBusInterfaceInfoBuilder;
+mod dbus_interface_skeleton;
+mod dbus_introspection;
 mod dbus_message;
+pub use self::dbus_message::{DBusMessageBuilder, DBusMessageHeader};
+mod dbus_method_info;
+pub use self::dbus_method_info::DBusMethodInfoBuilder;
 mod dbus_method_invocation;
 mod dbus_node_info;
+pub use self::dbus_node_info::DBusNodeInfoBuilder;
+mod dbus_object_manager;
+pub use self::dbus_object_manager::{DBusObjectManagerEvent, DBusObjectManagerEvents};
+mod dbus_object_manager_client;
+pub use self::dbus_object_manager_client::DBusProxyTypeFn;
+mod dbus_property_info;
+pub use self::dbus_property_info::DBusPropertyInfoBuilder;
+mod dbus_server;
+pub use self::dbus_server::DBusServerIncoming;
+mod dbus_signal_info;
+pub use self::dbus_signal_info::DBusSignalInfoBuilder;
+mod dbus_signal_stream;
+pub use self::dbus_signal_stream::{DBusSignal, DBusSignalStream};
 #[cfg(any(feature = "v2_72", feature = "dox"))]
 #[cfg_attr(feature = "dox", doc(cfg(feature = "v2_72")))]
 mod debug_controller_dbus;
@@ -48,7 +73,12 @@ mod file_attribute_info_list;
 mod file_attribute_matcher;
 pub use crate::file_attribute_matcher::FileAttributematcherIter;
 mod file_enumerator;
+pub use self::file_enumerator::FileEnumeratorStream;
 mod file_info;
+mod file_monitor;
+pub use self::file_monitor::{FileChange, FileMonitorDebounce, FileMonitorStream};
+mod file_walk;
+pub use self::file_walk::FileWalk;
 mod flags;
 mod inet_address;
 pub use crate::inet_address::InetAddressBytes;
@@ -73,15 +103,21 @@ pub use crate::pollable_output_stream::OutputStreamAsyncWrite;
 mod resource;
 pub use crate::resource::resources_register_include_impl;
 mod settings;
-pub use crate::settings::BindingBuilder;
+pub use crate::settings::{BindingBuilder, SettingsChanges};
+mod settings_backend;
+pub use crate::settings_backend::SettingsBackendOriginTag;
 mod simple_proxy_resolver;
 mod socket;
 pub use socket::{InputMessage, InputVector, OutputMessage, OutputVector, SocketControlMessages};
 mod socket_control_message;
+mod socket_listener;
+pub use socket_listener::SocketListenerIncoming;
 mod socket_msg_flags;
 pub use socket_msg_flags::SocketMsgFlags;
 mod subprocess;
 mod subprocess_launcher;
+mod test_dbus;
+pub use self::test_dbus::TestDBusGuard;
 mod threaded_socket_service;
 #[cfg(any(unix, feature = "dox"))]
 mod unix_fd_list;
@@ -97,6 +133,7 @@ mod unix_mount_point;
 mod unix_output_stream;
 #[cfg(any(unix, feature = "dox"))]
 mod unix_socket_address;
+mod vfs;
 
 #[cfg(test)]
 mod test_util;
@@ -125,6 +162,10 @@ pub use crate::task::*;
 
 #[macro_use]
 pub mod subclass;
+mod async_read_input_stream;
+pub use crate::async_read_input_stream::AsyncReadInputStream;
+mod async_write_output_stream;
+pub use crate::async_write_output_stream::AsyncWriteOutputStream;
 mod read_input_stream;
 pub use crate::read_input_stream::ReadInputStream;
 mod write_output_stream;
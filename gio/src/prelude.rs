@@ -26,11 +26,14 @@ pub use crate::unix_output_stream::UnixOutputStreamExtManual;
 pub use crate::unix_socket_address::{UnixSocketAddressExtManual, UnixSocketAddressPath};
 pub use crate::{
     action_map::ActionMapExtManual, application::*, auto::traits::*, cancellable::*, converter::*,
-    data_input_stream::DataInputStreamExtManual, datagram_based::*, dbus_proxy::DBusProxyExtManual,
+    data_input_stream::DataInputStreamExtManual, datagram_based::*,
+    dbus_interface_skeleton::DBusInterfaceSkeletonExtManual,
+    dbus_object_manager::DBusObjectManagerExtManual, dbus_proxy::DBusProxyExtManual,
     file::FileExtManual, inet_address::InetAddressExtManual, input_stream::InputStreamExtManual,
     io_stream::IOStreamExtManual, list_model::ListModelExtManual,
     output_stream::OutputStreamExtManual, pollable_input_stream::PollableInputStreamExtManual,
     pollable_output_stream::PollableOutputStreamExtManual, settings::SettingsExtManual,
+    settings_backend::SettingsBackendExtManual,
     simple_proxy_resolver::SimpleProxyResolverExtManual, socket::SocketExtManual,
-    socket_control_message::*, tls_connection::TlsConnectionExtManual,
+    socket_control_message::*, tls_connection::TlsConnectionExtManual, vfs::VfsExtManual,
 };
@@ -1,6 +1,14 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use glib::{prelude::*, translate::*, variant::FromVariant, BoolError, Variant};
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_channel::mpsc;
+use futures_core::stream::{FusedStream, Stream};
+use glib::{prelude::*, translate::*, variant::FromVariant, BoolError, SignalHandlerId, Variant};
 
 use crate::{prelude::*, Settings, SettingsBindFlags};
 
@@ -178,6 +186,17 @@ pub trait SettingsExtManual {
         object: &'a P,
         property: &'a str,
     ) -> BindingBuilder<'a>;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a stream of the new values of `key` whenever it changes.
+    ///
+    /// Values that can't be converted to `U` are skipped with a warning. The signal handler is
+    /// disconnected when the stream is dropped.
+    ///
+    /// Changes are only delivered while the thread-default main context at the time the settings
+    /// were created is iterated.
+    #[doc(alias = "changed")]
+    fn receive_changes<U: FromVariant + 'static>(&self, key: &str) -> SettingsChanges<U>;
 }
 
 impl<O: IsA<Settings>> SettingsExtManual for O {
@@ -212,6 +231,85 @@ impl<O: IsA<Settings>> SettingsExtManual for O {
             set_mapping: None,
         }
     }
+
+    fn receive_changes<U: FromVariant + 'static>(&self, key: &str) -> SettingsChanges<U> {
+        let (sender, receiver) = mpsc::unbounded();
+        let id = self
+            .upcast_ref::<Settings>()
+            .connect_changed(Some(key), move |settings, key| {
+                let value = settings.value(key);
+                match value.try_get::<U>() {
+                    Ok(value) => {
+                        let _ = sender.unbounded_send(value);
+                    }
+                    Err(err) => {
+                        glib::g_warning!(
+                            "GIO-Rust",
+                            "Ignoring change of settings key {} with unexpected value: {}",
+                            key,
+                            err
+                        );
+                    }
+                }
+            });
+        // Some backends only emit change notifications for keys that were read at least once
+        let _ = self.value(key);
+
+        SettingsChanges {
+            settings: self.upcast_ref::<Settings>().clone(),
+            id: Some(id),
+            receiver,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A stream of the values of a [`Settings`] key.
+///
+/// Created by [`SettingsExtManual::receive_changes`].
+#[must_use = "streams do nothing unless polled"]
+pub struct SettingsChanges<T> {
+    settings: Settings,
+    id: Option<SignalHandlerId>,
+    receiver: mpsc::UnboundedReceiver<T>,
+}
+
+impl<T> SettingsChanges<T> {
+    // rustdoc-stripper-ignore-next
+    /// Returns the settings the changes are received from.
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+}
+
+impl<T> fmt::Debug for SettingsChanges<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SettingsChanges")
+            .field("settings", &self.settings)
+            .finish()
+    }
+}
+
+impl<T> Stream for SettingsChanges<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<T>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl<T> FusedStream for SettingsChanges<T> {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_terminated()
+    }
+}
+
+impl<T> Drop for SettingsChanges<T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            self.settings.disconnect(id);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,178 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{collections::BTreeMap, ffi::CStr, ptr};
+
+use glib::{prelude::*, translate::*, Variant};
+use libc::{c_char, c_int};
+
+use crate::SettingsBackend;
+
+// rustdoc-stripper-ignore-next
+/// Opaque tag identifying the origin of a change to a [`SettingsBackend`].
+///
+/// Backends receive it in their `write`, `write_tree` and `reset` implementations and have to
+/// pass it on when emitting the resulting change notifications.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SettingsBackendOriginTag(glib::ffi::gpointer);
+
+// The tag is only ever compared, never dereferenced.
+unsafe impl Send for SettingsBackendOriginTag {}
+unsafe impl Sync for SettingsBackendOriginTag {}
+
+impl SettingsBackendOriginTag {
+    pub(crate) fn from_raw(ptr: glib::ffi::gpointer) -> Option<Self> {
+        if ptr.is_null() {
+            None
+        } else {
+            Some(Self(ptr))
+        }
+    }
+}
+
+pub(crate) fn origin_tag_ptr(origin_tag: Option<SettingsBackendOriginTag>) -> glib::ffi::gpointer {
+    origin_tag.map_or(ptr::null_mut(), |tag| tag.0)
+}
+
+unsafe extern "C" fn tree_compare(
+    a: glib::ffi::gconstpointer,
+    b: glib::ffi::gconstpointer,
+    _user_data: glib::ffi::gpointer,
+) -> c_int {
+    libc::strcmp(a as *const c_char, b as *const c_char)
+}
+
+unsafe extern "C" fn tree_value_unref(value: glib::ffi::gpointer) {
+    if !value.is_null() {
+        glib::ffi::g_variant_unref(value as *mut glib::ffi::GVariant);
+    }
+}
+
+// Create a `GTree` as used by `GSettingsBackend`, `None` values mark keys to reset.
+pub(crate) fn tree_to_glib(tree: &BTreeMap<String, Option<Variant>>) -> *mut glib::ffi::GTree {
+    unsafe {
+        let ptr = glib::ffi::g_tree_new_full(
+            Some(tree_compare),
+            ptr::null_mut(),
+            Some(glib::ffi::g_free),
+            Some(tree_value_unref),
+        );
+        for (key, value) in tree {
+            glib::ffi::g_tree_insert(
+                ptr,
+                ToGlibPtr::<*mut c_char>::to_glib_full(key.as_str()) as glib::ffi::gpointer,
+                ToGlibPtr::<*mut glib::ffi::GVariant>::to_glib_full(value) as glib::ffi::gpointer,
+            );
+        }
+        ptr
+    }
+}
+
+pub(crate) unsafe fn tree_from_glib(
+    ptr: *mut glib::ffi::GTree,
+) -> BTreeMap<String, Option<Variant>> {
+    unsafe extern "C" fn insert(
+        key: glib::ffi::gpointer,
+        value: glib::ffi::gpointer,
+        tree: glib::ffi::gpointer,
+    ) -> glib::ffi::gboolean {
+        let tree = &mut *(tree as *mut BTreeMap<String, Option<Variant>>);
+        let key = CStr::from_ptr(key as *const c_char)
+            .to_string_lossy()
+            .into_owned();
+        tree.insert(key, from_glib_none(value as *mut glib::ffi::GVariant));
+        glib::ffi::GFALSE
+    }
+
+    let mut tree = BTreeMap::new();
+    glib::ffi::g_tree_foreach(
+        ptr,
+        Some(insert),
+        &mut tree as *mut BTreeMap<String, Option<Variant>> as glib::ffi::gpointer,
+    );
+    tree
+}
+
+pub trait SettingsBackendExtManual: 'static {
+    // rustdoc-stripper-ignore-next
+    /// Signals that `key` changed, with the `origin_tag` of the write that caused it, if any.
+    #[doc(alias = "g_settings_backend_changed")]
+    fn changed(&self, key: &str, origin_tag: Option<SettingsBackendOriginTag>);
+
+    // rustdoc-stripper-ignore-next
+    /// Signals that all keys in `tree` changed.
+    #[doc(alias = "g_settings_backend_changed_tree")]
+    fn changed_tree(
+        &self,
+        tree: &BTreeMap<String, Option<Variant>>,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    );
+
+    // rustdoc-stripper-ignore-next
+    /// Signals that the keys `items`, relative to the directory `path`, changed.
+    #[doc(alias = "g_settings_backend_keys_changed")]
+    fn keys_changed(
+        &self,
+        path: &str,
+        items: &[&str],
+        origin_tag: Option<SettingsBackendOriginTag>,
+    );
+
+    // rustdoc-stripper-ignore-next
+    /// Signals that any key below the directory `path` may have changed.
+    #[doc(alias = "g_settings_backend_path_changed")]
+    fn path_changed(&self, path: &str, origin_tag: Option<SettingsBackendOriginTag>);
+}
+
+impl<O: IsA<SettingsBackend>> SettingsBackendExtManual for O {
+    fn changed(&self, key: &str, origin_tag: Option<SettingsBackendOriginTag>) {
+        unsafe {
+            ffi::g_settings_backend_changed(
+                self.as_ref().to_glib_none().0,
+                key.to_glib_none().0,
+                origin_tag_ptr(origin_tag),
+            );
+        }
+    }
+
+    fn changed_tree(
+        &self,
+        tree: &BTreeMap<String, Option<Variant>>,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) {
+        unsafe {
+            let tree = tree_to_glib(tree);
+            ffi::g_settings_backend_changed_tree(
+                self.as_ref().to_glib_none().0,
+                tree,
+                origin_tag_ptr(origin_tag),
+            );
+            glib::ffi::g_tree_unref(tree);
+        }
+    }
+
+    fn keys_changed(
+        &self,
+        path: &str,
+        items: &[&str],
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) {
+        unsafe {
+            ffi::g_settings_backend_keys_changed(
+                self.as_ref().to_glib_none().0,
+                path.to_glib_none().0,
+                items.to_glib_none().0,
+                origin_tag_ptr(origin_tag),
+            );
+        }
+    }
+
+    fn path_changed(&self, path: &str, origin_tag: Option<SettingsBackendOriginTag>) {
+        unsafe {
+            ffi::g_settings_backend_path_changed(
+                self.as_ref().to_glib_none().0,
+                path.to_glib_none().0,
+                origin_tag_ptr(origin_tag),
+            );
+        }
+    }
+}
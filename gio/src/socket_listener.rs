@@ -0,0 +1,171 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_channel::oneshot;
+use futures_core::stream::{FusedStream, Stream};
+
+use crate::{prelude::*, Cancellable, SocketConnection, SocketListener};
+
+type AcceptResult = Result<(SocketConnection, Option<glib::Object>), glib::Error>;
+
+impl SocketListener {
+    // rustdoc-stripper-ignore-next
+    /// Returns a stream of the connections accepted by the listener, together with the source
+    /// objects passed when adding the corresponding sockets.
+    ///
+    /// Connections are accepted asynchronously one after another. Errors are returned by the
+    /// stream, which keeps accepting connections afterwards. Once `cancellable` is cancelled the
+    /// stream ends, and dropping the stream cancels a pending accept.
+    ///
+    /// A [`SocketService`](crate::SocketService) accepts connections itself and reports them via
+    /// its [`incoming`](crate::SocketService::connect_incoming) signal instead.
+    #[doc(alias = "g_socket_listener_accept_async")]
+    pub fn incoming(&self, cancellable: Option<&impl IsA<Cancellable>>) -> SocketListenerIncoming {
+        let shutdown = Cancellable::new();
+        let handler = cancellable.and_then(|cancellable| {
+            let id = cancellable.as_ref().connect_cancelled({
+                let shutdown = shutdown.clone();
+                move |_| shutdown.cancel()
+            })?;
+            Some((cancellable.as_ref().clone(), id))
+        });
+
+        SocketListenerIncoming {
+            listener: self.clone(),
+            shutdown,
+            handler,
+            receiver: None,
+            terminated: false,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A stream of the connections accepted by a [`SocketListener`].
+///
+/// Created by [`SocketListener::incoming`].
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct SocketListenerIncoming {
+    listener: SocketListener,
+    // Cancels the pending accept, either on shutdown or when the stream is dropped
+    shutdown: Cancellable,
+    handler: Option<(Cancellable, CancelledHandlerId)>,
+    receiver: Option<oneshot::Receiver<AcceptResult>>,
+    terminated: bool,
+}
+
+impl SocketListenerIncoming {
+    // rustdoc-stripper-ignore-next
+    /// Returns the listener the connections are accepted from.
+    pub fn listener(&self) -> &SocketListener {
+        &self.listener
+    }
+}
+
+impl Stream for SocketListenerIncoming {
+    type Item = AcceptResult;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<AcceptResult>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+
+        let this = &mut *self;
+        let receiver = this.receiver.get_or_insert_with(|| {
+            let (sender, receiver) = oneshot::channel();
+            this.listener
+                .accept_async(Some(&this.shutdown), move |res| {
+                    let _ = sender.send(res);
+                });
+            receiver
+        });
+        let res = match Pin::new(receiver).poll(cx) {
+            Poll::Ready(res) => res,
+            Poll::Pending => return Poll::Pending,
+        };
+        self.receiver = None;
+
+        match res {
+            Ok(Ok(res)) => Poll::Ready(Some(Ok(res))),
+            Ok(Err(err)) if !self.shutdown.is_cancelled() => Poll::Ready(Some(Err(err))),
+            _ => {
+                self.terminated = true;
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+impl FusedStream for SocketListenerIncoming {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl Drop for SocketListenerIncoming {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+        if let Some((cancellable, id)) = self.handler.take() {
+            cancellable.disconnect_cancelled(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use futures_util::StreamExt;
+
+    use super::*;
+    use crate::test_util::run_async_local;
+
+    #[test]
+    fn incoming() {
+        let received = run_async_local(|tx, l| {
+            let c = glib::MainContext::ref_thread_default();
+            c.spawn_local(async move {
+                let listener = SocketListener::new();
+                let source = glib::Object::new::<glib::Object>(&[]);
+                let port = listener.add_any_inet_port(Some(&source)).unwrap();
+                let cancellable = Cancellable::new();
+                let mut incoming = listener.incoming(Some(&cancellable));
+
+                let client = std::thread::spawn(move || {
+                    for data in [&b"a"[..], &b"b"[..]] {
+                        let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+                        stream.write_all(data).unwrap();
+                    }
+                });
+
+                let mut received = Vec::new();
+                for _ in 0..2 {
+                    let (connection, source_object) = incoming.next().await.unwrap().unwrap();
+                    assert_eq!(source_object.as_ref(), Some(&source));
+                    let (buf, _, _) = connection
+                        .input_stream()
+                        .read_all_future([0u8; 1], glib::PRIORITY_DEFAULT)
+                        .await
+                        .unwrap();
+                    received.push(buf[0]);
+                }
+                client.join().unwrap();
+
+                cancellable.cancel();
+                assert!(incoming.next().await.is_none());
+                assert!(incoming.is_terminated());
+
+                tx.send(received).unwrap();
+                l.quit();
+            });
+        });
+
+        assert_eq!(received, b"ab");
+    }
+}
@@ -0,0 +1,379 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{mem, ptr};
+
+use glib::{subclass::prelude::*, translate::*, Cast, Error};
+
+use crate::{Converter, ConverterFlags, ConverterResult};
+
+pub trait ConverterImpl: ObjectImpl + ConverterImplExt + Send {
+    // rustdoc-stripper-ignore-next
+    /// Converts data from `inbuf` into `outbuf`.
+    ///
+    /// Returns the result together with the number of bytes consumed from `inbuf` and the number
+    /// of bytes written to `outbuf`, which must not exceed their lengths.
+    ///
+    /// [`ConverterResult::Error`] must not be returned, errors are reported as `Err` instead, e.g.
+    /// [`IOErrorEnum::NoSpace`](crate::IOErrorEnum::NoSpace) if `outbuf` is too small to make
+    /// any progress or [`IOErrorEnum::PartialInput`](crate::IOErrorEnum::PartialInput) if more
+    /// input is needed and [`ConverterFlags::INPUT_AT_END`] is not set. A conversion that breaks
+    /// these rules fails with [`IOErrorEnum::Failed`](crate::IOErrorEnum::Failed).
+    ///
+    /// [`ConverterResult::Finished`] is returned once all input was converted and `flags`
+    /// contains [`ConverterFlags::INPUT_AT_END`], [`ConverterResult::Flushed`] once all input
+    /// was converted and `flags` contains [`ConverterFlags::FLUSH`].
+    fn convert(
+        &self,
+        inbuf: &[u8],
+        outbuf: &mut [u8],
+        flags: ConverterFlags,
+    ) -> Result<(ConverterResult, usize, usize), Error>;
+
+    fn reset(&self);
+}
+
+pub trait ConverterImplExt: ObjectSubclass {
+    fn parent_convert(
+        &self,
+        inbuf: &[u8],
+        outbuf: &mut [u8],
+        flags: ConverterFlags,
+    ) -> Result<(ConverterResult, usize, usize), Error>;
+
+    fn parent_reset(&self);
+}
+
+impl<T: ConverterImpl> ConverterImplExt for T {
+    fn parent_convert(
+        &self,
+        inbuf: &[u8],
+        outbuf: &mut [u8],
+        flags: ConverterFlags,
+    ) -> Result<(ConverterResult, usize, usize), Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface =
+                type_data.as_ref().parent_interface::<Converter>() as *const ffi::GConverterIface;
+
+            let func = (*parent_iface)
+                .convert
+                .expect("no parent \"convert\" implementation");
+            let mut bytes_read = mem::MaybeUninit::uninit();
+            let mut bytes_written = mem::MaybeUninit::uninit();
+            let mut err = ptr::null_mut();
+            let res = func(
+                self.obj().unsafe_cast_ref::<Converter>().to_glib_none().0,
+                mut_override(inbuf.as_ptr()),
+                inbuf.len(),
+                outbuf.as_mut_ptr(),
+                outbuf.len(),
+                flags.into_glib(),
+                bytes_read.as_mut_ptr(),
+                bytes_written.as_mut_ptr(),
+                &mut err,
+            );
+            if err.is_null() {
+                Ok((
+                    from_glib(res),
+                    bytes_read.assume_init(),
+                    bytes_written.assume_init(),
+                ))
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+
+    fn parent_reset(&self) {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface =
+                type_data.as_ref().parent_interface::<Converter>() as *const ffi::GConverterIface;
+
+            let func = (*parent_iface)
+                .reset
+                .expect("no parent \"reset\" implementation");
+            func(self.obj().unsafe_cast_ref::<Converter>().to_glib_none().0)
+        }
+    }
+}
+
+unsafe impl<T: ConverterImpl> IsImplementable<T> for Converter {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.convert = Some(converter_convert::<T>);
+        iface.reset = Some(converter_reset::<T>);
+    }
+}
+
+unsafe extern "C" fn converter_convert<T: ConverterImpl>(
+    converter: *mut ffi::GConverter,
+    inbuf: *mut u8,
+    inbuf_size: usize,
+    outbuf: *mut u8,
+    outbuf_size: usize,
+    flags: ffi::GConverterFlags,
+    bytes_read: *mut usize,
+    bytes_written: *mut usize,
+    err: *mut *mut glib::ffi::GError,
+) -> ffi::GConverterResult {
+    use std::slice;
+
+    let instance = &*(converter as *mut T::Instance);
+    let imp = instance.imp();
+
+    let inbuf = if inbuf_size == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(inbuf as *const u8, inbuf_size)
+    };
+    let outbuf = if outbuf_size == 0 {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(outbuf, outbuf_size)
+    };
+
+    let res = match glib::catch_callback_panic(|| imp.convert(inbuf, outbuf, from_glib(flags))) {
+        Some(Ok((res, read, written))) => {
+            // A broken implementation is reported as a failed conversion, panicking here would
+            // unwind into C
+            let broken = if res == ConverterResult::Error {
+                Some("returned ConverterResult::Error")
+            } else if read > inbuf_size {
+                Some("read more bytes than available")
+            } else if written > outbuf_size {
+                Some("wrote more bytes than available")
+            } else {
+                None
+            };
+            match broken {
+                Some(msg) => {
+                    glib::g_critical!("GIO-Rust", "Converter {} {msg}", T::NAME);
+                    Err(Error::new(
+                        crate::IOErrorEnum::Failed,
+                        &format!("Converter {msg}"),
+                    ))
+                }
+                None => Ok((res, read, written)),
+            }
+        }
+        Some(Err(err)) => Err(err),
+        // The panic was already handled according to the panic policy
+        None => Err(Error::new(crate::IOErrorEnum::Failed, "Converter panicked")),
+    };
+
+    match res {
+        Ok((res, read, written)) => {
+            *bytes_read = read;
+            *bytes_written = written;
+            res.into_glib()
+        }
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            ffi::G_CONVERTER_ERROR
+        }
+    }
+}
+
+unsafe extern "C" fn converter_reset<T: ConverterImpl>(converter: *mut ffi::GConverter) {
+    let instance = &*(converter as *mut T::Instance);
+    let imp = instance.imp();
+
+    glib::catch_callback_panic(|| imp.reset());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::prelude::*;
+
+    mod imp {
+        use super::*;
+
+        // Writes every byte twice and appends a `0` once the input is at its end
+        #[derive(Default)]
+        pub struct Doubler {
+            pub(super) finished: Cell<bool>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for Doubler {
+            const NAME: &'static str = "Doubler";
+            type Type = super::Doubler;
+            type Interfaces = (Converter,);
+        }
+
+        impl ObjectImpl for Doubler {}
+
+        impl ConverterImpl for Doubler {
+            fn convert(
+                &self,
+                inbuf: &[u8],
+                outbuf: &mut [u8],
+                flags: ConverterFlags,
+            ) -> Result<(ConverterResult, usize, usize), Error> {
+                let read = inbuf.len().min(outbuf.len() / 2);
+                for (i, b) in inbuf[..read].iter().enumerate() {
+                    outbuf[2 * i] = *b;
+                    outbuf[2 * i + 1] = *b;
+                }
+                let mut written = 2 * read;
+
+                if read < inbuf.len() {
+                    if read == 0 {
+                        return Err(Error::new(crate::IOErrorEnum::NoSpace, "Need more space"));
+                    }
+                    return Ok((ConverterResult::Converted, read, written));
+                }
+
+                if flags.contains(ConverterFlags::INPUT_AT_END) {
+                    if !self.finished.get() {
+                        if written == outbuf.len() {
+                            if written == 0 {
+                                return Err(Error::new(
+                                    crate::IOErrorEnum::NoSpace,
+                                    "Need more space",
+                                ));
+                            }
+                            return Ok((ConverterResult::Converted, read, written));
+                        }
+                        outbuf[written] = 0;
+                        written += 1;
+                        self.finished.set(true);
+                    }
+                    Ok((ConverterResult::Finished, read, written))
+                } else if flags.contains(ConverterFlags::FLUSH) {
+                    Ok((ConverterResult::Flushed, read, written))
+                } else if read == 0 {
+                    Err(Error::new(
+                        crate::IOErrorEnum::PartialInput,
+                        "Need more input",
+                    ))
+                } else {
+                    Ok((ConverterResult::Converted, read, written))
+                }
+            }
+
+            fn reset(&self) {
+                self.finished.set(false);
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct Doubler(ObjectSubclass<imp::Doubler>) @implements Converter;
+    }
+
+    mod imp_broken {
+        use super::*;
+
+        // Claims to have read more input than there is
+        #[derive(Default)]
+        pub struct Broken;
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for Broken {
+            const NAME: &'static str = "BrokenConverter";
+            type Type = super::Broken;
+            type Interfaces = (Converter,);
+        }
+
+        impl ObjectImpl for Broken {}
+
+        impl ConverterImpl for Broken {
+            fn convert(
+                &self,
+                inbuf: &[u8],
+                _outbuf: &mut [u8],
+                _flags: ConverterFlags,
+            ) -> Result<(ConverterResult, usize, usize), Error> {
+                Ok((ConverterResult::Converted, inbuf.len() + 1, 0))
+            }
+
+            fn reset(&self) {}
+        }
+    }
+
+    glib::wrapper! {
+        pub struct Broken(ObjectSubclass<imp_broken::Broken>) @implements Converter;
+    }
+
+    #[test]
+    fn test_convert() {
+        let converter = glib::Object::new::<Doubler>(&[]);
+
+        let mut outbuf = [0u8; 5];
+        assert_eq!(
+            converter.convert(b"\x01\x02\x03", &mut outbuf[..], ConverterFlags::NONE),
+            Ok((ConverterResult::Converted, 2, 4))
+        );
+        assert_eq!(&outbuf[..4], &[1, 1, 2, 2]);
+
+        assert!(converter
+            .convert(b"\x03", &mut outbuf[..1], ConverterFlags::NONE)
+            .unwrap_err()
+            .matches(crate::IOErrorEnum::NoSpace));
+        assert!(converter
+            .convert(b"", &mut outbuf[..], ConverterFlags::NONE)
+            .unwrap_err()
+            .matches(crate::IOErrorEnum::PartialInput));
+
+        assert_eq!(
+            converter.convert(b"\x03", &mut outbuf[..], ConverterFlags::FLUSH),
+            Ok((ConverterResult::Flushed, 1, 2))
+        );
+        assert_eq!(
+            converter.convert(b"\x04", &mut outbuf[..], ConverterFlags::INPUT_AT_END),
+            Ok((ConverterResult::Finished, 1, 3))
+        );
+        assert_eq!(&outbuf[..3], &[4, 4, 0]);
+        assert_eq!(
+            converter.convert(b"", &mut outbuf[..], ConverterFlags::INPUT_AT_END),
+            Ok((ConverterResult::Finished, 0, 0))
+        );
+
+        converter.reset();
+        assert_eq!(
+            converter.convert(b"", &mut outbuf[..], ConverterFlags::INPUT_AT_END),
+            Ok((ConverterResult::Finished, 0, 1))
+        );
+    }
+
+    #[test]
+    fn test_broken_convert() {
+        let converter = glib::Object::new::<Broken>(&[]);
+
+        let mut outbuf = [0u8; 4];
+        let err = converter
+            .convert(b"\x01", &mut outbuf[..], ConverterFlags::NONE)
+            .unwrap_err();
+        assert!(err.matches(crate::IOErrorEnum::Failed));
+        assert_eq!(err.message(), "Converter read more bytes than available");
+    }
+
+    #[test]
+    fn test_converter_streams() {
+        let converter = glib::Object::new::<Doubler>(&[]);
+
+        let base = crate::MemoryInputStream::from_bytes(&glib::Bytes::from_static(b"abc"));
+        let stream = crate::ConverterInputStream::new(&base, &converter);
+        let mut buf = [0u8; 16];
+        let (len, err) = stream
+            .read_all(&mut buf[..], crate::Cancellable::NONE)
+            .unwrap();
+        assert!(err.is_none());
+        assert_eq!(&buf[..len], b"aabbcc\0");
+
+        converter.reset();
+        let base = crate::MemoryOutputStream::new_resizable();
+        let stream = crate::ConverterOutputStream::new(&base, &converter);
+        stream.write_all(b"xyz", crate::Cancellable::NONE).unwrap();
+        stream.close(crate::Cancellable::NONE).unwrap();
+        assert_eq!(&*base.steal_as_bytes(), b"xxyyzz\0");
+    }
+}
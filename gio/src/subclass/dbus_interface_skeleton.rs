@@ -0,0 +1,496 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ptr;
+
+use glib::{prelude::*, subclass::prelude::*, translate::*, Cast, Error, Variant, VariantDict};
+use libc::c_char;
+use once_cell::sync::Lazy;
+
+use crate::{
+    prelude::*, DBusInterfaceInfo, DBusInterfaceSkeleton, DBusInterfaceSkeletonFlags,
+    DBusMethodInvocation, DBusPropertyInfoFlags, IOErrorEnum,
+};
+
+pub trait DBusInterfaceSkeletonImpl: ObjectImpl + DBusInterfaceSkeletonImplExt {
+    // rustdoc-stripper-ignore-next
+    /// Returns the introspection data of the interface.
+    ///
+    /// This is only called once per instance, the result is kept alive for the lifetime of the
+    /// instance and returned for all later calls.
+    fn info(&self) -> DBusInterfaceInfo;
+
+    // rustdoc-stripper-ignore-next
+    /// Handles a call of the method `method_name`.
+    ///
+    /// The call has to be completed with one of the `return_*()` functions of `invocation`,
+    /// possibly after returning from this function. The default implementation chains up to the
+    /// method call handler of the parent class, or replies with
+    /// `org.freedesktop.DBus.Error.UnknownMethod` if the parent class has none.
+    ///
+    /// This is always called on the thread-default main context of the thread the skeleton was
+    /// exported on. Method calls are rejected if
+    /// [`DBusInterfaceSkeletonFlags::HANDLE_METHOD_INVOCATIONS_IN_THREAD`] is set.
+    fn method_call(
+        &self,
+        sender: Option<&str>,
+        method_name: &str,
+        parameters: Variant,
+        invocation: DBusMethodInvocation,
+    ) {
+        self.parent_method_call(sender, method_name, parameters, invocation)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the D-Bus property `property_name`.
+    ///
+    /// The default implementation chains up to the parent class, or fails if it has no property
+    /// handler.
+    fn dbus_property(&self, sender: Option<&str>, property_name: &str) -> Result<Variant, Error> {
+        self.parent_dbus_property(sender, property_name)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the D-Bus property `property_name` to `value`.
+    ///
+    /// Changes are not announced automatically, use
+    /// [`DBusInterfaceSkeletonExtManual::emit_properties_changed`] for that. The default
+    /// implementation chains up to the parent class, or fails if it has no property handler.
+    ///
+    /// [`DBusInterfaceSkeletonExtManual::emit_properties_changed`]: crate::prelude::DBusInterfaceSkeletonExtManual::emit_properties_changed
+    fn set_dbus_property(
+        &self,
+        sender: Option<&str>,
+        property_name: &str,
+        value: Variant,
+    ) -> Result<(), Error> {
+        self.parent_set_dbus_property(sender, property_name, value)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all readable properties of the interface as `a{sv}` dictionary.
+    ///
+    /// The default implementation chains up to the parent class. If the parent class doesn't
+    /// implement it, the readable properties of [`info`](Self::info) are collected with
+    /// [`dbus_property`](Self::dbus_property), skipping the ones that fail.
+    fn properties(&self) -> Variant {
+        self.parent_properties()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Emits outstanding changes, e.g. property changes that were batched.
+    fn flush(&self) {
+        self.parent_flush()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Class handler of the `g-authorize-method` signal.
+    fn authorize_method(&self, invocation: &DBusMethodInvocation) -> bool {
+        self.parent_authorize_method(invocation)
+    }
+}
+
+pub trait DBusInterfaceSkeletonImplExt: ObjectSubclass {
+    fn parent_method_call(
+        &self,
+        sender: Option<&str>,
+        method_name: &str,
+        parameters: Variant,
+        invocation: DBusMethodInvocation,
+    );
+
+    fn parent_dbus_property(
+        &self,
+        sender: Option<&str>,
+        property_name: &str,
+    ) -> Result<Variant, Error>;
+
+    fn parent_set_dbus_property(
+        &self,
+        sender: Option<&str>,
+        property_name: &str,
+        value: Variant,
+    ) -> Result<(), Error>;
+
+    fn parent_properties(&self) -> Variant;
+
+    fn parent_flush(&self);
+
+    fn parent_authorize_method(&self, invocation: &DBusMethodInvocation) -> bool;
+}
+
+impl<T: DBusInterfaceSkeletonImpl> DBusInterfaceSkeletonImplExt for T {
+    fn parent_method_call(
+        &self,
+        sender: Option<&str>,
+        method_name: &str,
+        parameters: Variant,
+        invocation: DBusMethodInvocation,
+    ) {
+        unsafe {
+            if let Some(f) = parent_vtable::<T>(self).and_then(|vtable| vtable.method_call) {
+                let connection = invocation.connection();
+                let object_path = invocation.object_path();
+                let interface_name = invocation.interface_name();
+                return f(
+                    connection.to_glib_none().0,
+                    sender.to_glib_none().0,
+                    object_path.to_glib_none().0,
+                    interface_name.to_glib_none().0,
+                    method_name.to_glib_none().0,
+                    parameters.to_glib_none().0,
+                    invocation.into_glib_ptr(),
+                    skeleton_ptr(self) as glib::ffi::gpointer,
+                );
+            }
+        }
+
+        invocation.return_dbus_error(
+            "org.freedesktop.DBus.Error.UnknownMethod",
+            &format!("Unknown method {method_name}"),
+        );
+    }
+
+    fn parent_dbus_property(
+        &self,
+        sender: Option<&str>,
+        property_name: &str,
+    ) -> Result<Variant, Error> {
+        unsafe {
+            if let Some(f) = parent_vtable::<T>(self).and_then(|vtable| vtable.get_property) {
+                let (connection, object_path, interface_name) = export_details(self);
+                let mut error = ptr::null_mut();
+                let value = f(
+                    connection.to_glib_none().0,
+                    sender.to_glib_none().0,
+                    object_path.to_glib_none().0,
+                    interface_name.to_glib_none().0,
+                    property_name.to_glib_none().0,
+                    &mut error,
+                    skeleton_ptr(self) as glib::ffi::gpointer,
+                );
+                return if error.is_null() {
+                    assert!(!value.is_null());
+                    Ok(from_glib_full(value))
+                } else {
+                    Err(from_glib_full(error))
+                };
+            }
+        }
+
+        Err(Error::new(
+            IOErrorEnum::NotSupported,
+            &format!("Property {property_name} can't be read"),
+        ))
+    }
+
+    fn parent_set_dbus_property(
+        &self,
+        sender: Option<&str>,
+        property_name: &str,
+        value: Variant,
+    ) -> Result<(), Error> {
+        unsafe {
+            if let Some(f) = parent_vtable::<T>(self).and_then(|vtable| vtable.set_property) {
+                let (connection, object_path, interface_name) = export_details(self);
+                let mut error = ptr::null_mut();
+                let res = f(
+                    connection.to_glib_none().0,
+                    sender.to_glib_none().0,
+                    object_path.to_glib_none().0,
+                    interface_name.to_glib_none().0,
+                    property_name.to_glib_none().0,
+                    value.to_glib_none().0,
+                    &mut error,
+                    skeleton_ptr(self) as glib::ffi::gpointer,
+                );
+                return if error.is_null() {
+                    debug_assert_ne!(res, glib::ffi::GFALSE);
+                    Ok(())
+                } else {
+                    Err(from_glib_full(error))
+                };
+            }
+        }
+
+        Err(Error::new(
+            IOErrorEnum::NotSupported,
+            &format!("Property {property_name} can't be written"),
+        ))
+    }
+
+    fn parent_properties(&self) -> Variant {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().parent_class() as *mut ffi::GDBusInterfaceSkeletonClass;
+            if let Some(f) = (*parent_class).get_properties {
+                return from_glib_full(f(skeleton_ptr(self)));
+            }
+        }
+
+        let info = DBusInterfaceSkeletonExt::info(unsafe {
+            self.obj().unsafe_cast_ref::<DBusInterfaceSkeleton>()
+        });
+        let dict = VariantDict::new(None);
+        for property in info.properties() {
+            if !property.flags().contains(DBusPropertyInfoFlags::READABLE) {
+                continue;
+            }
+            if let Ok(value) = self.dbus_property(None, property.name()) {
+                dict.insert_value(property.name(), &value);
+            }
+        }
+        dict.end()
+    }
+
+    fn parent_flush(&self) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().parent_class() as *mut ffi::GDBusInterfaceSkeletonClass;
+            if let Some(f) = (*parent_class).flush {
+                f(self
+                    .obj()
+                    .unsafe_cast_ref::<DBusInterfaceSkeleton>()
+                    .to_glib_none()
+                    .0)
+            }
+        }
+    }
+
+    fn parent_authorize_method(&self, invocation: &DBusMethodInvocation) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().parent_class() as *mut ffi::GDBusInterfaceSkeletonClass;
+            if let Some(f) = (*parent_class).g_authorize_method {
+                from_glib(f(
+                    self.obj()
+                        .unsafe_cast_ref::<DBusInterfaceSkeleton>()
+                        .to_glib_none()
+                        .0,
+                    invocation.to_glib_none().0,
+                ))
+            } else {
+                true
+            }
+        }
+    }
+}
+
+fn skeleton_ptr<T: DBusInterfaceSkeletonImpl>(imp: &T) -> *mut ffi::GDBusInterfaceSkeleton {
+    unsafe {
+        imp.obj()
+            .unsafe_cast_ref::<DBusInterfaceSkeleton>()
+            .to_glib_none()
+            .0
+    }
+}
+
+// The vtable of the parent class, which the skeleton would have been exported with. The abstract
+// `GDBusInterfaceSkeleton` itself has none.
+unsafe fn parent_vtable<T: DBusInterfaceSkeletonImpl>(
+    imp: &T,
+) -> Option<&'static ffi::GDBusInterfaceVTable> {
+    let data = T::type_data();
+    let parent_class = data.as_ref().parent_class() as *mut ffi::GDBusInterfaceSkeletonClass;
+    let vtable = (*parent_class).get_vtable?(skeleton_ptr(imp));
+    if vtable.is_null() {
+        None
+    } else {
+        Some(&*vtable)
+    }
+}
+
+// Property accesses don't come with an invocation, so the parent's vtable gets the connection and
+// object path the skeleton was exported with first.
+fn export_details<T: DBusInterfaceSkeletonImpl>(
+    imp: &T,
+) -> (
+    Option<crate::DBusConnection>,
+    Option<glib::GString>,
+    glib::GString,
+) {
+    let skeleton = unsafe { imp.obj().unsafe_cast_ref::<DBusInterfaceSkeleton>().clone() };
+    let info = DBusInterfaceSkeletonExt::info(&skeleton);
+    (
+        skeleton.connection(),
+        skeleton.object_path(),
+        info.name().into(),
+    )
+}
+
+unsafe impl<T: DBusInterfaceSkeletonImpl> IsSubclassable<T> for DBusInterfaceSkeleton {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();
+        klass.get_info = Some(skeleton_get_info::<T>);
+        klass.get_vtable = Some(skeleton_get_vtable::<T>);
+        klass.get_properties = Some(skeleton_get_properties::<T>);
+        klass.flush = Some(skeleton_flush::<T>);
+        klass.g_authorize_method = Some(skeleton_authorize_method::<T>);
+    }
+}
+
+static INTERFACE_INFO_QUARK: Lazy<glib::Quark> =
+    Lazy::new(|| glib::Quark::from_str("gtk-rs-subclass-dbus-interface-info"));
+
+unsafe extern "C" fn skeleton_get_info<T: DBusInterfaceSkeletonImpl>(
+    ptr: *mut ffi::GDBusInterfaceSkeleton,
+) -> *mut ffi::GDBusInterfaceInfo {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+    let instance = imp.obj();
+
+    // The returned info is not owned by the caller and is used for as long as the skeleton is
+    // exported, so it is kept alive by the instance
+    if let Some(info) = instance.qdata::<DBusInterfaceInfo>(*INTERFACE_INFO_QUARK) {
+        return info.as_ref().to_glib_none().0;
+    }
+
+    let info = imp.info();
+    let ptr = info.to_glib_none().0;
+    instance.set_qdata(*INTERFACE_INFO_QUARK, info);
+    ptr
+}
+
+struct VTable<T>(std::marker::PhantomData<T>);
+
+impl<T: DBusInterfaceSkeletonImpl> VTable<T> {
+    const VTABLE: ffi::GDBusInterfaceVTable = ffi::GDBusInterfaceVTable {
+        method_call: Some(skeleton_method_call::<T>),
+        get_property: Some(skeleton_get_property::<T>),
+        set_property: Some(skeleton_set_property::<T>),
+        padding: [ptr::null_mut(); 8],
+    };
+}
+
+unsafe extern "C" fn skeleton_get_vtable<T: DBusInterfaceSkeletonImpl>(
+    _ptr: *mut ffi::GDBusInterfaceSkeleton,
+) -> *mut ffi::GDBusInterfaceVTable {
+    // GDBus never modifies the vtable
+    &VTable::<T>::VTABLE as *const _ as *mut _
+}
+
+// The user data of all vtable functions is the skeleton
+unsafe extern "C" fn skeleton_method_call<T: DBusInterfaceSkeletonImpl>(
+    _connection: *mut ffi::GDBusConnection,
+    sender: *const c_char,
+    _object_path: *const c_char,
+    _interface_name: *const c_char,
+    method_name: *const c_char,
+    parameters: *mut glib::ffi::GVariant,
+    invocation: *mut ffi::GDBusMethodInvocation,
+    user_data: glib::ffi::gpointer,
+) {
+    let invocation: DBusMethodInvocation = from_glib_full(invocation);
+
+    let skeleton = user_data as *mut ffi::GDBusInterfaceSkeleton;
+    let flags: DBusInterfaceSkeletonFlags =
+        from_glib(ffi::g_dbus_interface_skeleton_get_flags(skeleton));
+    if flags.contains(DBusInterfaceSkeletonFlags::HANDLE_METHOD_INVOCATIONS_IN_THREAD) {
+        glib::g_critical!(
+            "GIO-Rust",
+            "Method calls of Rust D-Bus interface skeletons can't be handled in a thread"
+        );
+        invocation.return_dbus_error(
+            "org.freedesktop.DBus.Error.Failed",
+            "Method calls can't be handled in a thread",
+        );
+        return;
+    }
+
+    let instance = &*(skeleton as *mut T::Instance);
+    let imp = instance.imp();
+    let sender: Borrowed<Option<glib::GString>> = from_glib_borrow(sender);
+    imp.method_call(
+        sender.as_ref().as_deref(),
+        &glib::GString::from_glib_borrow(method_name),
+        from_glib_none(parameters),
+        invocation,
+    )
+}
+
+unsafe extern "C" fn skeleton_get_property<T: DBusInterfaceSkeletonImpl>(
+    _connection: *mut ffi::GDBusConnection,
+    sender: *const c_char,
+    _object_path: *const c_char,
+    _interface_name: *const c_char,
+    property_name: *const c_char,
+    error: *mut *mut glib::ffi::GError,
+    user_data: glib::ffi::gpointer,
+) -> *mut glib::ffi::GVariant {
+    let instance = &*(user_data as *mut T::Instance);
+    let imp = instance.imp();
+    let sender: Borrowed<Option<glib::GString>> = from_glib_borrow(sender);
+    match imp.dbus_property(
+        sender.as_ref().as_deref(),
+        &glib::GString::from_glib_borrow(property_name),
+    ) {
+        Ok(value) => value.to_glib_full(),
+        Err(e) => {
+            if !error.is_null() {
+                *error = e.into_glib_ptr();
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn skeleton_set_property<T: DBusInterfaceSkeletonImpl>(
+    _connection: *mut ffi::GDBusConnection,
+    sender: *const c_char,
+    _object_path: *const c_char,
+    _interface_name: *const c_char,
+    property_name: *const c_char,
+    value: *mut glib::ffi::GVariant,
+    error: *mut *mut glib::ffi::GError,
+    user_data: glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    let instance = &*(user_data as *mut T::Instance);
+    let imp = instance.imp();
+    let sender: Borrowed<Option<glib::GString>> = from_glib_borrow(sender);
+    match imp.set_dbus_property(
+        sender.as_ref().as_deref(),
+        &glib::GString::from_glib_borrow(property_name),
+        from_glib_none(value),
+    ) {
+        Ok(()) => glib::ffi::GTRUE,
+        Err(e) => {
+            if !error.is_null() {
+                *error = e.into_glib_ptr();
+            }
+            glib::ffi::GFALSE
+        }
+    }
+}
+
+unsafe extern "C" fn skeleton_get_properties<T: DBusInterfaceSkeletonImpl>(
+    ptr: *mut ffi::GDBusInterfaceSkeleton,
+) -> *mut glib::ffi::GVariant {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.properties().to_glib_full()
+}
+
+unsafe extern "C" fn skeleton_flush<T: DBusInterfaceSkeletonImpl>(
+    ptr: *mut ffi::GDBusInterfaceSkeleton,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.flush()
+}
+
+unsafe extern "C" fn skeleton_authorize_method<T: DBusInterfaceSkeletonImpl>(
+    ptr: *mut ffi::GDBusInterfaceSkeleton,
+    invocation: *mut ffi::GDBusMethodInvocation,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.authorize_method(&from_glib_borrow(invocation))
+        .into_glib()
+}
@@ -0,0 +1,887 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    path::{Path, PathBuf},
+    ptr,
+};
+
+use glib::{subclass::prelude::*, translate::*, Cast, Error, GString};
+use libc::{c_char, c_uint};
+
+use crate::{
+    Cancellable, File, FileEnumerator, FileInfo, FileInputStream, FileQueryInfoFlags, IOErrorEnum,
+};
+
+// rustdoc-stripper-ignore-next
+/// Implementation of the [`File`] interface.
+///
+/// The methods without default implementation are required by GIO. The I/O operations default
+/// to failing with [`IOErrorEnum::NotSupported`], and the asynchronous variants of all of them
+/// run the synchronous implementation in a thread.
+pub trait FileImpl: ObjectImpl + FileImplExt + Send + Sync {
+    // rustdoc-stripper-ignore-next
+    /// Returns a new file for the same location.
+    fn dup(&self) -> File;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a hash of the location, equal files must have the same hash.
+    fn hash(&self) -> u32;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns whether `file2` refers to the same location, it is always of the same type.
+    fn equal(&self, file2: &File) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns whether the file is available on the local filesystem.
+    fn is_native(&self) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns whether the URI scheme of the file is `uri_scheme`, compared case-insensitively.
+    fn has_uri_scheme(&self, uri_scheme: &str) -> bool;
+
+    #[doc(alias = "get_uri_scheme")]
+    fn uri_scheme(&self) -> Option<GString>;
+
+    #[doc(alias = "get_basename")]
+    fn basename(&self) -> Option<PathBuf>;
+
+    #[doc(alias = "get_path")]
+    fn path(&self) -> Option<PathBuf>;
+
+    #[doc(alias = "get_uri")]
+    fn uri(&self) -> GString;
+
+    #[doc(alias = "get_parse_name")]
+    fn parse_name(&self) -> GString;
+
+    #[doc(alias = "get_parent")]
+    fn parent(&self) -> Option<File>;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns whether the file is an ancestor of `file`, which is always of the same type.
+    fn prefix_matches(&self, file: &File) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the path of `descendant` relative to the file, or `None` if it is no descendant.
+    #[doc(alias = "get_relative_path")]
+    fn relative_path(&self, descendant: &File) -> Option<PathBuf>;
+
+    fn resolve_relative_path(&self, relative_path: &Path) -> File;
+
+    #[doc(alias = "get_child_for_display_name")]
+    fn child_for_display_name(&self, display_name: &str) -> Result<File, Error>;
+
+    fn enumerate_children(
+        &self,
+        attributes: &str,
+        flags: FileQueryInfoFlags,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileEnumerator, Error> {
+        self.parent_enumerate_children(attributes, flags, cancellable)
+    }
+
+    fn query_info(
+        &self,
+        attributes: &str,
+        flags: FileQueryInfoFlags,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error> {
+        self.parent_query_info(attributes, flags, cancellable)
+    }
+
+    fn query_filesystem_info(
+        &self,
+        attributes: &str,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error> {
+        self.parent_query_filesystem_info(attributes, cancellable)
+    }
+
+    #[doc(alias = "read_fn")]
+    fn read(&self, cancellable: Option<&Cancellable>) -> Result<FileInputStream, Error> {
+        self.parent_read(cancellable)
+    }
+}
+
+pub trait FileImplExt: ObjectSubclass {
+    fn parent_enumerate_children(
+        &self,
+        attributes: &str,
+        flags: FileQueryInfoFlags,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileEnumerator, Error>;
+
+    fn parent_query_info(
+        &self,
+        attributes: &str,
+        flags: FileQueryInfoFlags,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error>;
+
+    fn parent_query_filesystem_info(
+        &self,
+        attributes: &str,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error>;
+
+    fn parent_read(&self, cancellable: Option<&Cancellable>) -> Result<FileInputStream, Error>;
+}
+
+fn not_supported() -> Error {
+    Error::new(IOErrorEnum::NotSupported, "Operation not supported")
+}
+
+impl<T: FileImpl> FileImplExt for T {
+    fn parent_enumerate_children(
+        &self,
+        attributes: &str,
+        flags: FileQueryInfoFlags,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileEnumerator, Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface =
+                type_data.as_ref().parent_interface::<File>() as *const ffi::GFileIface;
+
+            let func = (*parent_iface)
+                .enumerate_children
+                .ok_or_else(not_supported)?;
+            let mut err = ptr::null_mut();
+            let ret = func(
+                self.obj().unsafe_cast_ref::<File>().to_glib_none().0,
+                attributes.to_glib_none().0,
+                flags.into_glib(),
+                cancellable.to_glib_none().0,
+                &mut err,
+            );
+            if err.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+
+    fn parent_query_info(
+        &self,
+        attributes: &str,
+        flags: FileQueryInfoFlags,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface =
+                type_data.as_ref().parent_interface::<File>() as *const ffi::GFileIface;
+
+            let func = (*parent_iface).query_info.ok_or_else(not_supported)?;
+            let mut err = ptr::null_mut();
+            let ret = func(
+                self.obj().unsafe_cast_ref::<File>().to_glib_none().0,
+                attributes.to_glib_none().0,
+                flags.into_glib(),
+                cancellable.to_glib_none().0,
+                &mut err,
+            );
+            if err.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+
+    fn parent_query_filesystem_info(
+        &self,
+        attributes: &str,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface =
+                type_data.as_ref().parent_interface::<File>() as *const ffi::GFileIface;
+
+            let func = (*parent_iface)
+                .query_filesystem_info
+                .ok_or_else(not_supported)?;
+            let mut err = ptr::null_mut();
+            let ret = func(
+                self.obj().unsafe_cast_ref::<File>().to_glib_none().0,
+                attributes.to_glib_none().0,
+                cancellable.to_glib_none().0,
+                &mut err,
+            );
+            if err.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+
+    fn parent_read(&self, cancellable: Option<&Cancellable>) -> Result<FileInputStream, Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface =
+                type_data.as_ref().parent_interface::<File>() as *const ffi::GFileIface;
+
+            let func = (*parent_iface).read_fn.ok_or_else(not_supported)?;
+            let mut err = ptr::null_mut();
+            let ret = func(
+                self.obj().unsafe_cast_ref::<File>().to_glib_none().0,
+                cancellable.to_glib_none().0,
+                &mut err,
+            );
+            if err.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+}
+
+unsafe impl<T: FileImpl> IsImplementable<T> for File {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        // The asynchronous functions keep their default implementations, which call the
+        // synchronous ones from a thread.
+        iface.dup = Some(file_dup::<T>);
+        iface.hash = Some(file_hash::<T>);
+        iface.equal = Some(file_equal::<T>);
+        iface.is_native = Some(file_is_native::<T>);
+        iface.has_uri_scheme = Some(file_has_uri_scheme::<T>);
+        iface.get_uri_scheme = Some(file_get_uri_scheme::<T>);
+        iface.get_basename = Some(file_get_basename::<T>);
+        iface.get_path = Some(file_get_path::<T>);
+        iface.get_uri = Some(file_get_uri::<T>);
+        iface.get_parse_name = Some(file_get_parse_name::<T>);
+        iface.get_parent = Some(file_get_parent::<T>);
+        iface.prefix_matches = Some(file_prefix_matches::<T>);
+        iface.get_relative_path = Some(file_get_relative_path::<T>);
+        iface.resolve_relative_path = Some(file_resolve_relative_path::<T>);
+        iface.get_child_for_display_name = Some(file_get_child_for_display_name::<T>);
+        iface.enumerate_children = Some(file_enumerate_children::<T>);
+        iface.query_info = Some(file_query_info::<T>);
+        iface.query_filesystem_info = Some(file_query_filesystem_info::<T>);
+        iface.read_fn = Some(file_read::<T>);
+    }
+}
+
+unsafe extern "C" fn file_dup<T: FileImpl>(file: *mut ffi::GFile) -> *mut ffi::GFile {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.dup().into_glib_ptr()
+}
+
+unsafe extern "C" fn file_hash<T: FileImpl>(file: *mut ffi::GFile) -> c_uint {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    FileImpl::hash(imp)
+}
+
+unsafe extern "C" fn file_equal<T: FileImpl>(
+    file1: *mut ffi::GFile,
+    file2: *mut ffi::GFile,
+) -> glib::ffi::gboolean {
+    let instance = &*(file1 as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.equal(&from_glib_borrow(file2)).into_glib()
+}
+
+unsafe extern "C" fn file_is_native<T: FileImpl>(file: *mut ffi::GFile) -> glib::ffi::gboolean {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.is_native().into_glib()
+}
+
+unsafe extern "C" fn file_has_uri_scheme<T: FileImpl>(
+    file: *mut ffi::GFile,
+    uri_scheme: *const c_char,
+) -> glib::ffi::gboolean {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+    let uri_scheme: Borrowed<GString> = from_glib_borrow(uri_scheme);
+
+    imp.has_uri_scheme(uri_scheme.as_str()).into_glib()
+}
+
+unsafe extern "C" fn file_get_uri_scheme<T: FileImpl>(file: *mut ffi::GFile) -> *mut c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.uri_scheme().to_glib_full()
+}
+
+unsafe extern "C" fn file_get_basename<T: FileImpl>(file: *mut ffi::GFile) -> *mut c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.basename().to_glib_full()
+}
+
+unsafe extern "C" fn file_get_path<T: FileImpl>(file: *mut ffi::GFile) -> *mut c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.path().to_glib_full()
+}
+
+unsafe extern "C" fn file_get_uri<T: FileImpl>(file: *mut ffi::GFile) -> *mut c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.uri().to_glib_full()
+}
+
+unsafe extern "C" fn file_get_parse_name<T: FileImpl>(file: *mut ffi::GFile) -> *mut c_char {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.parse_name().to_glib_full()
+}
+
+unsafe extern "C" fn file_get_parent<T: FileImpl>(file: *mut ffi::GFile) -> *mut ffi::GFile {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    FileImpl::parent(imp).into_glib_ptr()
+}
+
+unsafe extern "C" fn file_prefix_matches<T: FileImpl>(
+    prefix: *mut ffi::GFile,
+    file: *mut ffi::GFile,
+) -> glib::ffi::gboolean {
+    let instance = &*(prefix as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.prefix_matches(&from_glib_borrow(file)).into_glib()
+}
+
+unsafe extern "C" fn file_get_relative_path<T: FileImpl>(
+    parent: *mut ffi::GFile,
+    descendant: *mut ffi::GFile,
+) -> *mut c_char {
+    let instance = &*(parent as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.relative_path(&from_glib_borrow(descendant))
+        .to_glib_full()
+}
+
+unsafe extern "C" fn file_resolve_relative_path<T: FileImpl>(
+    file: *mut ffi::GFile,
+    relative_path: *const c_char,
+) -> *mut ffi::GFile {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+    let relative_path: PathBuf = from_glib_none(relative_path);
+
+    imp.resolve_relative_path(&relative_path).into_glib_ptr()
+}
+
+unsafe extern "C" fn file_get_child_for_display_name<T: FileImpl>(
+    file: *mut ffi::GFile,
+    display_name: *const c_char,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFile {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+    let display_name: Borrowed<GString> = from_glib_borrow(display_name);
+
+    match imp.child_for_display_name(display_name.as_str()) {
+        Ok(child) => child.into_glib_ptr(),
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn file_enumerate_children<T: FileImpl>(
+    file: *mut ffi::GFile,
+    attributes: *const c_char,
+    flags: ffi::GFileQueryInfoFlags,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileEnumerator {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+    let attributes: Borrowed<GString> = from_glib_borrow(attributes);
+
+    match imp.enumerate_children(
+        attributes.as_str(),
+        from_glib(flags),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(enumerator) => enumerator.into_glib_ptr(),
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn file_query_info<T: FileImpl>(
+    file: *mut ffi::GFile,
+    attributes: *const c_char,
+    flags: ffi::GFileQueryInfoFlags,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileInfo {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+    let attributes: Borrowed<GString> = from_glib_borrow(attributes);
+
+    match imp.query_info(
+        attributes.as_str(),
+        from_glib(flags),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(info) => info.into_glib_ptr(),
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn file_query_filesystem_info<T: FileImpl>(
+    file: *mut ffi::GFile,
+    attributes: *const c_char,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileInfo {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+    let attributes: Borrowed<GString> = from_glib_borrow(attributes);
+
+    match imp.query_filesystem_info(
+        attributes.as_str(),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(info) => info.into_glib_ptr(),
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn file_read<T: FileImpl>(
+    file: *mut ffi::GFile,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileInputStream {
+    let instance = &*(file as *mut T::Instance);
+    let imp = instance.imp();
+
+    match imp.read(
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(stream) => stream.into_glib_ptr(),
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+        sync::Mutex,
+    };
+
+    use once_cell::sync::OnceCell;
+
+    use super::*;
+    use crate::{prelude::*, subclass::prelude::*, FileType, InputStream, Vfs};
+
+    const SCHEME: &str = "rust-mem";
+    const FILES: &[(&str, &[u8])] = &[("/hello.txt", b"Hello World"), ("/empty.txt", b"")];
+
+    fn not_found(path: &str) -> Error {
+        Error::new(IOErrorEnum::NotFound, &format!("{path} does not exist"))
+    }
+
+    mod imp {
+        use super::*;
+
+        #[derive(Default)]
+        pub struct MemFile {
+            pub path: OnceCell<String>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for MemFile {
+            const NAME: &'static str = "MemFile";
+            type Type = super::MemFile;
+            type Interfaces = (File,);
+        }
+
+        impl ObjectImpl for MemFile {}
+
+        impl MemFile {
+            fn path(&self) -> &str {
+                self.path.get().unwrap()
+            }
+
+            fn data(&self) -> Option<&'static [u8]> {
+                FILES
+                    .iter()
+                    .find(|(path, _)| *path == self.path())
+                    .map(|(_, data)| *data)
+            }
+        }
+
+        impl FileImpl for MemFile {
+            fn dup(&self) -> File {
+                super::MemFile::new(self.path()).upcast()
+            }
+
+            fn hash(&self) -> u32 {
+                let mut hasher = DefaultHasher::new();
+                self.path().hash(&mut hasher);
+                hasher.finish() as u32
+            }
+
+            fn equal(&self, file2: &File) -> bool {
+                file2.downcast_ref::<super::MemFile>().unwrap().imp().path() == self.path()
+            }
+
+            fn is_native(&self) -> bool {
+                false
+            }
+
+            fn has_uri_scheme(&self, uri_scheme: &str) -> bool {
+                uri_scheme.eq_ignore_ascii_case(SCHEME)
+            }
+
+            fn uri_scheme(&self) -> Option<GString> {
+                Some(SCHEME.into())
+            }
+
+            fn basename(&self) -> Option<PathBuf> {
+                match self.path().rsplit_once('/') {
+                    Some((_, "")) | None => Some(PathBuf::from("/")),
+                    Some((_, basename)) => Some(PathBuf::from(basename)),
+                }
+            }
+
+            fn path(&self) -> Option<PathBuf> {
+                None
+            }
+
+            fn uri(&self) -> GString {
+                format!("{SCHEME}://{}", self.path()).into()
+            }
+
+            fn parse_name(&self) -> GString {
+                FileImpl::uri(self)
+            }
+
+            fn parent(&self) -> Option<File> {
+                match self.path().rsplit_once('/') {
+                    Some((_, "")) | None => None,
+                    Some(("", _)) => Some(super::MemFile::new("/").upcast()),
+                    Some((parent, _)) => Some(super::MemFile::new(parent).upcast()),
+                }
+            }
+
+            fn prefix_matches(&self, file: &File) -> bool {
+                self.relative_path(file).is_some()
+            }
+
+            fn relative_path(&self, descendant: &File) -> Option<PathBuf> {
+                let descendant = descendant.downcast_ref::<super::MemFile>().unwrap().imp();
+                descendant
+                    .path()
+                    .strip_prefix(self.path().trim_end_matches('/'))
+                    .and_then(|path| path.strip_prefix('/'))
+                    .filter(|path| !path.is_empty())
+                    .map(PathBuf::from)
+            }
+
+            fn resolve_relative_path(&self, relative_path: &Path) -> File {
+                let path = Path::new(self.path()).join(relative_path);
+                super::MemFile::new(path.to_str().unwrap()).upcast()
+            }
+
+            fn child_for_display_name(&self, display_name: &str) -> Result<File, Error> {
+                Ok(self.resolve_relative_path(Path::new(display_name)))
+            }
+
+            fn enumerate_children(
+                &self,
+                _attributes: &str,
+                _flags: FileQueryInfoFlags,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<FileEnumerator, Error> {
+                if self.path() == "/" {
+                    Ok(
+                        glib::Object::new::<super::MemEnumerator>(&[("container", &*self.obj())])
+                            .upcast(),
+                    )
+                } else {
+                    Err(Error::new(IOErrorEnum::NotDirectory, "Not a directory"))
+                }
+            }
+
+            fn query_info(
+                &self,
+                _attributes: &str,
+                _flags: FileQueryInfoFlags,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<FileInfo, Error> {
+                let info = FileInfo::new();
+                info.set_name(self.basename().unwrap());
+                if self.path() == "/" {
+                    info.set_file_type(FileType::Directory);
+                } else {
+                    let data = self.data().ok_or_else(|| not_found(self.path()))?;
+                    info.set_file_type(FileType::Regular);
+                    info.set_size(data.len() as i64);
+                }
+                Ok(info)
+            }
+
+            fn read(&self, _cancellable: Option<&Cancellable>) -> Result<FileInputStream, Error> {
+                let data = self.data().ok_or_else(|| not_found(self.path()))?;
+                let stream = glib::Object::new::<super::MemInputStream>(&[]);
+                stream.imp().data.set(data).unwrap();
+                Ok(stream.upcast())
+            }
+        }
+
+        #[derive(Default)]
+        pub struct MemEnumerator {
+            pub index: Mutex<usize>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for MemEnumerator {
+            const NAME: &'static str = "MemEnumerator";
+            type Type = super::MemEnumerator;
+            type ParentType = FileEnumerator;
+        }
+
+        impl ObjectImpl for MemEnumerator {}
+
+        impl FileEnumeratorImpl for MemEnumerator {
+            fn next_file(
+                &self,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<Option<FileInfo>, Error> {
+                let mut index = self.index.lock().unwrap();
+                let (path, _) = match FILES.get(*index) {
+                    Some(file) => file,
+                    None => return Ok(None),
+                };
+                *index += 1;
+                self.obj()
+                    .container()
+                    .child(&path[1..])
+                    .query_info("*", FileQueryInfoFlags::NONE, Cancellable::NONE)
+                    .map(Some)
+            }
+        }
+
+        #[derive(Default)]
+        pub struct MemInputStream {
+            pub data: OnceCell<&'static [u8]>,
+            pub pos: Mutex<usize>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for MemInputStream {
+            const NAME: &'static str = "MemInputStream";
+            type Type = super::MemInputStream;
+            type ParentType = FileInputStream;
+        }
+
+        impl ObjectImpl for MemInputStream {}
+
+        impl InputStreamImpl for MemInputStream {
+            fn read(
+                &self,
+                buffer: &mut [u8],
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<usize, Error> {
+                let data = self.data.get().unwrap();
+                let mut pos = self.pos.lock().unwrap();
+                let len = buffer.len().min(data.len() - *pos);
+                buffer[..len].copy_from_slice(&data[*pos..*pos + len]);
+                *pos += len;
+                Ok(len)
+            }
+        }
+
+        impl FileInputStreamImpl for MemInputStream {
+            fn tell(&self) -> i64 {
+                *self.pos.lock().unwrap() as i64
+            }
+
+            fn can_seek(&self) -> bool {
+                true
+            }
+
+            fn seek(
+                &self,
+                offset: i64,
+                type_: glib::SeekType,
+                _cancellable: Option<&Cancellable>,
+            ) -> Result<(), Error> {
+                let len = self.data.get().unwrap().len() as i64;
+                let mut pos = self.pos.lock().unwrap();
+                let new_pos = match type_ {
+                    glib::SeekType::Cur => *pos as i64 + offset,
+                    glib::SeekType::End => len + offset,
+                    _ => offset,
+                };
+                if !(0..=len).contains(&new_pos) {
+                    return Err(Error::new(IOErrorEnum::InvalidArgument, "Invalid offset"));
+                }
+                *pos = new_pos as usize;
+                Ok(())
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct MemFile(ObjectSubclass<imp::MemFile>) @implements File;
+    }
+
+    impl MemFile {
+        fn new(path: &str) -> Self {
+            let file = glib::Object::new::<Self>(&[]);
+            file.imp().path.set(path.to_owned()).unwrap();
+            file
+        }
+    }
+
+    glib::wrapper! {
+        pub struct MemEnumerator(ObjectSubclass<imp::MemEnumerator>) @extends FileEnumerator;
+    }
+
+    glib::wrapper! {
+        pub struct MemInputStream(ObjectSubclass<imp::MemInputStream>)
+            @extends FileInputStream, InputStream, @implements crate::Seekable;
+    }
+
+    fn register_scheme() {
+        static REGISTERED: once_cell::sync::Lazy<bool> = once_cell::sync::Lazy::new(|| {
+            let lookup = |_: &Vfs, uri: &str| {
+                let path = uri.strip_prefix(SCHEME)?.strip_prefix("://")?;
+                Some(MemFile::new(path).upcast())
+            };
+            Vfs::default().register_uri_scheme(
+                SCHEME,
+                Some(Box::new(lookup)),
+                Some(Box::new(lookup)),
+            )
+        });
+        assert!(*REGISTERED);
+    }
+
+    #[test]
+    fn test_custom_scheme() {
+        register_scheme();
+        assert!(!Vfs::default().register_uri_scheme(SCHEME, None, None));
+
+        let file = File::for_uri("rust-mem:///hello.txt");
+        assert!(file.is::<MemFile>());
+        assert_eq!(file.uri(), "rust-mem:///hello.txt");
+        assert_eq!(file.basename().unwrap(), Path::new("hello.txt"));
+        assert!(file.has_uri_scheme("RUST-MEM"));
+        assert!(file.equal(&File::for_parse_name("rust-mem:///hello.txt")));
+
+        let root = file.parent().unwrap();
+        assert_eq!(root.uri(), "rust-mem:///");
+        assert!(file.has_prefix(&root));
+        assert_eq!(root.relative_path(&file).unwrap(), Path::new("hello.txt"));
+        assert!(root.child("hello.txt").equal(&file));
+
+        let (data, _) = file.load_contents(Cancellable::NONE).unwrap();
+        assert_eq!(data, b"Hello World");
+
+        let stream = file.read(Cancellable::NONE).unwrap();
+        stream
+            .seek(6, glib::SeekType::Set, Cancellable::NONE)
+            .unwrap();
+        let mut buf = [0; 16];
+        assert_eq!(stream.read(&mut buf, Cancellable::NONE), Ok(5));
+        assert_eq!(&buf[..5], b"World");
+        assert_eq!(stream.tell(), 11);
+
+        let names = root
+            .enumerate_children("*", FileQueryInfoFlags::NONE, Cancellable::NONE)
+            .unwrap()
+            .map(|info| info.unwrap().name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, [Path::new("hello.txt"), Path::new("empty.txt")]);
+
+        let err = root
+            .child("missing.txt")
+            .read(Cancellable::NONE)
+            .unwrap_err();
+        assert!(err.matches(IOErrorEnum::NotFound));
+        let err = file
+            .query_filesystem_info("*", Cancellable::NONE)
+            .unwrap_err();
+        assert!(err.matches(IOErrorEnum::NotSupported));
+    }
+
+    #[test]
+    fn test_custom_scheme_async() {
+        register_scheme();
+
+        let ret = crate::test_util::run_async(|tx, l| {
+            let file = File::for_uri("rust-mem:///hello.txt");
+            file.load_contents_async(Cancellable::NONE, move |ret| {
+                tx.send(ret.map(|(data, _)| data)).unwrap();
+                l.quit();
+            });
+        });
+        assert_eq!(ret.unwrap(), b"Hello World");
+
+        let ret = crate::test_util::run_async(|tx, l| {
+            let file = File::for_uri("rust-mem:///empty.txt");
+            file.query_info_async(
+                "*",
+                FileQueryInfoFlags::NONE,
+                glib::PRIORITY_DEFAULT,
+                Cancellable::NONE,
+                move |ret| {
+                    tx.send(ret.map(|info| info.size())).unwrap();
+                    l.quit();
+                },
+            );
+        });
+        assert_eq!(ret.unwrap(), 0);
+    }
+}
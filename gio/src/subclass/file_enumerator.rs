@@ -0,0 +1,137 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ptr;
+
+use glib::{subclass::prelude::*, translate::*, Cast, Error};
+
+use crate::{Cancellable, FileEnumerator, FileInfo};
+
+pub trait FileEnumeratorImpl: ObjectImpl + FileEnumeratorImplExt + Send {
+    // rustdoc-stripper-ignore-next
+    /// Returns the information of the next file, or `None` once all files were returned.
+    fn next_file(&self, cancellable: Option<&Cancellable>) -> Result<Option<FileInfo>, Error> {
+        self.parent_next_file(cancellable)
+    }
+
+    fn close(&self, cancellable: Option<&Cancellable>) -> Result<(), Error> {
+        self.parent_close(cancellable)
+    }
+}
+
+pub trait FileEnumeratorImplExt: ObjectSubclass {
+    fn parent_next_file(
+        &self,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<Option<FileInfo>, Error>;
+
+    fn parent_close(&self, cancellable: Option<&Cancellable>) -> Result<(), Error>;
+}
+
+impl<T: FileEnumeratorImpl> FileEnumeratorImplExt for T {
+    fn parent_next_file(
+        &self,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<Option<FileInfo>, Error> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GFileEnumeratorClass;
+            let f = (*parent_class)
+                .next_file
+                .expect("No parent class implementation for \"next_file\"");
+            let mut err = ptr::null_mut();
+            let res = f(
+                self.obj()
+                    .unsafe_cast_ref::<FileEnumerator>()
+                    .to_glib_none()
+                    .0,
+                cancellable.to_glib_none().0,
+                &mut err,
+            );
+            if err.is_null() {
+                Ok(from_glib_full(res))
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+
+    fn parent_close(&self, cancellable: Option<&Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GFileEnumeratorClass;
+            let mut err = ptr::null_mut();
+            if let Some(f) = (*parent_class).close_fn {
+                if from_glib(f(
+                    self.obj()
+                        .unsafe_cast_ref::<FileEnumerator>()
+                        .to_glib_none()
+                        .0,
+                    cancellable.to_glib_none().0,
+                    &mut err,
+                )) {
+                    Ok(())
+                } else {
+                    Err(from_glib_full(err))
+                }
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+unsafe impl<T: FileEnumeratorImpl> IsSubclassable<T> for FileEnumerator {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();
+        klass.next_file = Some(enumerator_next_file::<T>);
+        klass.close_fn = Some(enumerator_close::<T>);
+    }
+}
+
+unsafe extern "C" fn enumerator_next_file<T: FileEnumeratorImpl>(
+    ptr: *mut ffi::GFileEnumerator,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileInfo {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    match imp.next_file(
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(info) => info.into_glib_ptr(),
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "C" fn enumerator_close<T: FileEnumeratorImpl>(
+    ptr: *mut ffi::GFileEnumerator,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    match imp.close(
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(_) => glib::ffi::GTRUE,
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            glib::ffi::GFALSE
+        }
+    }
+}
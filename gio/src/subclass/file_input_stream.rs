@@ -0,0 +1,233 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ptr;
+
+use glib::{subclass::prelude::*, translate::*, Cast, Error, SeekType};
+use libc::c_char;
+
+use crate::subclass::prelude::InputStreamImpl;
+use crate::{Cancellable, FileInfo, FileInputStream, IOErrorEnum};
+
+pub trait FileInputStreamImpl: InputStreamImpl + FileInputStreamImplExt {
+    fn tell(&self) -> i64 {
+        self.parent_tell()
+    }
+
+    fn can_seek(&self) -> bool {
+        self.parent_can_seek()
+    }
+
+    fn seek(
+        &self,
+        offset: i64,
+        type_: SeekType,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error> {
+        self.parent_seek(offset, type_, cancellable)
+    }
+
+    fn query_info(
+        &self,
+        attributes: &str,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error> {
+        self.parent_query_info(attributes, cancellable)
+    }
+}
+
+pub trait FileInputStreamImplExt: ObjectSubclass {
+    fn parent_tell(&self) -> i64;
+
+    fn parent_can_seek(&self) -> bool;
+
+    fn parent_seek(
+        &self,
+        offset: i64,
+        type_: SeekType,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error>;
+
+    fn parent_query_info(
+        &self,
+        attributes: &str,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error>;
+}
+
+impl<T: FileInputStreamImpl> FileInputStreamImplExt for T {
+    fn parent_tell(&self) -> i64 {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GFileInputStreamClass;
+            if let Some(f) = (*parent_class).tell {
+                f(self
+                    .obj()
+                    .unsafe_cast_ref::<FileInputStream>()
+                    .to_glib_none()
+                    .0)
+            } else {
+                0
+            }
+        }
+    }
+
+    fn parent_can_seek(&self) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GFileInputStreamClass;
+            // Same as GIO: streams that implement `seek` can seek unless they say otherwise
+            match ((*parent_class).can_seek, (*parent_class).seek) {
+                (Some(f), Some(_)) => from_glib(f(self
+                    .obj()
+                    .unsafe_cast_ref::<FileInputStream>()
+                    .to_glib_none()
+                    .0)),
+                (None, Some(_)) => true,
+                (_, None) => false,
+            }
+        }
+    }
+
+    fn parent_seek(
+        &self,
+        offset: i64,
+        type_: SeekType,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<(), Error> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GFileInputStreamClass;
+            let f = (*parent_class).seek.ok_or_else(|| {
+                Error::new(IOErrorEnum::NotSupported, "Seek not supported on stream")
+            })?;
+            let mut err = ptr::null_mut();
+            if from_glib(f(
+                self.obj()
+                    .unsafe_cast_ref::<FileInputStream>()
+                    .to_glib_none()
+                    .0,
+                offset,
+                type_.into_glib(),
+                cancellable.to_glib_none().0,
+                &mut err,
+            )) {
+                Ok(())
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+
+    fn parent_query_info(
+        &self,
+        attributes: &str,
+        cancellable: Option<&Cancellable>,
+    ) -> Result<FileInfo, Error> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GFileInputStreamClass;
+            let f = (*parent_class).query_info.ok_or_else(|| {
+                Error::new(
+                    IOErrorEnum::NotSupported,
+                    "Stream doesn’t support query_info",
+                )
+            })?;
+            let mut err = ptr::null_mut();
+            let res = f(
+                self.obj()
+                    .unsafe_cast_ref::<FileInputStream>()
+                    .to_glib_none()
+                    .0,
+                attributes.to_glib_none().0,
+                cancellable.to_glib_none().0,
+                &mut err,
+            );
+            if err.is_null() {
+                Ok(from_glib_full(res))
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+}
+
+unsafe impl<T: FileInputStreamImpl> IsSubclassable<T> for FileInputStream {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();
+        klass.tell = Some(stream_tell::<T>);
+        klass.can_seek = Some(stream_can_seek::<T>);
+        klass.seek = Some(stream_seek::<T>);
+        klass.query_info = Some(stream_query_info::<T>);
+    }
+}
+
+unsafe extern "C" fn stream_tell<T: FileInputStreamImpl>(ptr: *mut ffi::GFileInputStream) -> i64 {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.tell()
+}
+
+unsafe extern "C" fn stream_can_seek<T: FileInputStreamImpl>(
+    ptr: *mut ffi::GFileInputStream,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.can_seek().into_glib()
+}
+
+unsafe extern "C" fn stream_seek<T: FileInputStreamImpl>(
+    ptr: *mut ffi::GFileInputStream,
+    offset: i64,
+    type_: glib::ffi::GSeekType,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    match imp.seek(
+        offset,
+        from_glib(type_),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(()) => glib::ffi::GTRUE,
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            glib::ffi::GFALSE
+        }
+    }
+}
+
+unsafe extern "C" fn stream_query_info<T: FileInputStreamImpl>(
+    ptr: *mut ffi::GFileInputStream,
+    attributes: *const c_char,
+    cancellable: *mut ffi::GCancellable,
+    err: *mut *mut glib::ffi::GError,
+) -> *mut ffi::GFileInfo {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+    let attributes: Borrowed<glib::GString> = from_glib_borrow(attributes);
+
+    match imp.query_info(
+        attributes.as_str(),
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    ) {
+        Ok(info) => info.into_glib_ptr(),
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            ptr::null_mut()
+        }
+    }
+}
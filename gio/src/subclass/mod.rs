@@ -4,12 +4,24 @@ mod action_group;
 mod action_map;
 mod application;
 mod async_initable;
+mod converter;
+mod dbus_interface_skeleton;
+mod file;
+mod file_enumerator;
+mod file_input_stream;
 mod initable;
 mod input_stream;
 mod io_stream;
 mod list_model;
 mod output_stream;
+mod pollable_input_stream;
+mod pollable_output_stream;
 mod seekable;
+mod settings_backend;
+mod socket_listener;
+mod socket_service;
+mod threaded_socket_service;
+mod vfs;
 
 pub use self::application::ArgumentList;
 
@@ -22,11 +34,23 @@ pub mod prelude {
         action_map::{ActionMapImpl, ActionMapImplExt},
         application::{ApplicationImpl, ApplicationImplExt},
         async_initable::{AsyncInitableImpl, AsyncInitableImplExt},
+        converter::{ConverterImpl, ConverterImplExt},
+        dbus_interface_skeleton::{DBusInterfaceSkeletonImpl, DBusInterfaceSkeletonImplExt},
+        file::{FileImpl, FileImplExt},
+        file_enumerator::{FileEnumeratorImpl, FileEnumeratorImplExt},
+        file_input_stream::{FileInputStreamImpl, FileInputStreamImplExt},
         initable::{InitableImpl, InitableImplExt},
         input_stream::{InputStreamImpl, InputStreamImplExt},
         io_stream::{IOStreamImpl, IOStreamImplExt},
         list_model::{ListModelImpl, ListModelImplExt},
         output_stream::{OutputStreamImpl, OutputStreamImplExt},
+        pollable_input_stream::{PollableInputStreamImpl, PollableInputStreamImplExt},
+        pollable_output_stream::{PollableOutputStreamImpl, PollableOutputStreamImplExt},
         seekable::{SeekableImpl, SeekableImplExt},
+        settings_backend::{SettingsBackendImpl, SettingsBackendImplExt},
+        socket_listener::{SocketListenerImpl, SocketListenerImplExt},
+        socket_service::{SocketServiceImpl, SocketServiceImplExt},
+        threaded_socket_service::{ThreadedSocketServiceImpl, ThreadedSocketServiceImplExt},
+        vfs::{VfsImpl, VfsImplExt},
     };
 }
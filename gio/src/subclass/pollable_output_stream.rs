@@ -0,0 +1,162 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ptr;
+
+use glib::{subclass::prelude::*, translate::*, Cast, Error};
+
+use crate::{Cancellable, PollableOutputStream};
+
+pub trait PollableOutputStreamImpl: ObjectImpl + PollableOutputStreamImplExt + Send {
+    fn can_poll(&self) -> bool {
+        self.parent_can_poll()
+    }
+
+    fn is_writable(&self) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a source that is dispatched once the stream is writable.
+    fn create_source(&self, cancellable: Option<&Cancellable>) -> glib::Source;
+
+    // rustdoc-stripper-ignore-next
+    /// Writes without blocking, failing with [`IOErrorEnum::WouldBlock`](crate::IOErrorEnum::WouldBlock)
+    /// if no data can be written.
+    fn write_nonblocking(&self, buffer: &[u8]) -> Result<usize, Error> {
+        self.parent_write_nonblocking(buffer)
+    }
+}
+
+pub trait PollableOutputStreamImplExt: ObjectSubclass {
+    fn parent_can_poll(&self) -> bool;
+
+    fn parent_write_nonblocking(&self, buffer: &[u8]) -> Result<usize, Error>;
+}
+
+impl<T: PollableOutputStreamImpl> PollableOutputStreamImplExt for T {
+    fn parent_can_poll(&self) -> bool {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .parent_interface::<PollableOutputStream>()
+                as *const ffi::GPollableOutputStreamInterface;
+
+            let func = (*parent_iface)
+                .can_poll
+                .expect("no parent \"can_poll\" implementation");
+            from_glib(func(
+                self.obj()
+                    .unsafe_cast_ref::<PollableOutputStream>()
+                    .to_glib_none()
+                    .0,
+            ))
+        }
+    }
+
+    fn parent_write_nonblocking(&self, buffer: &[u8]) -> Result<usize, Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface = type_data
+                .as_ref()
+                .parent_interface::<PollableOutputStream>()
+                as *const ffi::GPollableOutputStreamInterface;
+
+            let func = (*parent_iface)
+                .write_nonblocking
+                .expect("no parent \"write_nonblocking\" implementation");
+            let mut err = ptr::null_mut();
+            let res = func(
+                self.obj()
+                    .unsafe_cast_ref::<PollableOutputStream>()
+                    .to_glib_none()
+                    .0,
+                buffer.as_ptr() as *mut u8,
+                buffer.len(),
+                &mut err,
+            );
+            if res == -1 {
+                Err(from_glib_full(err))
+            } else {
+                debug_assert!(res >= 0);
+                let res = res as usize;
+                debug_assert!(res <= buffer.len());
+                Ok(res)
+            }
+        }
+    }
+}
+
+unsafe impl<T: PollableOutputStreamImpl> IsImplementable<T> for PollableOutputStream {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.can_poll = Some(stream_can_poll::<T>);
+        iface.is_writable = Some(stream_is_writable::<T>);
+        iface.create_source = Some(stream_create_source::<T>);
+        iface.write_nonblocking = Some(stream_write_nonblocking::<T>);
+    }
+}
+
+unsafe extern "C" fn stream_can_poll<T: PollableOutputStreamImpl>(
+    stream: *mut ffi::GPollableOutputStream,
+) -> glib::ffi::gboolean {
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.can_poll().into_glib()
+}
+
+unsafe extern "C" fn stream_is_writable<T: PollableOutputStreamImpl>(
+    stream: *mut ffi::GPollableOutputStream,
+) -> glib::ffi::gboolean {
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.is_writable().into_glib()
+}
+
+unsafe extern "C" fn stream_create_source<T: PollableOutputStreamImpl>(
+    stream: *mut ffi::GPollableOutputStream,
+    cancellable: *mut ffi::GCancellable,
+) -> *mut glib::ffi::GSource {
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.create_source(
+        Option::<Cancellable>::from_glib_borrow(cancellable)
+            .as_ref()
+            .as_ref(),
+    )
+    .to_glib_full()
+}
+
+unsafe extern "C" fn stream_write_nonblocking<T: PollableOutputStreamImpl>(
+    stream: *mut ffi::GPollableOutputStream,
+    buffer: *mut u8,
+    count: usize,
+    err: *mut *mut glib::ffi::GError,
+) -> isize {
+    use std::slice;
+
+    debug_assert!(count <= isize::MAX as usize);
+
+    let instance = &*(stream as *mut T::Instance);
+    let imp = instance.imp();
+
+    match imp.write_nonblocking(if count == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(buffer as *const u8, count)
+    }) {
+        Ok(res) => {
+            assert!(res <= isize::MAX as usize);
+            assert!(res <= count);
+            res as isize
+        }
+        Err(e) => {
+            if !err.is_null() {
+                *err = e.into_glib_ptr();
+            }
+            -1
+        }
+    }
+}
@@ -0,0 +1,459 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::collections::BTreeMap;
+
+use glib::{subclass::prelude::*, translate::*, Cast, Variant, VariantTy};
+use libc::c_char;
+
+use crate::{
+    settings_backend::{origin_tag_ptr, tree_from_glib, tree_to_glib},
+    Permission, SettingsBackend, SettingsBackendOriginTag, SimplePermission,
+};
+
+pub trait SettingsBackendImpl: ObjectImpl + SettingsBackendImplExt + Send + Sync {
+    // rustdoc-stripper-ignore-next
+    /// Reads the value of the full path `key`, of type `expected_type`.
+    ///
+    /// If `default_value` is `true` the default value, e.g. from a system-wide configuration,
+    /// should be returned instead of the user's value.
+    fn read(&self, key: &str, expected_type: &VariantTy, default_value: bool) -> Option<Variant>;
+
+    // rustdoc-stripper-ignore-next
+    /// Reads the value the user set for `key`, ignoring defaults.
+    fn read_user_value(&self, key: &str, expected_type: &VariantTy) -> Option<Variant> {
+        self.parent_read_user_value(key, expected_type)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Writes `value` to `key`, returning `false` if it could not be written.
+    ///
+    /// On success [`changed`](crate::prelude::SettingsBackendExtManual::changed) has to be called
+    /// with `origin_tag`.
+    fn write(
+        &self,
+        key: &str,
+        value: &Variant,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Atomically writes all values in `tree`, resetting the keys with `None` values.
+    ///
+    /// On success [`changed_tree`](crate::prelude::SettingsBackendExtManual::changed_tree) has to
+    /// be called with `origin_tag`.
+    fn write_tree(
+        &self,
+        tree: &BTreeMap<String, Option<Variant>>,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Resets `key` to its default value.
+    fn reset(&self, key: &str, origin_tag: Option<SettingsBackendOriginTag>);
+
+    // rustdoc-stripper-ignore-next
+    /// Returns whether `key` is writable.
+    #[doc(alias = "get_writable")]
+    fn writable(&self, key: &str) -> bool;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the permission needed to change `key`.
+    #[doc(alias = "get_permission")]
+    fn permission(&self, key: &str) -> Permission {
+        self.parent_permission(key)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Starts watching the path or key `name` for changes made outside of this process.
+    fn subscribe(&self, name: &str) {
+        self.parent_subscribe(name)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Stops watching `name`.
+    fn unsubscribe(&self, name: &str) {
+        self.parent_unsubscribe(name)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Flushes pending writes to the permanent storage.
+    fn sync(&self) {
+        self.parent_sync()
+    }
+}
+
+pub trait SettingsBackendImplExt: ObjectSubclass {
+    fn parent_read(
+        &self,
+        key: &str,
+        expected_type: &VariantTy,
+        default_value: bool,
+    ) -> Option<Variant>;
+
+    fn parent_read_user_value(&self, key: &str, expected_type: &VariantTy) -> Option<Variant>;
+
+    fn parent_write(
+        &self,
+        key: &str,
+        value: &Variant,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) -> bool;
+
+    fn parent_write_tree(
+        &self,
+        tree: &BTreeMap<String, Option<Variant>>,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) -> bool;
+
+    fn parent_reset(&self, key: &str, origin_tag: Option<SettingsBackendOriginTag>);
+
+    fn parent_writable(&self, key: &str) -> bool;
+
+    fn parent_permission(&self, key: &str) -> Permission;
+
+    fn parent_subscribe(&self, name: &str);
+
+    fn parent_unsubscribe(&self, name: &str);
+
+    fn parent_sync(&self);
+}
+
+impl<T: SettingsBackendImpl> SettingsBackendImplExt for T {
+    fn parent_read(
+        &self,
+        key: &str,
+        expected_type: &VariantTy,
+        default_value: bool,
+    ) -> Option<Variant> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            let f = (*parent_class)
+                .read
+                .expect("No parent class implementation for \"read\"");
+            from_glib_full(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+                expected_type.to_glib_none().0,
+                default_value.into_glib(),
+            ))
+        }
+    }
+
+    fn parent_read_user_value(&self, key: &str, expected_type: &VariantTy) -> Option<Variant> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            let f = (*parent_class)
+                .read_user_value
+                .expect("No parent class implementation for \"read_user_value\"");
+            from_glib_full(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+                expected_type.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn parent_write(
+        &self,
+        key: &str,
+        value: &Variant,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            let f = (*parent_class)
+                .write
+                .expect("No parent class implementation for \"write\"");
+            from_glib(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+                value.to_glib_none().0,
+                origin_tag_ptr(origin_tag),
+            ))
+        }
+    }
+
+    fn parent_write_tree(
+        &self,
+        tree: &BTreeMap<String, Option<Variant>>,
+        origin_tag: Option<SettingsBackendOriginTag>,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            let f = (*parent_class)
+                .write_tree
+                .expect("No parent class implementation for \"write_tree\"");
+            let tree = tree_to_glib(tree);
+            let res = from_glib(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                tree,
+                origin_tag_ptr(origin_tag),
+            ));
+            glib::ffi::g_tree_unref(tree);
+            res
+        }
+    }
+
+    fn parent_reset(&self, key: &str, origin_tag: Option<SettingsBackendOriginTag>) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            let f = (*parent_class)
+                .reset
+                .expect("No parent class implementation for \"reset\"");
+            f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+                origin_tag_ptr(origin_tag),
+            )
+        }
+    }
+
+    fn parent_writable(&self, key: &str) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            let f = (*parent_class)
+                .get_writable
+                .expect("No parent class implementation for \"get_writable\"");
+            from_glib(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn parent_permission(&self, key: &str) -> Permission {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            if let Some(f) = (*parent_class).get_permission {
+                from_glib_full(f(
+                    self.obj()
+                        .unsafe_cast_ref::<SettingsBackend>()
+                        .to_glib_none()
+                        .0,
+                    key.to_glib_none().0,
+                ))
+            } else {
+                // Same as GSettingsBackend without implementation
+                SimplePermission::new(true).upcast()
+            }
+        }
+    }
+
+    fn parent_subscribe(&self, name: &str) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            if let Some(f) = (*parent_class).subscribe {
+                f(
+                    self.obj()
+                        .unsafe_cast_ref::<SettingsBackend>()
+                        .to_glib_none()
+                        .0,
+                    name.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_unsubscribe(&self, name: &str) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            if let Some(f) = (*parent_class).unsubscribe {
+                f(
+                    self.obj()
+                        .unsafe_cast_ref::<SettingsBackend>()
+                        .to_glib_none()
+                        .0,
+                    name.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_sync(&self) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSettingsBackendClass;
+            if let Some(f) = (*parent_class).sync {
+                f(self
+                    .obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0)
+            }
+        }
+    }
+}
+
+unsafe impl<T: SettingsBackendImpl> IsSubclassable<T> for SettingsBackend {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();
+        klass.read = Some(settings_backend_read::<T>);
+        klass.read_user_value = Some(settings_backend_read_user_value::<T>);
+        klass.write = Some(settings_backend_write::<T>);
+        klass.write_tree = Some(settings_backend_write_tree::<T>);
+        klass.reset = Some(settings_backend_reset::<T>);
+        klass.get_writable = Some(settings_backend_get_writable::<T>);
+        klass.get_permission = Some(settings_backend_get_permission::<T>);
+        klass.subscribe = Some(settings_backend_subscribe::<T>);
+        klass.unsubscribe = Some(settings_backend_unsubscribe::<T>);
+        klass.sync = Some(settings_backend_sync::<T>);
+    }
+}
+
+unsafe extern "C" fn settings_backend_read<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const c_char,
+    expected_type: *const glib::ffi::GVariantType,
+    default_value: glib::ffi::gboolean,
+) -> *mut glib::ffi::GVariant {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.read(
+        &glib::GString::from_glib_borrow(key),
+        VariantTy::from_ptr(expected_type),
+        from_glib(default_value),
+    )
+    .into_glib_ptr()
+}
+
+unsafe extern "C" fn settings_backend_read_user_value<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const c_char,
+    expected_type: *const glib::ffi::GVariantType,
+) -> *mut glib::ffi::GVariant {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.read_user_value(
+        &glib::GString::from_glib_borrow(key),
+        VariantTy::from_ptr(expected_type),
+    )
+    .into_glib_ptr()
+}
+
+unsafe extern "C" fn settings_backend_write<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const c_char,
+    value: *mut glib::ffi::GVariant,
+    origin_tag: glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.write(
+        &glib::GString::from_glib_borrow(key),
+        &from_glib_borrow(value),
+        SettingsBackendOriginTag::from_raw(origin_tag),
+    )
+    .into_glib()
+}
+
+unsafe extern "C" fn settings_backend_write_tree<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    tree: *mut glib::ffi::GTree,
+    origin_tag: glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.write_tree(
+        &tree_from_glib(tree),
+        SettingsBackendOriginTag::from_raw(origin_tag),
+    )
+    .into_glib()
+}
+
+unsafe extern "C" fn settings_backend_reset<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const c_char,
+    origin_tag: glib::ffi::gpointer,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.reset(
+        &glib::GString::from_glib_borrow(key),
+        SettingsBackendOriginTag::from_raw(origin_tag),
+    )
+}
+
+unsafe extern "C" fn settings_backend_get_writable<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const c_char,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.writable(&glib::GString::from_glib_borrow(key))
+        .into_glib()
+}
+
+unsafe extern "C" fn settings_backend_get_permission<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    key: *const c_char,
+) -> *mut ffi::GPermission {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.permission(&glib::GString::from_glib_borrow(key))
+        .into_glib_ptr()
+}
+
+unsafe extern "C" fn settings_backend_subscribe<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    name: *const c_char,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.subscribe(&glib::GString::from_glib_borrow(name))
+}
+
+unsafe extern "C" fn settings_backend_unsubscribe<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+    name: *const c_char,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.unsubscribe(&glib::GString::from_glib_borrow(name))
+}
+
+unsafe extern "C" fn settings_backend_sync<T: SettingsBackendImpl>(
+    ptr: *mut ffi::GSettingsBackend,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.sync()
+}
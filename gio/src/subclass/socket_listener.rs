@@ -0,0 +1,82 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{subclass::prelude::*, translate::*, Cast};
+
+use crate::{Socket, SocketListener, SocketListenerEvent};
+
+pub trait SocketListenerImpl: ObjectImpl + SocketListenerImplExt {
+    fn changed(&self) {
+        self.parent_changed()
+    }
+
+    fn event(&self, event: SocketListenerEvent, socket: &Socket) {
+        self.parent_event(event, socket)
+    }
+}
+
+pub trait SocketListenerImplExt: ObjectSubclass {
+    fn parent_changed(&self);
+
+    fn parent_event(&self, event: SocketListenerEvent, socket: &Socket);
+}
+
+impl<T: SocketListenerImpl> SocketListenerImplExt for T {
+    fn parent_changed(&self) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSocketListenerClass;
+            if let Some(f) = (*parent_class).changed {
+                f(self
+                    .obj()
+                    .unsafe_cast_ref::<SocketListener>()
+                    .to_glib_none()
+                    .0)
+            }
+        }
+    }
+
+    fn parent_event(&self, event: SocketListenerEvent, socket: &Socket) {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSocketListenerClass;
+            if let Some(f) = (*parent_class).event {
+                f(
+                    self.obj()
+                        .unsafe_cast_ref::<SocketListener>()
+                        .to_glib_none()
+                        .0,
+                    event.into_glib(),
+                    socket.to_glib_none().0,
+                )
+            }
+        }
+    }
+}
+
+unsafe impl<T: SocketListenerImpl> IsSubclassable<T> for SocketListener {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();
+        klass.changed = Some(listener_changed::<T>);
+        klass.event = Some(listener_event::<T>);
+    }
+}
+
+unsafe extern "C" fn listener_changed<T: SocketListenerImpl>(ptr: *mut ffi::GSocketListener) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.changed()
+}
+
+unsafe extern "C" fn listener_event<T: SocketListenerImpl>(
+    ptr: *mut ffi::GSocketListener,
+    event: ffi::GSocketListenerEvent,
+    socket: *mut ffi::GSocket,
+) {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.event(from_glib(event), &from_glib_borrow(socket))
+}
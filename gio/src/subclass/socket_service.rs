@@ -0,0 +1,79 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{subclass::prelude::*, translate::*, Cast};
+
+use super::socket_listener::SocketListenerImpl;
+use crate::{SocketConnection, SocketService};
+
+pub trait SocketServiceImpl: SocketListenerImpl + SocketServiceImplExt {
+    // rustdoc-stripper-ignore-next
+    /// Handles a new connection, returns `true` if the connection was handled and no other
+    /// handlers of the [`incoming`](crate::SocketService::connect_incoming) signal should be
+    /// called.
+    fn incoming(
+        &self,
+        connection: &SocketConnection,
+        source_object: Option<&glib::Object>,
+    ) -> bool {
+        self.parent_incoming(connection, source_object)
+    }
+}
+
+pub trait SocketServiceImplExt: ObjectSubclass {
+    fn parent_incoming(
+        &self,
+        connection: &SocketConnection,
+        source_object: Option<&glib::Object>,
+    ) -> bool;
+}
+
+impl<T: SocketServiceImpl> SocketServiceImplExt for T {
+    fn parent_incoming(
+        &self,
+        connection: &SocketConnection,
+        source_object: Option<&glib::Object>,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GSocketServiceClass;
+            if let Some(f) = (*parent_class).incoming {
+                from_glib(f(
+                    self.obj()
+                        .unsafe_cast_ref::<SocketService>()
+                        .to_glib_none()
+                        .0,
+                    connection.to_glib_none().0,
+                    source_object.to_glib_none().0,
+                ))
+            } else {
+                false
+            }
+        }
+    }
+}
+
+unsafe impl<T: SocketServiceImpl> IsSubclassable<T> for SocketService {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();
+        klass.incoming = Some(service_incoming::<T>);
+    }
+}
+
+unsafe extern "C" fn service_incoming<T: SocketServiceImpl>(
+    ptr: *mut ffi::GSocketService,
+    connection: *mut ffi::GSocketConnection,
+    source_object: *mut glib::gobject_ffi::GObject,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.incoming(
+        &from_glib_borrow(connection),
+        Option::<glib::Object>::from_glib_borrow(source_object)
+            .as_ref()
+            .as_ref(),
+    )
+    .into_glib()
+}
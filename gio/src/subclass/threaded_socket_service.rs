@@ -0,0 +1,155 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{subclass::prelude::*, translate::*, Cast};
+
+use super::socket_service::SocketServiceImpl;
+use crate::{SocketConnection, ThreadedSocketService};
+
+pub trait ThreadedSocketServiceImpl:
+    SocketServiceImpl + ThreadedSocketServiceImplExt + Send + Sync
+{
+    // rustdoc-stripper-ignore-next
+    /// Handles a new connection in a worker thread, returns `true` if the connection was handled
+    /// and no other handlers of the [`run`](crate::ThreadedSocketService::connect_run) signal
+    /// should be called.
+    ///
+    /// The connection is closed once this returns.
+    fn run(&self, connection: &SocketConnection, source_object: Option<&glib::Object>) -> bool {
+        self.parent_run(connection, source_object)
+    }
+}
+
+pub trait ThreadedSocketServiceImplExt: ObjectSubclass {
+    fn parent_run(
+        &self,
+        connection: &SocketConnection,
+        source_object: Option<&glib::Object>,
+    ) -> bool;
+}
+
+impl<T: ThreadedSocketServiceImpl> ThreadedSocketServiceImplExt for T {
+    fn parent_run(
+        &self,
+        connection: &SocketConnection,
+        source_object: Option<&glib::Object>,
+    ) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class =
+                data.as_ref().parent_class() as *mut ffi::GThreadedSocketServiceClass;
+            if let Some(f) = (*parent_class).run {
+                from_glib(f(
+                    self.obj()
+                        .unsafe_cast_ref::<ThreadedSocketService>()
+                        .to_glib_none()
+                        .0,
+                    connection.to_glib_none().0,
+                    source_object.to_glib_none().0,
+                ))
+            } else {
+                false
+            }
+        }
+    }
+}
+
+unsafe impl<T: ThreadedSocketServiceImpl> IsSubclassable<T> for ThreadedSocketService {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();
+        klass.run = Some(service_run::<T>);
+    }
+}
+
+unsafe extern "C" fn service_run<T: ThreadedSocketServiceImpl>(
+    ptr: *mut ffi::GThreadedSocketService,
+    connection: *mut ffi::GSocketConnection,
+    source_object: *mut glib::gobject_ffi::GObject,
+) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.run(
+        &from_glib_borrow(connection),
+        Option::<glib::Object>::from_glib_borrow(source_object)
+            .as_ref()
+            .as_ref(),
+    )
+    .into_glib()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use super::*;
+    use crate::prelude::*;
+
+    mod imp {
+        use super::*;
+        use crate::subclass::prelude::*;
+
+        // Replies to every connection with the bytes it received, upper-cased
+        #[derive(Default)]
+        pub struct UpperService;
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for UpperService {
+            const NAME: &'static str = "UpperService";
+            type Type = super::UpperService;
+            type ParentType = ThreadedSocketService;
+        }
+
+        impl ObjectImpl for UpperService {}
+        impl SocketListenerImpl for UpperService {}
+        impl SocketServiceImpl for UpperService {}
+
+        impl ThreadedSocketServiceImpl for UpperService {
+            fn run(
+                &self,
+                connection: &SocketConnection,
+                _source_object: Option<&glib::Object>,
+            ) -> bool {
+                let mut buf = [0u8; 16];
+                let len = connection
+                    .input_stream()
+                    .read(&mut buf[..], crate::Cancellable::NONE)
+                    .unwrap();
+                buf.make_ascii_uppercase();
+                connection
+                    .output_stream()
+                    .write_all(&buf[..len], crate::Cancellable::NONE)
+                    .unwrap();
+                true
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct UpperService(ObjectSubclass<imp::UpperService>)
+            @extends ThreadedSocketService, crate::SocketService, crate::SocketListener;
+    }
+
+    #[test]
+    fn test_threaded_socket_service() {
+        let service = glib::Object::new::<UpperService>(&[("max-threads", &1i32)]);
+        let port = service.add_any_inet_port(glib::Object::NONE).unwrap();
+        service.start();
+
+        let main_loop = glib::MainLoop::new(None, false);
+        let client = std::thread::spawn({
+            let main_loop = main_loop.clone();
+            move || {
+                let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+                stream.write_all(b"hello").unwrap();
+                let mut reply = Vec::new();
+                stream.read_to_end(&mut reply).unwrap();
+                main_loop.quit();
+                reply
+            }
+        });
+        main_loop.run();
+        assert_eq!(client.join().unwrap(), b"HELLO");
+    }
+}
@@ -0,0 +1,264 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::sync::Mutex;
+
+use glib::{prelude::*, subclass::prelude::*, translate::*, GString, StrV};
+use libc::c_char;
+use once_cell::sync::Lazy;
+
+use crate::{File, Vfs};
+
+pub trait VfsImpl: ObjectImpl + VfsImplExt + Send + Sync {
+    fn is_active(&self) -> bool;
+
+    #[doc(alias = "get_file_for_path")]
+    fn file_for_path(&self, path: &str) -> File;
+
+    #[doc(alias = "get_file_for_uri")]
+    fn file_for_uri(&self, uri: &str) -> File;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the URI schemes supported by the VFS.
+    ///
+    /// This is only called once per instance, the result is kept for the lifetime of the VFS.
+    #[doc(alias = "get_supported_uri_schemes")]
+    fn supported_uri_schemes(&self) -> Vec<GString>;
+
+    fn parse_name(&self, parse_name: &str) -> File;
+}
+
+pub trait VfsImplExt: ObjectSubclass {
+    fn parent_is_active(&self) -> bool;
+
+    fn parent_file_for_path(&self, path: &str) -> File;
+
+    fn parent_file_for_uri(&self, uri: &str) -> File;
+
+    fn parent_supported_uri_schemes(&self) -> Vec<GString>;
+
+    fn parent_parse_name(&self, parse_name: &str) -> File;
+}
+
+impl<T: VfsImpl> VfsImplExt for T {
+    fn parent_is_active(&self) -> bool {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GVfsClass;
+            let f = (*parent_class)
+                .is_active
+                .expect("No parent class implementation for \"is_active\"");
+            from_glib(f(self.obj().unsafe_cast_ref::<Vfs>().to_glib_none().0))
+        }
+    }
+
+    fn parent_file_for_path(&self, path: &str) -> File {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GVfsClass;
+            let f = (*parent_class)
+                .get_file_for_path
+                .expect("No parent class implementation for \"get_file_for_path\"");
+            from_glib_full(f(
+                self.obj().unsafe_cast_ref::<Vfs>().to_glib_none().0,
+                path.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn parent_file_for_uri(&self, uri: &str) -> File {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GVfsClass;
+            let f = (*parent_class)
+                .get_file_for_uri
+                .expect("No parent class implementation for \"get_file_for_uri\"");
+            from_glib_full(f(
+                self.obj().unsafe_cast_ref::<Vfs>().to_glib_none().0,
+                uri.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn parent_supported_uri_schemes(&self) -> Vec<GString> {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GVfsClass;
+            let f = (*parent_class)
+                .get_supported_uri_schemes
+                .expect("No parent class implementation for \"get_supported_uri_schemes\"");
+            FromGlibPtrContainer::from_glib_none(f(self
+                .obj()
+                .unsafe_cast_ref::<Vfs>()
+                .to_glib_none()
+                .0))
+        }
+    }
+
+    fn parent_parse_name(&self, parse_name: &str) -> File {
+        unsafe {
+            let data = T::type_data();
+            let parent_class = data.as_ref().parent_class() as *mut ffi::GVfsClass;
+            let f = (*parent_class)
+                .parse_name
+                .expect("No parent class implementation for \"parse_name\"");
+            from_glib_full(f(
+                self.obj().unsafe_cast_ref::<Vfs>().to_glib_none().0,
+                parse_name.to_glib_none().0,
+            ))
+        }
+    }
+}
+
+unsafe impl<T: VfsImpl> IsSubclassable<T> for Vfs {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();
+        klass.is_active = Some(vfs_is_active::<T>);
+        klass.get_file_for_path = Some(vfs_get_file_for_path::<T>);
+        klass.get_file_for_uri = Some(vfs_get_file_for_uri::<T>);
+        klass.get_supported_uri_schemes = Some(vfs_get_supported_uri_schemes::<T>);
+        klass.parse_name = Some(vfs_parse_name::<T>);
+    }
+}
+
+unsafe extern "C" fn vfs_is_active<T: VfsImpl>(ptr: *mut ffi::GVfs) -> glib::ffi::gboolean {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    imp.is_active().into_glib()
+}
+
+unsafe extern "C" fn vfs_get_file_for_path<T: VfsImpl>(
+    ptr: *mut ffi::GVfs,
+    path: *const c_char,
+) -> *mut ffi::GFile {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+    let path: Borrowed<GString> = from_glib_borrow(path);
+
+    imp.file_for_path(path.as_str()).into_glib_ptr()
+}
+
+unsafe extern "C" fn vfs_get_file_for_uri<T: VfsImpl>(
+    ptr: *mut ffi::GVfs,
+    uri: *const c_char,
+) -> *mut ffi::GFile {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+    let uri: Borrowed<GString> = from_glib_borrow(uri);
+
+    imp.file_for_uri(uri.as_str()).into_glib_ptr()
+}
+
+static SUPPORTED_URI_SCHEMES_QUARK: Lazy<glib::Quark> =
+    Lazy::new(|| glib::Quark::from_str("gtk-rs-subclass-vfs-supported-uri-schemes"));
+static SUPPORTED_URI_SCHEMES_LOCK: Mutex<()> = Mutex::new(());
+
+unsafe extern "C" fn vfs_get_supported_uri_schemes<T: VfsImpl>(
+    ptr: *mut ffi::GVfs,
+) -> *const *const c_char {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+
+    // The returned array is owned by the VFS and has to stay valid as long as it lives.
+    let _lock = SUPPORTED_URI_SCHEMES_LOCK.lock().unwrap();
+    let obj = imp.obj();
+    let schemes = match obj.qdata::<StrV>(*SUPPORTED_URI_SCHEMES_QUARK) {
+        Some(schemes) => schemes,
+        None => {
+            obj.set_qdata(
+                *SUPPORTED_URI_SCHEMES_QUARK,
+                StrV::from(imp.supported_uri_schemes()),
+            );
+            obj.qdata::<StrV>(*SUPPORTED_URI_SCHEMES_QUARK).unwrap()
+        }
+    };
+    schemes.as_ref().as_ptr() as *const *const c_char
+}
+
+unsafe extern "C" fn vfs_parse_name<T: VfsImpl>(
+    ptr: *mut ffi::GVfs,
+    parse_name: *const c_char,
+) -> *mut ffi::GFile {
+    let instance = &*(ptr as *mut T::Instance);
+    let imp = instance.imp();
+    let parse_name: Borrowed<GString> = from_glib_borrow(parse_name);
+
+    imp.parse_name(parse_name.as_str()).into_glib_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::prelude::*;
+
+    mod imp {
+        use super::*;
+
+        // Forwards to the local VFS and counts the lookups of the supported schemes
+        #[derive(Default)]
+        pub struct CountingVfs {
+            pub scheme_lookups: AtomicUsize,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for CountingVfs {
+            const NAME: &'static str = "CountingVfs";
+            type Type = super::CountingVfs;
+            type ParentType = Vfs;
+        }
+
+        impl ObjectImpl for CountingVfs {}
+
+        impl VfsImpl for CountingVfs {
+            fn is_active(&self) -> bool {
+                true
+            }
+
+            fn file_for_path(&self, path: &str) -> File {
+                Vfs::local().file_for_path(path)
+            }
+
+            fn file_for_uri(&self, uri: &str) -> File {
+                Vfs::local().file_for_uri(uri)
+            }
+
+            fn supported_uri_schemes(&self) -> Vec<GString> {
+                self.scheme_lookups.fetch_add(1, Ordering::SeqCst);
+                vec!["file".into(), "counting".into()]
+            }
+
+            fn parse_name(&self, parse_name: &str) -> File {
+                Vfs::local().parse_name(parse_name)
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct CountingVfs(ObjectSubclass<imp::CountingVfs>) @extends Vfs;
+    }
+
+    #[test]
+    fn supported_uri_schemes() {
+        let vfs = glib::Object::new::<CountingVfs>(&[]);
+        assert!(vfs.is_active());
+        assert_eq!(vfs.file_for_path("/tmp").uri(), "file:///tmp");
+
+        // GVfs adds the schemes registered on it, e.g. `resource`
+        let schemes = vfs.supported_uri_schemes();
+        assert_eq!(schemes[..2], ["file", "counting"]);
+        assert!(!schemes.iter().any(|scheme| scheme == "counting-extra"));
+        assert_eq!(vfs.imp().scheme_lookups.load(Ordering::SeqCst), 1);
+
+        // Registering a scheme makes GVfs query the schemes of the implementation again, which
+        // are taken from the qdata of the instance from then on
+        assert!(vfs.register_uri_scheme("counting-extra", None, None));
+        let schemes = vfs.supported_uri_schemes();
+        assert_eq!(schemes[..2], ["file", "counting"]);
+        assert!(schemes.iter().any(|scheme| scheme == "counting-extra"));
+        assert_eq!(vfs.imp().scheme_lookups.load(Ordering::SeqCst), 1);
+    }
+}
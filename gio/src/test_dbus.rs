@@ -0,0 +1,96 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::sync::{Mutex, MutexGuard};
+
+use crate::TestDBus;
+
+// The bus address is stored in the process environment, so only one test bus can be up at a time
+static SCOPE_LOCK: Mutex<()> = Mutex::new(());
+
+impl TestDBus {
+    // rustdoc-stripper-ignore-next
+    /// Starts the private bus and stops it again when the returned guard is dropped.
+    ///
+    /// While the guard is alive `DBUS_SESSION_BUS_ADDRESS` points to the private bus, so that
+    /// [`bus_get_future`](crate::bus_get_future) and similar functions connect to it for
+    /// [`BusType::Session`](crate::BusType::Session). Guards of different test buses are
+    /// serialized: if another one is alive, this function blocks until it is dropped.
+    ///
+    /// Stopping the bus waits until the session bus connection returned by `bus_get()` was
+    /// disposed, so all references to it have to be dropped before the guard. While waiting only
+    /// the global default main context is iterated: if the connection was created with another
+    /// thread-default main context, the callbacks it schedules there when the bus goes away keep
+    /// it alive and stopping the bus only finishes after a timeout of 30 seconds.
+    ///
+    /// ```no_run
+    /// let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+    /// let _guard = bus.up_scoped();
+    ///
+    /// let connection = gio::bus_get_sync(gio::BusType::Session, gio::Cancellable::NONE).unwrap();
+    /// // ...
+    /// ```
+    #[doc(alias = "g_test_dbus_up")]
+    pub fn up_scoped(&self) -> TestDBusGuard {
+        let lock = SCOPE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        self.up();
+        TestDBusGuard {
+            bus: self.clone(),
+            _lock: lock,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Guard returned by [`TestDBus::up_scoped`].
+///
+/// The private bus is stopped when the guard is dropped.
+#[derive(Debug)]
+#[must_use = "The bus is stopped when the guard is dropped"]
+pub struct TestDBusGuard {
+    bus: TestDBus,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl TestDBusGuard {
+    // rustdoc-stripper-ignore-next
+    /// Returns the test bus.
+    pub fn bus(&self) -> &TestDBus {
+        &self.bus
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the address of the running private bus.
+    pub fn bus_address(&self) -> glib::GString {
+        self.bus.bus_address().unwrap()
+    }
+}
+
+impl Drop for TestDBusGuard {
+    fn drop(&mut self) {
+        self.bus.down();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::{prelude::*, BusType, TestDBus, TestDBusFlags};
+
+    #[test]
+    fn up_scoped() {
+        let bus = TestDBus::new(TestDBusFlags::NONE);
+        assert!(bus.bus_address().is_none());
+
+        let guard = bus.up_scoped();
+        assert_eq!(
+            std::env::var("DBUS_SESSION_BUS_ADDRESS").unwrap(),
+            guard.bus_address().as_str()
+        );
+
+        glib::MainContext::default().block_on(async {
+            let connection = crate::bus_get_future(BusType::Session).await.unwrap();
+            assert!(connection.unique_name().is_some());
+        });
+
+        drop(guard);
+    }
+}
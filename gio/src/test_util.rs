@@ -41,3 +41,25 @@ pub fn run_async_local<T: 'static, Q: FnOnce(Sender<T>, MainLoop) + Send + 'stat
 
     rx.recv().unwrap()
 }
+
+// A temporary directory that is removed together with its contents when dropped
+#[cfg(test)]
+pub struct TempDir(std::path::PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    pub fn new() -> Self {
+        Self(glib::mkdtemp(std::env::temp_dir().join("gio-test-XXXXXX")).unwrap())
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,72 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{prelude::*, translate::*};
+use libc::c_char;
+
+use crate::{File, Vfs};
+
+type FileLookupFunc = Box<dyn Fn(&Vfs, &str) -> Option<File> + Send + Sync + 'static>;
+
+pub trait VfsExtManual: 'static {
+    // rustdoc-stripper-ignore-next
+    /// Registers `uri_func` and `parse_name_func` as the handlers for URIs and parse names of the
+    /// scheme `scheme`.
+    ///
+    /// The handlers are called from whatever thread a [`File`] for the scheme is looked up in,
+    /// e.g. by [`File::for_uri`]. When they return `None` the lookup falls back to the VFS
+    /// implementation.
+    ///
+    /// Returns `false` if the scheme is already registered.
+    #[doc(alias = "g_vfs_register_uri_scheme")]
+    fn register_uri_scheme(
+        &self,
+        scheme: &str,
+        uri_func: Option<FileLookupFunc>,
+        parse_name_func: Option<FileLookupFunc>,
+    ) -> bool;
+}
+
+impl<O: IsA<Vfs>> VfsExtManual for O {
+    fn register_uri_scheme(
+        &self,
+        scheme: &str,
+        uri_func: Option<FileLookupFunc>,
+        parse_name_func: Option<FileLookupFunc>,
+    ) -> bool {
+        unsafe extern "C" fn lookup_func(
+            vfs: *mut ffi::GVfs,
+            identifier: *const c_char,
+            user_data: glib::ffi::gpointer,
+        ) -> *mut ffi::GFile {
+            let vfs = from_glib_borrow(vfs);
+            let identifier: Borrowed<glib::GString> = from_glib_borrow(identifier);
+            let callback = &*(user_data as *mut FileLookupFunc);
+            callback(&vfs, identifier.as_str()).into_glib_ptr()
+        }
+        unsafe extern "C" fn destroy_func(data: glib::ffi::gpointer) {
+            let _callback = Box::from_raw(data as *mut FileLookupFunc);
+        }
+
+        let uri_func_data = uri_func.map(|f| Box::into_raw(Box::new(f)));
+        let parse_name_func_data = parse_name_func.map(|f| Box::into_raw(Box::new(f)));
+        unsafe {
+            let registered: bool = from_glib(ffi::g_vfs_register_uri_scheme(
+                self.as_ref().to_glib_none().0,
+                scheme.to_glib_none().0,
+                uri_func_data.map(|_| lookup_func as _),
+                uri_func_data.map_or(std::ptr::null_mut(), |data| data as _),
+                uri_func_data.map(|_| destroy_func as _),
+                parse_name_func_data.map(|_| lookup_func as _),
+                parse_name_func_data.map_or(std::ptr::null_mut(), |data| data as _),
+                parse_name_func_data.map(|_| destroy_func as _),
+            ));
+            // The callbacks are only taken over if the scheme was registered
+            if !registered {
+                for data in uri_func_data.into_iter().chain(parse_name_func_data) {
+                    destroy_func(data as _);
+                }
+            }
+            registered
+        }
+    }
+}
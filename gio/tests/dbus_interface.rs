@@ -0,0 +1,122 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#[gio::dbus_interface(name = "org.gtk_rs.Test")]
+trait Test {
+    async fn add(&self, a: i32, b: i32) -> Result<i32, glib::Error>;
+
+    fn split(&self, s: String) -> Result<(String, String), glib::Error>;
+
+    #[dbus(name = "Reset")]
+    fn clear(&self) -> Result<(), glib::Error>;
+
+    #[dbus(property)]
+    fn count(&self) -> u32;
+    #[dbus(property)]
+    fn set_count(&self, value: u32);
+
+    #[dbus(property)]
+    fn label(&self) -> String;
+
+    #[dbus(signal)]
+    fn changed(count: u32, label: String);
+}
+
+#[test]
+fn interface_info() {
+    assert_eq!(TestSkeleton::INTERFACE_NAME, "org.gtk_rs.Test");
+
+    let info = TestSkeleton::interface_info();
+    assert_eq!(info.name(), "org.gtk_rs.Test");
+    assert!(info.lookup_method("Add").is_some());
+    assert!(info.lookup_method("Split").is_some());
+    assert!(info.lookup_method("Reset").is_some());
+    assert!(info.lookup_method("Clear").is_none());
+    assert!(info.lookup_property("Count").is_some());
+    assert!(info.lookup_property("Label").is_some());
+    assert!(info.lookup_signal("Changed").is_some());
+}
+
+// Needs dbus-daemon
+#[cfg(unix)]
+mod bus {
+    use std::cell::{Cell, RefCell};
+
+    use futures::channel::oneshot;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter {
+        count: Cell<u32>,
+    }
+
+    #[gio::dbus_interface]
+    impl Test for Counter {
+        async fn add(&self, a: i32, b: i32) -> Result<i32, glib::Error> {
+            Ok(a + b)
+        }
+
+        fn split(&self, s: String) -> Result<(String, String), glib::Error> {
+            s.split_once(' ')
+                .map(|(a, b)| (a.to_owned(), b.to_owned()))
+                .ok_or_else(|| glib::Error::new(gio::IOErrorEnum::InvalidArgument, "No space"))
+        }
+
+        fn clear(&self) -> Result<(), glib::Error> {
+            self.count.set(0);
+            Ok(())
+        }
+
+        fn count(&self) -> u32 {
+            self.count.get()
+        }
+
+        fn set_count(&self, value: u32) {
+            self.count.set(value);
+        }
+
+        fn label(&self) -> String {
+            format!("Count {}", self.count.get())
+        }
+    }
+
+    #[test]
+    fn call() {
+        let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+        let _guard = bus.up_scoped();
+
+        // Stopping the bus iterates the default main context until the connection is disposed,
+        // which dispatches the callbacks of the closed connection
+        glib::MainContext::default().block_on(async {
+            let connection = gio::bus_get_future(gio::BusType::Session).await.unwrap();
+
+            let path = "/org/gtk_rs/Test";
+            let skeleton = TestSkeleton::register(&connection, path, Counter::default()).unwrap();
+            let name = connection.unique_name();
+            let proxy = TestProxy::new(&connection, name.as_deref(), path)
+                .await
+                .unwrap();
+
+            assert_eq!(proxy.add(1, 2).await.unwrap(), 3);
+            assert_eq!(
+                proxy.split(String::from("a b")).await.unwrap(),
+                (String::from("a"), String::from("b"))
+            );
+            assert!(proxy.split(String::from("ab")).await.is_err());
+            assert_eq!(proxy.count(), Some(0));
+            assert_eq!(proxy.label().as_deref(), Some("Count 0"));
+            proxy.set_count(5).await.unwrap();
+            proxy.clear().await.unwrap();
+
+            let (tx, rx) = oneshot::channel();
+            let tx = RefCell::new(Some(tx));
+            proxy.connect_changed(move |_, count, label| {
+                if let Some(tx) = tx.borrow_mut().take() {
+                    tx.send((count, label)).unwrap();
+                }
+            });
+            skeleton.emit_changed(3, String::from("three")).unwrap();
+            assert_eq!(rx.await.unwrap(), (3, String::from("three")));
+        });
+    }
+}
@@ -0,0 +1,300 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#![cfg(unix)]
+
+use std::{cell::Cell, collections::HashMap};
+
+use futures::StreamExt;
+use gio::{prelude::*, subclass::prelude::*};
+use glib::{Variant, VariantTy};
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct Counter {
+        pub count: Cell<i32>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Counter {
+        const NAME: &'static str = "GioTestDBusCounter";
+        type Type = super::Counter;
+        type ParentType = gio::DBusInterfaceSkeleton;
+    }
+
+    impl ObjectImpl for Counter {}
+
+    impl DBusInterfaceSkeletonImpl for Counter {
+        fn info(&self) -> gio::DBusInterfaceInfo {
+            gio::DBusInterfaceInfo::builder("org.gtk_rs.Counter")
+                .method(
+                    gio::DBusMethodInfo::builder("Add")
+                        .in_arg::<i32>("value")
+                        .out_arg::<i32>("count")
+                        .build(),
+                )
+                .property(gio::DBusPropertyInfo::builder::<i32>("Count").build())
+                .build()
+        }
+
+        fn method_call(
+            &self,
+            _sender: Option<&str>,
+            method_name: &str,
+            parameters: Variant,
+            invocation: gio::DBusMethodInvocation,
+        ) {
+            match method_name {
+                "Add" => {
+                    let (value,) = parameters.get::<(i32,)>().unwrap();
+                    self.count.set(self.count.get() + value);
+                    self.obj()
+                        .emit_properties_changed(&[("Count", self.count.get().to_variant())], &[])
+                        .unwrap();
+                    invocation.return_value(Some(&(self.count.get(),).to_variant()));
+                }
+                _ => self.parent_method_call(_sender, method_name, parameters, invocation),
+            }
+        }
+
+        fn dbus_property(
+            &self,
+            sender: Option<&str>,
+            property_name: &str,
+        ) -> Result<Variant, glib::Error> {
+            match property_name {
+                "Count" => Ok(self.count.get().to_variant()),
+                _ => self.parent_dbus_property(sender, property_name),
+            }
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct Counter(ObjectSubclass<imp::Counter>)
+        @extends gio::DBusInterfaceSkeleton,
+        @implements gio::DBusInterface;
+}
+
+pub trait CounterImpl: DBusInterfaceSkeletonImpl {}
+
+unsafe impl<T: CounterImpl> IsSubclassable<T> for Counter {}
+
+mod imp_doubler {
+    use super::*;
+
+    // Adds a method and leaves everything else to `Counter`
+    #[derive(Default)]
+    pub struct Doubler;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Doubler {
+        const NAME: &'static str = "GioTestDBusDoubler";
+        type Type = super::Doubler;
+        type ParentType = super::Counter;
+    }
+
+    impl ObjectImpl for Doubler {}
+
+    impl DBusInterfaceSkeletonImpl for Doubler {
+        fn info(&self) -> gio::DBusInterfaceInfo {
+            gio::DBusInterfaceInfo::builder("org.gtk_rs.Counter")
+                .method(
+                    gio::DBusMethodInfo::builder("Add")
+                        .in_arg::<i32>("value")
+                        .out_arg::<i32>("count")
+                        .build(),
+                )
+                .method(
+                    gio::DBusMethodInfo::builder("Double")
+                        .out_arg::<i32>("count")
+                        .build(),
+                )
+                .method(gio::DBusMethodInfo::builder("Reset").build())
+                .property(gio::DBusPropertyInfo::builder::<i32>("Count").build())
+                .build()
+        }
+
+        fn method_call(
+            &self,
+            sender: Option<&str>,
+            method_name: &str,
+            parameters: Variant,
+            invocation: gio::DBusMethodInvocation,
+        ) {
+            match method_name {
+                "Double" => {
+                    let obj = self.obj();
+                    let count = &obj.upcast_ref::<Counter>().imp().count;
+                    count.set(count.get() * 2);
+                    invocation.return_value(Some(&(count.get(),).to_variant()));
+                }
+                _ => self.parent_method_call(sender, method_name, parameters, invocation),
+            }
+        }
+    }
+
+    impl CounterImpl for Doubler {}
+}
+
+glib::wrapper! {
+    pub struct Doubler(ObjectSubclass<imp_doubler::Doubler>)
+        @extends Counter, gio::DBusInterfaceSkeleton,
+        @implements gio::DBusInterface;
+}
+
+#[test]
+fn interface_skeleton() {
+    let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+
+    let _guard = bus.up_scoped();
+
+    // Stopping the bus iterates the default main context until the connection is disposed,
+    // which dispatches the callbacks of the closed connection
+    glib::MainContext::default().block_on(async {
+        let connection = gio::bus_get_future(gio::BusType::Session).await.unwrap();
+        let name = connection.unique_name().unwrap();
+
+        let counter = glib::Object::new::<Counter>(&[]);
+        counter.export(&connection, "/org/gtk_rs/Counter").unwrap();
+        assert_eq!(
+            counter.object_path().as_deref(),
+            Some("/org/gtk_rs/Counter")
+        );
+        assert_eq!(
+            DBusInterfaceSkeletonExt::info(&counter).name(),
+            "org.gtk_rs.Counter"
+        );
+
+        let mut changes = connection
+            .receive_signal::<(String, HashMap<String, Variant>, Vec<String>)>(
+                Some(&name),
+                Some("org.freedesktop.DBus.Properties"),
+                Some("PropertiesChanged"),
+                Some("/org/gtk_rs/Counter"),
+                None,
+                gio::DBusSignalFlags::NONE,
+            );
+
+        let reply = connection
+            .call_future(
+                Some(&name),
+                "/org/gtk_rs/Counter",
+                "org.gtk_rs.Counter",
+                "Add",
+                Some(&(5i32,).to_variant()),
+                Some(VariantTy::new("(i)").unwrap()),
+                gio::DBusCallFlags::NONE,
+                -1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(reply.get::<(i32,)>(), Some((5,)));
+
+        let signal = changes.next().await.unwrap();
+        let (interface_name, changed, invalidated) = signal.parameters;
+        assert_eq!(interface_name, "org.gtk_rs.Counter");
+        assert_eq!(changed["Count"].get::<i32>(), Some(5));
+        assert!(invalidated.is_empty());
+
+        let reply = connection
+            .call_future(
+                Some(&name),
+                "/org/gtk_rs/Counter",
+                "org.freedesktop.DBus.Properties",
+                "Get",
+                Some(&("org.gtk_rs.Counter", "Count").to_variant()),
+                Some(VariantTy::new("(v)").unwrap()),
+                gio::DBusCallFlags::NONE,
+                -1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            reply.child_value(0).as_variant().unwrap().get::<i32>(),
+            Some(5)
+        );
+
+        let properties = glib::VariantDict::new(Some(&counter.properties()));
+        assert_eq!(properties.lookup::<i32>("Count").unwrap(), Some(5));
+
+        // Read-only properties are rejected before reaching the implementation
+        let res = connection
+            .call_future(
+                Some(&name),
+                "/org/gtk_rs/Counter",
+                "org.freedesktop.DBus.Properties",
+                "Set",
+                Some(&("org.gtk_rs.Counter", "Count", 1i32.to_variant()).to_variant()),
+                None,
+                gio::DBusCallFlags::NONE,
+                -1,
+            )
+            .await;
+        assert!(res.is_err());
+
+        counter.unexport();
+        assert!(counter.object_path().is_none());
+    });
+}
+
+#[test]
+fn chain_up() {
+    let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+    let _guard = bus.up_scoped();
+
+    glib::MainContext::default().block_on(async {
+        let connection = gio::bus_get_future(gio::BusType::Session).await.unwrap();
+        let name = connection.unique_name().unwrap();
+
+        let doubler = glib::Object::new::<Doubler>(&[]);
+        doubler.export(&connection, "/org/gtk_rs/Doubler").unwrap();
+
+        let call = |method: &'static str, parameters: Option<Variant>| {
+            connection.call_future(
+                Some(&name),
+                "/org/gtk_rs/Doubler",
+                "org.gtk_rs.Counter",
+                method,
+                parameters.as_ref(),
+                None,
+                gio::DBusCallFlags::NONE,
+                -1,
+            )
+        };
+
+        // Handled by the parent class
+        let reply = call("Add", Some((3i32,).to_variant())).await.unwrap();
+        assert_eq!(reply.get::<(i32,)>(), Some((3,)));
+        // Handled by the subclass
+        let reply = call("Double", None).await.unwrap();
+        assert_eq!(reply.get::<(i32,)>(), Some((6,)));
+        // Unknown to both, rejected by the default implementation
+        let err = call("Reset", None).await.unwrap_err();
+        assert!(err.message().contains("Unknown method Reset"), "{err}");
+
+        // Properties are read by the parent class
+        let reply = connection
+            .call_future(
+                Some(&name),
+                "/org/gtk_rs/Doubler",
+                "org.freedesktop.DBus.Properties",
+                "Get",
+                Some(&("org.gtk_rs.Counter", "Count").to_variant()),
+                Some(VariantTy::new("(v)").unwrap()),
+                gio::DBusCallFlags::NONE,
+                -1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            reply.child_value(0).as_variant().unwrap().get::<i32>(),
+            Some(6)
+        );
+        let properties = glib::VariantDict::new(Some(&doubler.properties()));
+        assert_eq!(properties.lookup::<i32>("Count").unwrap(), Some(6));
+
+        doubler.unexport();
+    });
+}
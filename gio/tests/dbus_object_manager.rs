@@ -0,0 +1,118 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#![cfg(unix)]
+
+use futures::{FutureExt, StreamExt};
+use gio::{prelude::*, subclass::prelude::*};
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct Empty;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Empty {
+        const NAME: &'static str = "GioTestDBusEmpty";
+        type Type = super::Empty;
+        type ParentType = gio::DBusInterfaceSkeleton;
+    }
+
+    impl ObjectImpl for Empty {}
+
+    impl DBusInterfaceSkeletonImpl for Empty {
+        fn info(&self) -> gio::DBusInterfaceInfo {
+            gio::DBusInterfaceInfo::builder("org.gtk_rs.Empty").build()
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct Empty(ObjectSubclass<imp::Empty>)
+        @extends gio::DBusInterfaceSkeleton,
+        @implements gio::DBusInterface;
+}
+
+#[test]
+fn object_manager() {
+    let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+    let _guard = bus.up_scoped();
+
+    // Stopping the bus iterates the default main context until the connection is disposed,
+    // which dispatches the callbacks of the closed connection
+    glib::MainContext::default().block_on(async {
+        let connection = gio::bus_get_future(gio::BusType::Session).await.unwrap();
+        let name = connection.unique_name().unwrap();
+
+        let server = gio::DBusObjectManagerServer::new("/org/gtk_rs/Test");
+        let mut server_events = server.receive_events();
+        server.set_connection(Some(&connection));
+        assert_eq!(server.connection().as_ref(), Some(&connection));
+
+        let client = gio::DBusObjectManagerClient::new_future(
+            &connection,
+            gio::DBusObjectManagerClientFlags::NONE,
+            Some(&name),
+            "/org/gtk_rs/Test",
+            Some(Box::new(
+                |_: &gio::DBusObjectManagerClient, _: &str, interface_name: Option<&str>| {
+                    if interface_name.is_some() {
+                        gio::DBusProxy::static_type()
+                    } else {
+                        gio::DBusObjectProxy::static_type()
+                    }
+                },
+            )),
+        )
+        .await
+        .unwrap();
+        assert_eq!(client.name(), name);
+        assert_eq!(client.name_owner().as_ref(), Some(&name));
+        assert_eq!(client.object_path(), "/org/gtk_rs/Test");
+        assert!(client.objects().is_empty());
+        let mut client_events = client.receive_events();
+
+        // Objects without interfaces of their own are announced to clients too
+        let object = gio::DBusObjectSkeleton::new("/org/gtk_rs/Test/Object");
+        server.export(&object);
+        assert!(server.is_exported(&object));
+        assert_eq!(server.objects().len(), 1);
+        match client_events.next().await.unwrap() {
+            gio::DBusObjectManagerEvent::ObjectAdded(added) => {
+                assert_eq!(added.object_path(), "/org/gtk_rs/Test/Object");
+            }
+            event => panic!("Unexpected event {event:?}"),
+        }
+        let objects = client.objects();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].object_path(), "/org/gtk_rs/Test/Object");
+
+        object.add_interface(&glib::Object::new::<Empty>(&[]));
+        match client_events.next().await.unwrap() {
+            gio::DBusObjectManagerEvent::InterfaceAdded(object, interface) => {
+                assert_eq!(object.object_path(), "/org/gtk_rs/Test/Object");
+                let proxy = interface.downcast::<gio::DBusProxy>().unwrap();
+                assert_eq!(proxy.interface_name(), "org.gtk_rs.Empty");
+            }
+            event => panic!("Unexpected event {event:?}"),
+        }
+
+        assert!(server.unexport("/org/gtk_rs/Test/Object"));
+        assert!(!server.is_exported(&object));
+        match client_events.next().await.unwrap() {
+            gio::DBusObjectManagerEvent::ObjectRemoved(removed) => {
+                assert_eq!(removed.object_path(), "/org/gtk_rs/Test/Object");
+            }
+            event => panic!("Unexpected event {event:?}"),
+        }
+        assert!(client.objects().is_empty());
+
+        // The server doesn't emit any signals, they would have been received synchronously
+        assert!(server_events.next().now_or_never().is_none());
+
+        // Neither the stream nor the client leak a reference
+        let weak = client.downgrade();
+        drop((client, client_events));
+        assert!(weak.upgrade().is_none());
+    });
+}
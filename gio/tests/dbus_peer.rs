@@ -0,0 +1,158 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#![cfg(unix)]
+
+use std::sync::{Arc, Mutex};
+
+use futures::StreamExt;
+use gio::prelude::*;
+
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="org.gtk_rs.Peer">
+    <method name="Hello">
+      <arg type="s" name="name" direction="in"/>
+      <arg type="s" name="greeting" direction="out"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+#[test]
+fn new_pair() {
+    let c = glib::MainContext::new();
+    c.with_thread_default(|| {
+        c.block_on(async {
+            let (server, client) = gio::DBusConnection::new_pair_future().await.unwrap();
+            assert!(server.unique_name().is_none());
+            assert!(client.unique_name().is_none());
+
+            let info = gio::DBusNodeInfo::for_xml(INTERFACE_XML)
+                .unwrap()
+                .lookup_interface("org.gtk_rs.Peer")
+                .unwrap();
+            let id = server
+                .register_object_async(
+                    "/org/gtk_rs/Peer",
+                    &info,
+                    |_, sender, _, _, method_name, parameters, _| {
+                        assert_eq!(sender, "");
+                        assert_eq!(method_name, "Hello");
+                        async move {
+                            let (name,) = parameters.get::<(String,)>().unwrap();
+                            Ok((format!("Hello {name}"),).to_variant())
+                        }
+                    },
+                    |_, _, _, _, _| unreachable!(),
+                    |_, _, _, _, _, _| unreachable!(),
+                )
+                .unwrap();
+
+            let reply = client
+                .call_future(
+                    None,
+                    "/org/gtk_rs/Peer",
+                    "org.gtk_rs.Peer",
+                    "Hello",
+                    Some(&("peer",).to_variant()),
+                    None,
+                    gio::DBusCallFlags::NONE,
+                    -1,
+                )
+                .await
+                .unwrap();
+            assert_eq!(reply.get::<(String,)>().unwrap().0, "Hello peer");
+
+            server.unregister_object(id).unwrap();
+        })
+    })
+    .unwrap();
+}
+
+#[test]
+fn invocation_freed() {
+    let c = glib::MainContext::new();
+    c.with_thread_default(|| {
+        c.block_on(async {
+            let (server, client) = gio::DBusConnection::new_pair_future().await.unwrap();
+
+            let info = gio::DBusNodeInfo::for_xml(INTERFACE_XML)
+                .unwrap()
+                .lookup_interface("org.gtk_rs.Peer")
+                .unwrap();
+            let invocation = Arc::new(Mutex::new(None));
+            let id = server
+                .register_object(
+                    "/org/gtk_rs/Peer",
+                    &info,
+                    {
+                        let invocation = invocation.clone();
+                        move |_, _, _, _, _, _, inv: gio::DBusMethodInvocation| {
+                            *invocation.lock().unwrap() =
+                                Some(glib::SendWeakRef::from(inv.downgrade()));
+                            inv.return_value(Some(&("Hello",).to_variant()));
+                        }
+                    },
+                    |_, _, _, _, _| unreachable!(),
+                    |_, _, _, _, _, _| unreachable!(),
+                )
+                .unwrap();
+
+            client
+                .call_future(
+                    None,
+                    "/org/gtk_rs/Peer",
+                    "org.gtk_rs.Peer",
+                    "Hello",
+                    Some(&("peer",).to_variant()),
+                    None,
+                    gio::DBusCallFlags::NONE,
+                    -1,
+                )
+                .await
+                .unwrap();
+
+            // Returning the value consumed the last reference to the invocation
+            let invocation = invocation.lock().unwrap().take().unwrap();
+            assert!(invocation.upgrade().is_none());
+
+            server.unregister_object(id).unwrap();
+        })
+    })
+    .unwrap();
+}
+
+#[test]
+fn server_incoming() {
+    let c = glib::MainContext::new();
+    c.with_thread_default(|| {
+        c.block_on(async {
+            let guid = gio::dbus_generate_guid();
+            let server = gio::DBusServer::new_sync(
+                "unix:tmpdir=/tmp",
+                gio::DBusServerFlags::NONE,
+                &guid,
+                None,
+                gio::Cancellable::NONE,
+            )
+            .unwrap();
+            let mut incoming = server.incoming();
+            assert!(server.is_active());
+
+            let client = gio::DBusConnection::for_address_future(
+                &server.client_address(),
+                gio::DBusConnectionFlags::AUTHENTICATION_CLIENT,
+                None,
+            );
+            let (client, connection) = futures::join!(client, incoming.next());
+            let client = client.unwrap();
+            let connection = connection.unwrap();
+            assert_eq!(client.guid(), guid);
+            assert_eq!(connection.guid(), guid);
+
+            drop(incoming);
+            server.stop();
+        })
+    })
+    .unwrap();
+}
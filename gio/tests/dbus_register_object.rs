@@ -0,0 +1,105 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#![cfg(unix)]
+
+use futures::{channel::mpsc, StreamExt};
+use gio::prelude::*;
+
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="org.gtk_rs.Slow">
+    <method name="Wait"/>
+  </interface>
+</node>
+"#;
+
+// Notifies the test once the handler future is dropped
+struct DropNotify(mpsc::UnboundedSender<()>);
+
+impl Drop for DropNotify {
+    fn drop(&mut self) {
+        let _ = self.0.unbounded_send(());
+    }
+}
+
+#[test]
+fn caller_vanished() {
+    let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+    let guard = bus.up_scoped();
+
+    // Stopping the bus iterates the default main context until the connection is disposed,
+    // which dispatches the callbacks of the closed connection
+    glib::MainContext::default().block_on(async {
+        let server = gio::bus_get_future(gio::BusType::Session).await.unwrap();
+        let client = gio::DBusConnection::for_address_future(
+            &guard.bus_address(),
+            gio::DBusConnectionFlags::AUTHENTICATION_CLIENT
+                | gio::DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let info = gio::DBusNodeInfo::for_xml(INTERFACE_XML)
+            .unwrap()
+            .lookup_interface("org.gtk_rs.Slow")
+            .unwrap();
+        let (started_tx, mut started) = mpsc::unbounded();
+        let (dropped_tx, mut dropped) = mpsc::unbounded();
+        let id = server
+            .register_object_async(
+                "/org/gtk_rs/Slow",
+                &info,
+                move |_, _, _, _, _, _, cancellable| {
+                    let _ = started_tx.unbounded_send(cancellable);
+                    let notify = DropNotify(dropped_tx.clone());
+                    async move {
+                        let _notify = notify;
+                        futures::future::pending::<()>().await;
+                        Ok(().to_variant())
+                    }
+                },
+                |_, _, _, _, _| unreachable!(),
+                |_, _, _, _, _, _| unreachable!(),
+            )
+            .unwrap();
+
+        let name = server.unique_name().unwrap();
+        let calls = (0..2)
+            .map(|_| {
+                client.call_future(
+                    Some(&name),
+                    "/org/gtk_rs/Slow",
+                    "org.gtk_rs.Slow",
+                    "Wait",
+                    None,
+                    None,
+                    gio::DBusCallFlags::NONE,
+                    -1,
+                )
+            })
+            .collect::<Vec<_>>();
+        let calls =
+            glib::MainContext::ref_thread_default().spawn_local(futures::future::join_all(calls));
+
+        let first = started.next().await.unwrap();
+        let second = started.next().await.unwrap();
+        assert!(!first.is_cancelled());
+        assert!(!second.is_cancelled());
+
+        // Closing the caller's connection mid-call drops both handler futures
+        client.close_future().await.unwrap();
+        dropped.next().await.unwrap();
+        dropped.next().await.unwrap();
+        assert!(first.is_cancelled());
+        assert!(second.is_cancelled());
+
+        for res in calls.await.unwrap() {
+            assert!(res.is_err());
+        }
+
+        server.unregister_object(id).unwrap();
+    });
+
+    drop(guard);
+}
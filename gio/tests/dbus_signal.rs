@@ -0,0 +1,78 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#![cfg(unix)]
+
+use futures::StreamExt;
+use gio::prelude::*;
+
+#[test]
+fn receive_signal() {
+    let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+    let _guard = bus.up_scoped();
+
+    // Stopping the bus iterates the default main context until the connection is disposed,
+    // which dispatches the callbacks of the closed connection
+    glib::MainContext::default().block_on(async {
+        let connection = gio::bus_get_future(gio::BusType::Session).await.unwrap();
+        let name = connection.unique_name().unwrap();
+
+        let mut signals = connection.receive_signal::<(String, u32)>(
+            Some(&name),
+            Some("org.gtk_rs.Test"),
+            Some("Changed"),
+            None,
+            None,
+            gio::DBusSignalFlags::NONE,
+        );
+
+        // Skipped because of the wrong type
+        connection
+            .emit_signal(
+                None,
+                "/org/gtk_rs/Test",
+                "org.gtk_rs.Test",
+                "Changed",
+                Some(&(1u32,).to_variant()),
+            )
+            .unwrap();
+        connection
+            .emit_signal(
+                None,
+                "/org/gtk_rs/Test",
+                "org.gtk_rs.Test",
+                "Changed",
+                Some(&("count", 2u32).to_variant()),
+            )
+            .unwrap();
+
+        let signal = signals.next().await.unwrap();
+        assert_eq!(signal.sender, name.as_str());
+        assert_eq!(signal.object_path, "/org/gtk_rs/Test");
+        assert_eq!(signal.interface_name, "org.gtk_rs.Test");
+        assert_eq!(signal.signal_name, "Changed");
+        assert_eq!(signal.parameters, (String::from("count"), 2));
+
+        let proxy = gio::DBusProxy::new_future(
+            &connection,
+            gio::DBusProxyFlags::DO_NOT_LOAD_PROPERTIES,
+            None,
+            Some(&name),
+            "/org/gtk_rs/Test",
+            "org.gtk_rs.Test",
+        )
+        .await
+        .unwrap();
+        let mut signals = proxy.receive_signal::<(String, u32)>("Changed");
+
+        connection
+            .emit_signal(
+                None,
+                "/org/gtk_rs/Test",
+                "org.gtk_rs.Test",
+                "Changed",
+                Some(&("count", 3u32).to_variant()),
+            )
+            .unwrap();
+        assert_eq!(signals.next().await.unwrap(), (String::from("count"), 3));
+    });
+}
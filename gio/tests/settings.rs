@@ -0,0 +1,221 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{env::set_var, process::Command, str::from_utf8, sync::Once};
+
+use futures_util::StreamExt;
+use gio::prelude::*;
+
+#[gio::settings(id = "com.github.gtk-rs.test")]
+struct TestSettings {
+    test_string: String,
+    #[settings(key = "test-bool")]
+    enabled: bool,
+    test_int: i32,
+}
+
+static INIT: Once = Once::new();
+
+fn set_env() {
+    INIT.call_once(|| {
+        let output = Command::new("glib-compile-schemas")
+            .args([
+                &format!("{}/tests", env!("CARGO_MANIFEST_DIR")),
+                "--targetdir",
+                env!("OUT_DIR"),
+            ])
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "glib-compile-schemas failed: {}",
+            from_utf8(&output.stderr).unwrap()
+        );
+
+        set_var("GSETTINGS_SCHEMA_DIR", env!("OUT_DIR"));
+        set_var("GSETTINGS_BACKEND", "memory");
+    });
+}
+
+#[test]
+fn typed_settings() {
+    set_env();
+
+    let settings = TestSettings::new();
+    assert_eq!(TestSettings::SCHEMA_ID, "com.github.gtk-rs.test");
+    assert_eq!(settings.test_string(), "Good");
+    assert!(settings.enabled());
+    assert_eq!(settings.test_int(), 42);
+
+    settings.set_test_int(7).unwrap();
+    assert_eq!(settings.test_int(), 7);
+    assert_eq!(settings.settings().int("test-int"), 7);
+    settings.reset_test_int();
+    assert_eq!(settings.test_int(), 42);
+
+    // Change notifications are dispatched to the thread-default main context of the creation
+    let c = glib::MainContext::new();
+    c.with_thread_default(|| {
+        let settings = TestSettings::new();
+        let mut changes = settings.receive_enabled_changes();
+        settings.set_enabled(false).unwrap();
+        settings.set_test_string("Bad".into()).unwrap();
+        settings.set_enabled(true).unwrap();
+        c.block_on(async {
+            assert_eq!(changes.next().await, Some(false));
+            assert_eq!(changes.next().await, Some(true));
+        });
+    })
+    .unwrap();
+    assert_eq!(settings.test_string(), "Bad");
+}
+
+#[test]
+#[should_panic(expected = "Settings for the wrong schema")]
+fn wrong_schema() {
+    set_env();
+
+    #[gio::settings(id = "com.github.gtk-rs.other")]
+    struct OtherSettings {}
+    OtherSettings::from_settings(gio::Settings::new("com.github.gtk-rs.test"));
+}
+
+mod imp {
+    use std::{collections::BTreeMap, sync::Mutex};
+
+    use gio::{prelude::*, subclass::prelude::*};
+    use glib::{Variant, VariantTy};
+
+    // Stores the user values in a map, the test-bool key is read-only
+    #[derive(Default)]
+    pub struct MapBackend {
+        pub values: Mutex<BTreeMap<String, Variant>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for MapBackend {
+        const NAME: &'static str = "GioTestMapBackend";
+        type Type = super::MapBackend;
+        type ParentType = gio::SettingsBackend;
+    }
+
+    impl ObjectImpl for MapBackend {}
+
+    impl SettingsBackendImpl for MapBackend {
+        fn read(
+            &self,
+            key: &str,
+            expected_type: &VariantTy,
+            default_value: bool,
+        ) -> Option<Variant> {
+            if default_value {
+                return None;
+            }
+            self.values
+                .lock()
+                .unwrap()
+                .get(key)
+                .filter(|value| value.type_() == expected_type)
+                .cloned()
+        }
+
+        fn write(
+            &self,
+            key: &str,
+            value: &Variant,
+            origin_tag: Option<gio::SettingsBackendOriginTag>,
+        ) -> bool {
+            if !self.writable(key) {
+                return false;
+            }
+            self.values
+                .lock()
+                .unwrap()
+                .insert(key.to_owned(), value.clone());
+            self.obj().changed(key, origin_tag);
+            true
+        }
+
+        fn write_tree(
+            &self,
+            tree: &BTreeMap<String, Option<Variant>>,
+            origin_tag: Option<gio::SettingsBackendOriginTag>,
+        ) -> bool {
+            if !tree.keys().all(|key| self.writable(key)) {
+                return false;
+            }
+            let mut values = self.values.lock().unwrap();
+            for (key, value) in tree {
+                match value {
+                    Some(value) => values.insert(key.clone(), value.clone()),
+                    None => values.remove(key),
+                };
+            }
+            drop(values);
+            self.obj().changed_tree(tree, origin_tag);
+            true
+        }
+
+        fn reset(&self, key: &str, origin_tag: Option<gio::SettingsBackendOriginTag>) {
+            self.values.lock().unwrap().remove(key);
+            self.obj().changed(key, origin_tag);
+        }
+
+        fn writable(&self, key: &str) -> bool {
+            !key.ends_with("/test-bool")
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct MapBackend(ObjectSubclass<imp::MapBackend>) @extends gio::SettingsBackend;
+}
+
+#[test]
+fn settings_backend_subclass() {
+    use gio::subclass::prelude::*;
+
+    set_env();
+
+    let c = glib::MainContext::new();
+    c.with_thread_default(|| {
+        let backend = glib::Object::new::<MapBackend>(&[]);
+        let values = || backend.imp().values.lock().unwrap().clone();
+        let settings = gio::Settings::with_backend("com.github.gtk-rs.test", &backend);
+
+        assert_eq!(settings.string("test-string"), "Good");
+        settings.set_string("test-string", "Bad").unwrap();
+        assert_eq!(settings.string("test-string"), "Bad");
+        assert_eq!(
+            values()["/com/github/gtk-rs/test-string"].str(),
+            Some("Bad")
+        );
+
+        assert!(!settings.is_writable("test-bool"));
+        assert!(settings.set_boolean("test-bool", false).is_err());
+        assert!(settings.boolean("test-bool"));
+
+        // Delayed changes are written as a tree
+        settings.delay();
+        settings.set_int("test-int", 1).unwrap();
+        settings.reset("test-string");
+        assert!(values().contains_key("/com/github/gtk-rs/test-string"));
+        settings.apply();
+        assert_eq!(values()["/com/github/gtk-rs/test-int"].get(), Some(1));
+        assert!(!values().contains_key("/com/github/gtk-rs/test-string"));
+        assert_eq!(settings.string("test-string"), "Good");
+
+        // Changes made by the backend itself are seen by the typed wrapper too
+        let typed = TestSettings::from_settings(settings.clone());
+        let mut changes = typed.receive_test_int_changes();
+        backend.imp().values.lock().unwrap().insert(
+            "/com/github/gtk-rs/test-int".into(),
+            glib::ToVariant::to_variant(&5i32),
+        );
+        backend.keys_changed("/com/github/gtk-rs/", &["test-int"], None);
+        c.block_on(async {
+            assert_eq!(changes.next().await, Some(5));
+        });
+        assert_eq!(typed.test_int(), 5);
+    })
+    .unwrap();
+}
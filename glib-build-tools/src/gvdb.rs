@@ -0,0 +1,136 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Writer for GVDB files, the hash table format of compiled GResource bundles. Everything is
+// written in little endian without bloom filter, the same as `glib-compile-resources` does.
+
+pub(crate) enum Value {
+    // A serialized GVariant of type `v` in normal form
+    Variant(Vec<u8>),
+    // Indices of the child items
+    List(Vec<usize>),
+}
+
+pub(crate) struct Item {
+    // The full key, the parent's key is a prefix of it
+    pub key: String,
+    pub parent: Option<usize>,
+    pub value: Value,
+}
+
+const HEADER_SIZE: usize = 24;
+const HASH_HEADER_SIZE: usize = 8;
+const HASH_ITEM_SIZE: usize = 24;
+const BLOOM_SHIFT: u32 = 5;
+
+fn djb_hash(key: &str) -> u32 {
+    key.bytes().fold(5381u32, |hash, byte| {
+        // Bytes are treated as signed chars
+        hash.wrapping_mul(33).wrapping_add(byte as i8 as u32)
+    })
+}
+
+fn align(out: &mut Vec<u8>, alignment: usize) {
+    let len = (out.len() + alignment - 1) / alignment * alignment;
+    out.resize(len, 0);
+}
+
+fn offset(offset: usize) -> Result<[u8; 4], String> {
+    u32::try_from(offset)
+        .map(u32::to_le_bytes)
+        .map_err(|_| String::from("File too large"))
+}
+
+fn pointer(start: usize, end: usize) -> Result<[u8; 8], String> {
+    let mut p = [0; 8];
+    p[..4].copy_from_slice(&offset(start)?);
+    p[4..].copy_from_slice(&offset(end)?);
+    Ok(p)
+}
+
+// Write a GVDB file with a single hash table containing `items`.
+pub(crate) fn write(items: &[Item]) -> Result<Vec<u8>, String> {
+    let n_items = items.len();
+    let n_buckets = n_items;
+
+    // Items are stored ordered by bucket
+    let hashes = items
+        .iter()
+        .map(|item| djb_hash(&item.key))
+        .collect::<Vec<_>>();
+    let mut order = (0..n_items).collect::<Vec<_>>();
+    order.sort_by_key(|&i| hashes[i] as usize % n_buckets);
+    let mut index = vec![0; n_items];
+    for (new, &old) in order.iter().enumerate() {
+        index[old] = new;
+    }
+
+    let table_start = HEADER_SIZE;
+    let buckets_start = table_start + HASH_HEADER_SIZE;
+    let items_start = buckets_start + 4 * n_buckets;
+    let table_end = items_start + HASH_ITEM_SIZE * n_items;
+
+    let mut out = Vec::with_capacity(table_end);
+    out.extend_from_slice(b"GVariant");
+    out.extend_from_slice(&0u32.to_le_bytes()); // version
+    out.extend_from_slice(&0u32.to_le_bytes()); // options
+    out.extend_from_slice(&pointer(table_start, table_end)?);
+
+    out.extend_from_slice(&(BLOOM_SHIFT << 27).to_le_bytes());
+    out.extend_from_slice(&(n_buckets as u32).to_le_bytes());
+    let mut bucket_start = 0;
+    for bucket in 0..n_buckets {
+        while bucket_start < n_items && hashes[order[bucket_start]] as usize % n_buckets < bucket {
+            bucket_start += 1;
+        }
+        out.extend_from_slice(&(bucket_start as u32).to_le_bytes());
+    }
+    out.resize(table_end, 0);
+
+    for (new, &old) in order.iter().enumerate() {
+        let item = &items[old];
+        let basename = match item.parent {
+            Some(parent) => item
+                .key
+                .strip_prefix(items[parent].key.as_str())
+                .ok_or_else(|| format!("{} is not a prefix of {}", items[parent].key, item.key))?,
+            None => &item.key,
+        };
+        let key_size =
+            u16::try_from(basename.len()).map_err(|_| format!("Key {} is too long", item.key))?;
+
+        let key_start = out.len();
+        out.extend_from_slice(basename.as_bytes());
+
+        let (type_, value) = match &item.value {
+            Value::Variant(data) => {
+                align(&mut out, 8);
+                let start = out.len();
+                out.extend_from_slice(data);
+                (b'v', pointer(start, out.len())?)
+            }
+            Value::List(children) => {
+                align(&mut out, 4);
+                let start = out.len();
+                for &child in children {
+                    out.extend_from_slice(&(index[child] as u32).to_le_bytes());
+                }
+                (b'L', pointer(start, out.len())?)
+            }
+        };
+
+        let mut entry = Vec::with_capacity(HASH_ITEM_SIZE);
+        entry.extend_from_slice(&hashes[old].to_le_bytes());
+        let parent = item.parent.map_or(u32::MAX, |parent| index[parent] as u32);
+        entry.extend_from_slice(&parent.to_le_bytes());
+        entry.extend_from_slice(&offset(key_start)?);
+        entry.extend_from_slice(&key_size.to_le_bytes());
+        entry.push(type_);
+        entry.push(0);
+        entry.extend_from_slice(&value);
+
+        let entry_start = items_start + HASH_ITEM_SIZE * new;
+        out[entry_start..entry_start + HASH_ITEM_SIZE].copy_from_slice(&entry);
+    }
+
+    Ok(out)
+}
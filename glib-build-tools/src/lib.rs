@@ -2,7 +2,17 @@
 
 #![doc = include_str!("../README.md")]
 
-use std::{env, path::Path, process::Command};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+mod gvdb;
+mod resources;
+mod schemas;
+mod xml;
+mod zlib;
 
 // rustdoc-stripper-ignore-next
 /// Call to run `glib-compile-resources` to generate compiled gresources to embed
@@ -46,3 +56,184 @@ pub fn compile_resources<P: AsRef<Path>>(source_dir: P, gresource: &str, target:
         println!("cargo:rerun-if-changed={dep}");
     }
 }
+
+// rustdoc-stripper-ignore-next
+/// Compile gresources like [`compile_resources`], but without running `glib-compile-resources`.
+/// `target` is relative to `OUT_DIR`.
+///
+/// The `compressed` attribute and the `xml-stripblanks` and `json-stripblanks` preprocessing
+/// options are supported, other preprocessing options result in an error.
+///
+/// ```no_run
+/// glib_build_tools::try_compile_resources(
+///     "resources",
+///     "resources/resources.gresource.xml",
+///     "compiled.gresource",
+/// )
+/// .expect("Failed to compile resources");
+/// ```
+pub fn try_compile_resources<P: AsRef<Path>>(
+    source_dir: P,
+    gresource: &str,
+    target: &str,
+) -> io::Result<()> {
+    let out_dir = env::var_os("OUT_DIR")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "OUT_DIR is not set"))?;
+
+    let (data, dependencies) = resources::compile(source_dir.as_ref(), Path::new(gresource))?;
+    fs::write(Path::new(&out_dir).join(target), data)?;
+
+    for dep in dependencies {
+        println!("cargo:rerun-if-changed={}", dep.display());
+    }
+    Ok(())
+}
+
+// rustdoc-stripper-ignore-next
+/// Compile the gresources described by `gresource` into a bundle that can be loaded with
+/// [`gio::Resource::from_data`], without running `glib-compile-resources`.
+pub fn compile_resources_to_bytes<P: AsRef<Path>, Q: AsRef<Path>>(
+    source_dir: P,
+    gresource: Q,
+) -> io::Result<Vec<u8>> {
+    resources::compile(source_dir.as_ref(), gresource.as_ref()).map(|(data, _)| data)
+}
+
+// rustdoc-stripper-ignore-next
+/// Generate a gresource XML manifest listing all files below `source_dir` with `prefix`.
+///
+/// ```no_run
+/// let manifest = glib_build_tools::generate_resources_xml("resources", "/org/gtk_rs/Example")
+///     .unwrap();
+/// let path = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("resources.xml");
+/// std::fs::write(&path, manifest).unwrap();
+/// glib_build_tools::try_compile_resources("resources", path.to_str().unwrap(), "compiled.gresource")
+///     .unwrap();
+/// ```
+pub fn generate_resources_xml<P: AsRef<Path>>(source_dir: P, prefix: &str) -> io::Result<String> {
+    resources::generate_manifest(source_dir.as_ref(), prefix)
+}
+
+// The schema sources of a directory as `glib-compile-schemas` would read them.
+fn schema_files(source_dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(source_dir)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", source_dir.display())))?
+    {
+        let path = entry?.path();
+        let name = path.file_name().unwrap().to_string_lossy();
+        if name.ends_with(".gschema.xml")
+            || name.ends_with(".enums.xml")
+            || name.ends_with(".gschema.override")
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn out_dir() -> io::Result<PathBuf> {
+    env::var_os("OUT_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "OUT_DIR is not set"))
+}
+
+// rustdoc-stripper-ignore-next
+/// Call to run `glib-compile-schemas` to validate the `*.gschema.xml` files in `source_dir` and
+/// compile them into a `gschemas.compiled` file. `target` is a directory relative to `OUT_DIR`.
+///
+/// The compiled schemas can then be loaded without installing them with
+/// [`gio::SettingsSchemaSource::from_directory`], and [`gio::settings`] can check its keys
+/// against them at compile time with `schema_dir = target`.
+///
+/// Fails if `glib-compile-schemas` can't be run or rejects the schemas.
+///
+/// ```no_run
+/// glib_build_tools::compile_schemas("schemas", "schemas").expect("Failed to compile schemas");
+/// ```
+///
+/// ```ignore
+/// let source = gio::SettingsSchemaSource::from_directory(
+///     concat!(env!("OUT_DIR"), "/schemas"),
+///     gio::SettingsSchemaSource::default().as_ref(),
+///     false,
+/// )?;
+/// ```
+pub fn compile_schemas<P: AsRef<Path>>(source_dir: P, target: &str) -> io::Result<()> {
+    let target_dir = out_dir()?.join(target);
+    fs::create_dir_all(&target_dir)?;
+
+    let output = Command::new("glib-compile-schemas")
+        .arg("--strict")
+        .arg("--targetdir")
+        .arg(&target_dir)
+        .arg(source_dir.as_ref())
+        .output()
+        .map_err(|err| io::Error::new(err.kind(), format!("glib-compile-schemas: {err}")))?;
+    if !output.status.success() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "glib-compile-schemas failed with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr),
+            ),
+        ));
+    }
+
+    println!("cargo:rerun-if-changed={}", source_dir.as_ref().display());
+    for file in schema_files(source_dir.as_ref())? {
+        println!("cargo:rerun-if-changed={}", file.display());
+    }
+    Ok(())
+}
+
+// rustdoc-stripper-ignore-next
+/// Generate typed accessors for the `*.gschema.xml` files in `source_dir` and write them to
+/// `target`, relative to `OUT_DIR`.
+///
+/// Each schema becomes a module named after its id, containing a `Settings` struct declared
+/// with [`gio::settings`] and, for keys with ranges or choices, `KEY_RANGE` and `KEY_CHOICES`
+/// constants. Enums and flags of the schemas become Rust enums at the top level of the
+/// generated file; flags keys are accessed as a `Vec` of them. The generated code refers to
+/// the `gio` crate by that name.
+///
+/// Fails if the schemas can't be read or parsed.
+///
+/// ```no_run
+/// glib_build_tools::generate_settings("schemas", "settings.rs")
+///     .expect("Failed to generate settings");
+/// ```
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/settings.rs"));
+///
+/// let settings = org_gtk_rs_example::Settings::new();
+/// ```
+pub fn generate_settings<P: AsRef<Path>>(source_dir: P, target: &str) -> io::Result<()> {
+    let out_dir = out_dir()?;
+    let invalid_data = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let mut schemas = schemas::Schemas::default();
+    let files = schema_files(source_dir.as_ref())?;
+    for file in &files {
+        if file.extension().map_or(false, |ext| ext == "override") {
+            continue;
+        }
+        let xml = fs::read_to_string(file)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", file.display())))?;
+        schemas
+            .parse(&xml)
+            .map_err(|err| invalid_data(format!("{}: {err}", file.display())))?;
+    }
+
+    let code = schemas.generate().map_err(invalid_data)?;
+    fs::write(out_dir.join(target), code)?;
+
+    println!("cargo:rerun-if-changed={}", source_dir.as_ref().display());
+    for file in files {
+        println!("cargo:rerun-if-changed={}", file.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,319 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Compiler for GResource bundles that does not need `glib-compile-resources`.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    gvdb,
+    xml::{self, Event},
+    zlib,
+};
+
+const G_RESOURCE_FLAGS_COMPRESSED: u32 = 1;
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read(path: &Path) -> io::Result<Vec<u8>> {
+    fs::read(path).map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))
+}
+
+struct File {
+    key: String,
+    path: PathBuf,
+    compressed: bool,
+    preprocess: Option<String>,
+}
+
+fn parse_manifest(source_dir: &Path, manifest: &str) -> Result<Vec<File>, String> {
+    let mut files = Vec::new();
+    let mut prefix = String::from("/");
+    let mut file: Option<(BTreeMap<String, String>, String)> = None;
+
+    for event in xml::parse(manifest)? {
+        match event {
+            Event::Start { name, attrs } if name == "gresource" => {
+                prefix = attrs.get("prefix").cloned().unwrap_or_default();
+                if !prefix.starts_with('/') {
+                    prefix.insert(0, '/');
+                }
+                if !prefix.ends_with('/') {
+                    prefix.push('/');
+                }
+            }
+            Event::Start { name, attrs } if name == "file" => file = Some((attrs, String::new())),
+            Event::Text(text) => {
+                if let Some((_, name)) = &mut file {
+                    name.push_str(&text);
+                }
+            }
+            Event::End { name } if name == "file" => {
+                let (attrs, name) = file.take().unwrap();
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(String::from("<file> without file name"));
+                }
+                let alias = attrs.get("alias").map_or(name, String::as_str);
+                let compressed = match attrs.get("compressed").map(String::as_str) {
+                    None | Some("false") => false,
+                    Some("true") => true,
+                    Some(value) => return Err(format!("Invalid compressed value {value}")),
+                };
+                files.push(File {
+                    key: format!("{prefix}{}", alias.trim_start_matches('/')),
+                    path: source_dir.join(name),
+                    compressed,
+                    preprocess: attrs.get("preprocess").cloned(),
+                });
+            }
+            _ => (),
+        }
+    }
+
+    Ok(files)
+}
+
+// Remove whitespace-only text between tags, like `xmllint --noblanks` does. Whitespace that is
+// the only content of an element is kept.
+fn xml_stripblanks(data: &[u8]) -> Result<Vec<u8>, String> {
+    let xml = std::str::from_utf8(data).map_err(|err| err.to_string())?;
+    let mut out = String::with_capacity(xml.len());
+    let mut rest = xml;
+    let mut after_start_tag = false;
+
+    while let Some(start) = rest.find('<') {
+        let text = &rest[..start];
+        rest = &rest[start..];
+        let blank = text.chars().all(char::is_whitespace);
+        if !blank || (after_start_tag && rest.starts_with("</")) {
+            out.push_str(text);
+        }
+
+        let end = [("<!--", "-->"), ("<![CDATA[", "]]>"), ("<?", "?>")]
+            .iter()
+            .find(|(open, _)| rest.starts_with(open))
+            .map(|(_, close)| rest.find(close).map(|end| end + close.len()))
+            .unwrap_or_else(|| {
+                // Skip over quoted attribute values that might contain `>`
+                let mut quote = None;
+                rest.char_indices().find_map(|(i, c)| match (quote, c) {
+                    (None, '"' | '\'') => {
+                        quote = Some(c);
+                        None
+                    }
+                    (Some(q), c) if q == c => {
+                        quote = None;
+                        None
+                    }
+                    (None, '>') => Some(i + 1),
+                    _ => None,
+                })
+            })
+            .ok_or_else(|| String::from("Unterminated tag"))?;
+
+        let tag = &rest[..end];
+        after_start_tag = !tag.starts_with("</")
+            && !tag.starts_with("<!")
+            && !tag.starts_with("<?")
+            && !tag.ends_with("/>");
+        out.push_str(tag);
+        rest = &rest[end..];
+    }
+    if !rest.chars().all(char::is_whitespace) {
+        out.push_str(rest);
+    }
+
+    Ok(out.into_bytes())
+}
+
+// Remove whitespace outside of strings from JSON.
+fn json_stripblanks(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for &byte in data {
+        if in_string {
+            out.push(byte);
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => (),
+            }
+        } else if !byte.is_ascii_whitespace() {
+            in_string = byte == b'"';
+            out.push(byte);
+        }
+    }
+
+    out
+}
+
+// The serialized `(uuay)` GVariant of a resource, boxed in a `v` as stored in GVDB.
+fn resource_variant(file: &File, data: Vec<u8>) -> io::Result<Vec<u8>> {
+    let size = u32::try_from(data.len())
+        .map_err(|_| invalid_data(format!("{} is too large", file.path.display())))?;
+    let (flags, mut content) = if file.compressed {
+        (G_RESOURCE_FLAGS_COMPRESSED, zlib::compress(&data))
+    } else {
+        // Uncompressed data is NUL-terminated so it can be used as a string directly
+        let mut data = data;
+        data.push(0);
+        (0, data)
+    };
+
+    let mut variant = Vec::with_capacity(content.len() + 15);
+    variant.extend_from_slice(&size.to_le_bytes());
+    variant.extend_from_slice(&flags.to_le_bytes());
+    variant.append(&mut content);
+    variant.push(0);
+    variant.extend_from_slice(b"(uuay)");
+    Ok(variant)
+}
+
+fn parent(key: &str) -> Option<&str> {
+    let end = key.strip_suffix('/').unwrap_or(key).rfind('/')?;
+    Some(&key[..end + 1])
+}
+
+// Compile the resources listed in the manifest `gresource`, also returning the files the result
+// depends on.
+pub(crate) fn compile(source_dir: &Path, gresource: &Path) -> io::Result<(Vec<u8>, Vec<PathBuf>)> {
+    let manifest = String::from_utf8(read(gresource)?)
+        .map_err(|err| invalid_data(format!("{}: {err}", gresource.display())))?;
+    let files = parse_manifest(source_dir, &manifest)
+        .map_err(|err| invalid_data(format!("{}: {err}", gresource.display())))?;
+
+    // Resources and their directories, `None` for directories
+    let mut entries = BTreeMap::<String, Option<Vec<u8>>>::new();
+    entries.insert(String::from("/"), None);
+    let mut dependencies = vec![gresource.to_owned()];
+
+    for file in &files {
+        let mut data = read(&file.path)?;
+        dependencies.push(file.path.clone());
+        for preprocess in file.preprocess.iter().flat_map(|p| p.split(',')) {
+            data = match preprocess.trim() {
+                "xml-stripblanks" => xml_stripblanks(&data)
+                    .map_err(|err| invalid_data(format!("{}: {err}", file.path.display())))?,
+                "json-stripblanks" => json_stripblanks(&data),
+                other => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        format!("Unsupported preprocess option {other}"),
+                    ))
+                }
+            };
+        }
+
+        if entries.contains_key(&file.key) {
+            return Err(invalid_data(format!("Duplicate resource {}", file.key)));
+        }
+        let mut dir = parent(&file.key);
+        while let Some(d) = dir {
+            match entries.get(d) {
+                Some(Some(_)) => return Err(invalid_data(format!("{d} is a file"))),
+                Some(None) => break,
+                None => {
+                    entries.insert(d.to_owned(), None);
+                }
+            }
+            dir = parent(d);
+        }
+        entries.insert(file.key.clone(), Some(resource_variant(file, data)?));
+    }
+
+    let indices = entries
+        .keys()
+        .enumerate()
+        .map(|(i, key)| (key.clone(), i))
+        .collect::<BTreeMap<_, _>>();
+    let mut items = entries
+        .into_iter()
+        .map(|(key, data)| gvdb::Item {
+            parent: parent(&key).map(|p| indices[p]),
+            value: match data {
+                Some(data) => gvdb::Value::Variant(data),
+                None => gvdb::Value::List(Vec::new()),
+            },
+            key,
+        })
+        .collect::<Vec<_>>();
+    for i in 0..items.len() {
+        if let Some(parent) = items[i].parent {
+            if let gvdb::Value::List(children) = &mut items[parent].value {
+                children.push(i);
+            }
+        }
+    }
+
+    let data = gvdb::write(&items).map_err(invalid_data)?;
+    Ok((data, dependencies))
+}
+
+fn collect_files(dir: &Path, relative: &str, files: &mut Vec<String>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = entry.file_name();
+        let name = name.to_str().ok_or_else(|| {
+            invalid_data(format!("{} is not valid UTF-8", entry.path().display()))
+        })?;
+        let path = format!("{relative}{name}");
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), &format!("{path}/"), files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// A manifest containing all files below `source_dir`.
+pub(crate) fn generate_manifest(source_dir: &Path, prefix: &str) -> io::Result<String> {
+    let mut files = Vec::new();
+    collect_files(source_dir, "", &mut files)?;
+
+    let mut manifest = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gresources>\n  <gresource prefix=\"{}\">\n",
+        xml::escape(prefix)
+    );
+    for file in files {
+        manifest.push_str(&format!("    <file>{}</file>\n", xml::escape(&file)));
+    }
+    manifest.push_str("  </gresource>\n</gresources>\n");
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stripblanks() {
+        let xml = b"<?xml version=\"1.0\"?>\n<a>\n  <b attr=\"x > y\">text </b>\n  <c> </c>\n  <!-- comment -->\n  <d/>\n</a>\n";
+        assert_eq!(
+            String::from_utf8(xml_stripblanks(xml).unwrap()).unwrap(),
+            "<?xml version=\"1.0\"?><a><b attr=\"x > y\">text </b><c> </c><!-- comment --><d/></a>"
+        );
+        assert_eq!(
+            json_stripblanks(b"{ \"a b\" : [1, \"\\\" x\"] }\n"),
+            b"{\"a b\":[1,\"\\\" x\"]}"
+        );
+    }
+
+    #[test]
+    fn parents() {
+        assert_eq!(parent("/"), None);
+        assert_eq!(parent("/a/"), Some("/"));
+        assert_eq!(parent("/a/b"), Some("/a/"));
+        assert_eq!(parent("/a/b/"), Some("/a/"));
+    }
+}
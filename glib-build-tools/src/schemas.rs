@@ -0,0 +1,498 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Minimal GSettings schema parser and generator of typed accessors for the schemas.
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::xml::{self, Event};
+
+struct Tag {
+    name: String,
+    attrs: BTreeMap<String, String>,
+    is_end: bool,
+}
+
+// The start and end tags of an XML document, text is not needed for the schemas.
+fn xml_tags(xml: &str) -> Result<Vec<Tag>, String> {
+    Ok(xml::parse(xml)?
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::Start { name, attrs } => Some(Tag {
+                name,
+                attrs,
+                is_end: false,
+            }),
+            Event::End { name } => Some(Tag {
+                name,
+                attrs: BTreeMap::new(),
+                is_end: true,
+            }),
+            Event::Text(_) => None,
+        })
+        .collect())
+}
+
+enum KeyType {
+    Type(String),
+    Enum(String),
+    Flags(String),
+}
+
+struct Key {
+    name: String,
+    type_: KeyType,
+    range: Option<(String, String)>,
+    choices: Vec<String>,
+}
+
+struct Schema {
+    id: String,
+    extends: Option<String>,
+    list_of: bool,
+    keys: Vec<Key>,
+}
+
+struct Enum {
+    id: String,
+    flags: bool,
+    nicks: Vec<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct Schemas {
+    schemas: Vec<Schema>,
+    enums: Vec<Enum>,
+}
+
+fn attr<'a>(tag: &'a Tag, name: &str) -> Result<&'a str, String> {
+    tag.attrs
+        .get(name)
+        .map(String::as_str)
+        .ok_or_else(|| format!("<{}> without {name}", tag.name))
+}
+
+impl Schemas {
+    // Add the schemas and enums of a `.gschema.xml` or `.enums.xml` file.
+    pub(crate) fn parse(&mut self, xml: &str) -> Result<(), String> {
+        let mut schema: Option<Schema> = None;
+        let mut key: Option<Key> = None;
+        let mut enum_: Option<Enum> = None;
+
+        for tag in xml_tags(xml)? {
+            match (tag.name.as_str(), tag.is_end) {
+                ("schema", false) => {
+                    schema = Some(Schema {
+                        id: attr(&tag, "id")?.to_owned(),
+                        extends: tag.attrs.get("extends").cloned(),
+                        list_of: tag.attrs.contains_key("list-of"),
+                        keys: Vec::new(),
+                    });
+                }
+                ("schema", true) => self.schemas.extend(schema.take()),
+                ("key", false) => {
+                    let type_ = if let Some(type_) = tag.attrs.get("type") {
+                        KeyType::Type(type_.clone())
+                    } else if let Some(id) = tag.attrs.get("enum") {
+                        KeyType::Enum(id.clone())
+                    } else if let Some(id) = tag.attrs.get("flags") {
+                        KeyType::Flags(id.clone())
+                    } else {
+                        return Err(format!("Key {} without type", attr(&tag, "name")?));
+                    };
+                    key = Some(Key {
+                        name: attr(&tag, "name")?.to_owned(),
+                        type_,
+                        range: None,
+                        choices: Vec::new(),
+                    });
+                }
+                ("key", true) => {
+                    if let (Some(schema), Some(key)) = (&mut schema, key.take()) {
+                        schema.keys.push(key);
+                    }
+                }
+                ("range", false) => {
+                    if let Some(key) = &mut key {
+                        key.range =
+                            Some((attr(&tag, "min")?.to_owned(), attr(&tag, "max")?.to_owned()));
+                    }
+                }
+                ("choice", false) => {
+                    if let Some(key) = &mut key {
+                        key.choices.push(attr(&tag, "value")?.to_owned());
+                    }
+                }
+                ("enum" | "flags", false) => {
+                    enum_ = Some(Enum {
+                        id: attr(&tag, "id")?.to_owned(),
+                        flags: tag.name == "flags",
+                        nicks: Vec::new(),
+                    });
+                }
+                ("enum" | "flags", true) => self.enums.extend(enum_.take()),
+                ("value", false) => {
+                    if let Some(enum_) = &mut enum_ {
+                        enum_.nicks.push(attr(&tag, "nick")?.to_owned());
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    // All keys of a schema including the ones of the schemas it extends.
+    fn keys<'a>(&'a self, schema: &'a Schema) -> Result<Vec<&'a Key>, String> {
+        let mut chain = vec![schema];
+        while let Some(extends) = &chain.last().unwrap().extends {
+            if chain.len() > self.schemas.len() {
+                return Err(format!("Schema {} extends itself", schema.id));
+            }
+            let base = self
+                .schemas
+                .iter()
+                .find(|s| &s.id == extends)
+                .ok_or_else(|| format!("Schema {} extends unknown schema {extends}", schema.id))?;
+            chain.push(base);
+        }
+
+        let mut keys = Vec::<&Key>::new();
+        for schema in chain.iter().rev() {
+            for key in &schema.keys {
+                match keys.iter_mut().find(|k| k.name == key.name) {
+                    Some(k) => *k = key,
+                    None => keys.push(key),
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    // Generate the Rust code for all parsed schemas.
+    pub(crate) fn generate(&self) -> Result<String, String> {
+        let mut code =
+            String::from("// Generated by glib-build-tools from GSettings schemas. Do not edit.\n");
+
+        let mut enum_names = BTreeMap::new();
+        for enum_ in &self.enums {
+            let short = camel_case(enum_.id.rsplit('.').next().unwrap());
+            let name =
+                if self.enums.iter().any(|e| {
+                    e.id != enum_.id && camel_case(e.id.rsplit('.').next().unwrap()) == short
+                }) {
+                    camel_case(&enum_.id)
+                } else {
+                    short
+                };
+            generate_enum(&mut code, &name, enum_);
+            enum_names.insert(enum_.id.as_str(), name);
+        }
+
+        for schema in self.schemas.iter().filter(|s| !s.list_of) {
+            let keys = self.keys(schema)?;
+            let module = schema.id.replace(['.', '-'], "_").to_lowercase();
+
+            let mut fields = String::new();
+            let mut consts = String::new();
+            for key in keys {
+                let field = field_name(&key.name);
+                let (rust_type, type_string) = match &key.type_ {
+                    KeyType::Type(type_) => (rust_type(type_)?, type_.as_str()),
+                    KeyType::Enum(id) | KeyType::Flags(id) => {
+                        let name = enum_names.get(id.as_str()).ok_or_else(|| {
+                            format!("Key {} uses unknown enum or flags {id}", key.name)
+                        })?;
+                        if matches!(key.type_, KeyType::Enum(_)) {
+                            (format!("super::{name}"), "s")
+                        } else {
+                            (format!("Vec<super::{name}>"), "as")
+                        }
+                    }
+                };
+                writeln!(
+                    fields,
+                    "        #[settings(key = \"{}\")]\n        {field}: {rust_type},",
+                    key.name
+                )
+                .unwrap();
+
+                let const_prefix = field.trim_end_matches('_').to_uppercase();
+                if let (Some((min, max)), Some(suffix)) = (&key.range, number_suffix(type_string)) {
+                    writeln!(
+                        consts,
+                        "        /// Range of valid values of the `{}` key.\n        \
+                         pub const {const_prefix}_RANGE: std::ops::RangeInclusive<{rust_type}> = \
+                         std::ops::RangeInclusive::new({min}_{suffix}, {max}_{suffix});",
+                        key.name
+                    )
+                    .unwrap();
+                }
+                if !key.choices.is_empty() {
+                    let choices = key
+                        .choices
+                        .iter()
+                        .map(|c| format!("{c:?}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    writeln!(
+                        consts,
+                        "        /// Valid values of the `{}` key.\n        \
+                         pub const {const_prefix}_CHOICES: &'static [&'static str] = &[{choices}];",
+                        key.name
+                    )
+                    .unwrap();
+                }
+            }
+
+            write!(
+                code,
+                "\n/// Settings of the `{id}` schema.\n\
+                 #[allow(dead_code)]\n\
+                 pub mod {module} {{\n    \
+                 #[gio::settings(id = \"{id}\")]\n    \
+                 pub struct Settings {{\n{fields}    }}\n",
+                id = schema.id,
+            )
+            .unwrap();
+            if !consts.is_empty() {
+                write!(code, "\n    impl Settings {{\n{consts}    }}\n").unwrap();
+            }
+            code.push_str("}\n");
+        }
+
+        Ok(code)
+    }
+}
+
+fn generate_enum(code: &mut String, name: &str, enum_: &Enum) {
+    let variants = enum_
+        .nicks
+        .iter()
+        .map(|nick| (variant_name(nick), nick))
+        .collect::<Vec<_>>();
+    let kind = if enum_.flags { "flags" } else { "enum" };
+
+    write!(
+        code,
+        "\n/// Values of the `{id}` {kind}.\n\
+         #[allow(dead_code)]\n\
+         #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]\n\
+         pub enum {name} {{\n",
+        id = enum_.id,
+    )
+    .unwrap();
+    for (variant, nick) in &variants {
+        writeln!(code, "    /// `{nick}`\n    {variant},").unwrap();
+    }
+    code.push_str("}\n");
+
+    write!(
+        code,
+        "\nimpl gio::glib::StaticVariantType for {name} {{\n    \
+         fn static_variant_type() -> std::borrow::Cow<'static, gio::glib::VariantTy> {{\n        \
+         std::borrow::Cow::Borrowed(gio::glib::VariantTy::STRING)\n    \
+         }}\n\
+         }}\n\
+         \n\
+         impl gio::glib::ToVariant for {name} {{\n    \
+         fn to_variant(&self) -> gio::glib::Variant {{\n        \
+         gio::glib::ToVariant::to_variant(match self {{\n"
+    )
+    .unwrap();
+    for (variant, nick) in &variants {
+        writeln!(code, "            Self::{variant} => {nick:?},").unwrap();
+    }
+    write!(
+        code,
+        "        }})\n    \
+         }}\n\
+         }}\n\
+         \n\
+         impl gio::glib::FromVariant for {name} {{\n    \
+         fn from_variant(variant: &gio::glib::Variant) -> Option<Self> {{\n        \
+         match variant.str()? {{\n"
+    )
+    .unwrap();
+    for (variant, nick) in &variants {
+        writeln!(code, "            {nick:?} => Some(Self::{variant}),").unwrap();
+    }
+    code.push_str("            _ => None,\n        }\n    }\n}\n");
+}
+
+fn camel_case(s: &str) -> String {
+    let mut camel = String::new();
+    for word in s.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            camel.push(first.to_ascii_uppercase());
+            camel.extend(chars);
+        }
+    }
+    camel
+}
+
+fn variant_name(nick: &str) -> String {
+    let name = camel_case(nick);
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("V{name}")
+    } else {
+        name
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "static",
+    "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+fn field_name(key: &str) -> String {
+    let name = key.replace('-', "_");
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+fn number_suffix(type_: &str) -> Option<&'static str> {
+    Some(match type_ {
+        "y" => "u8",
+        "n" => "i16",
+        "q" => "u16",
+        "i" => "i32",
+        "u" => "u32",
+        "x" => "i64",
+        "t" => "u64",
+        "d" => "f64",
+        _ => return None,
+    })
+}
+
+// The Rust type for a GVariant type string.
+fn rust_type(type_: &str) -> Result<String, String> {
+    match parse_type(type_)? {
+        (rust_type, "") => Ok(rust_type),
+        _ => Err(format!("Invalid type string {type_}")),
+    }
+}
+
+fn parse_type(type_: &str) -> Result<(String, &str), String> {
+    let mut chars = type_.chars();
+    let c = chars
+        .next()
+        .ok_or_else(|| String::from("Incomplete type string"))?;
+    let rest = chars.as_str();
+    if let Some(suffix) = number_suffix(&c.to_string()) {
+        return Ok((suffix.to_owned(), rest));
+    }
+    let basic = match c {
+        'b' => "bool",
+        's' => "String",
+        'o' => "gio::glib::variant::ObjectPath",
+        'g' => "gio::glib::variant::Signature",
+        'v' => "gio::glib::Variant",
+        _ => "",
+    };
+    if !basic.is_empty() {
+        return Ok((basic.to_owned(), rest));
+    }
+
+    match c {
+        'a' if rest.starts_with('{') => {
+            let (key, rest) = parse_type(&rest[1..])?;
+            let (value, rest) = parse_type(rest)?;
+            let rest = rest
+                .strip_prefix('}')
+                .ok_or_else(|| format!("Unterminated dictionary entry in {type_}"))?;
+            Ok((format!("std::collections::HashMap<{key}, {value}>"), rest))
+        }
+        'a' => {
+            let (elem, rest) = parse_type(rest)?;
+            Ok((format!("Vec<{elem}>"), rest))
+        }
+        'm' => {
+            let (elem, rest) = parse_type(rest)?;
+            Ok((format!("Option<{elem}>"), rest))
+        }
+        '(' => {
+            let mut elems = Vec::new();
+            let mut rest = rest;
+            while !rest.starts_with(')') {
+                let (elem, r) = parse_type(rest)?;
+                elems.push(elem);
+                rest = r;
+            }
+            let tuple = match elems.len() {
+                1 => format!("({},)", elems[0]),
+                _ => format!("({})", elems.join(", ")),
+            };
+            Ok((tuple, &rest[1..]))
+        }
+        c => Err(format!("Unsupported type '{c}' in {type_}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn types() {
+        assert_eq!(rust_type("as").unwrap(), "Vec<String>");
+        assert_eq!(
+            rust_type("a{sv}").unwrap(),
+            "std::collections::HashMap<String, gio::glib::Variant>"
+        );
+        assert_eq!(rust_type("(imb)").unwrap(), "(i32, Option<bool>)");
+        assert!(rust_type("(i").is_err());
+        assert!(rust_type("ii").is_err());
+        assert!(rust_type("h").is_err());
+    }
+
+    #[test]
+    fn generate() {
+        let mut schemas = Schemas::default();
+        schemas
+            .parse(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<schemalist>
+  <!-- <schema id="commented"/> -->
+  <enum id="org.gtk_rs.Color">
+    <value nick="red" value="0"/>
+    <value nick="light-blue" value="1"/>
+  </enum>
+  <schema id="org.gtk_rs.Base">
+    <key name="type" type="s">
+      <choices><choice value="a"/><choice value="b"/></choices>
+      <default>'a'</default>
+    </key>
+  </schema>
+  <schema id="org.gtk_rs.Test" path="/org/gtk_rs/Test/" extends="org.gtk_rs.Base">
+    <key name="width" type="i">
+      <range min="-1" max="100"/>
+      <default>10</default>
+    </key>
+    <key name="color" enum="org.gtk_rs.Color">
+      <default>'red'</default>
+    </key>
+  </schema>
+</schemalist>"#,
+            )
+            .unwrap();
+        let code = schemas.generate().unwrap();
+
+        assert!(!code.contains("commented"));
+        assert!(code.contains("pub enum Color {"));
+        assert!(code.contains("    LightBlue,"));
+        assert!(code.contains("\"light-blue\" => Some(Self::LightBlue),"));
+        assert!(code.contains("pub mod org_gtk_rs_test {"));
+        assert!(code.contains("        type_: String,"));
+        assert!(code.contains("        color: super::Color,"));
+        assert!(code.contains("pub const WIDTH_RANGE: std::ops::RangeInclusive<i32> = std::ops::RangeInclusive::new(-1_i32, 100_i32);"));
+        assert!(code.contains("pub const TYPE_CHOICES: &'static [&'static str] = &[\"a\", \"b\"];"));
+    }
+}
@@ -0,0 +1,116 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Minimal XML reader that understands as much XML as is needed for GSettings schema and
+// GResource files: comments, processing instructions and doctype declarations are skipped,
+// CDATA sections are reported as text, namespaces and custom entities are not supported.
+
+use std::collections::BTreeMap;
+
+pub(crate) enum Event {
+    Start {
+        name: String,
+        attrs: BTreeMap<String, String>,
+    },
+    End {
+        name: String,
+    },
+    Text(String),
+}
+
+pub(crate) fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Parse an XML document into a list of events, self-closing tags are reported as a start and
+// an end event.
+pub(crate) fn parse(xml: &str) -> Result<Vec<Event>, String> {
+    let mut events = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        if start > 0 {
+            events.push(Event::Text(unescape(&rest[..start])));
+        }
+        rest = &rest[start..];
+
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata
+                .find("]]>")
+                .ok_or_else(|| String::from("Unterminated CDATA section"))?;
+            events.push(Event::Text(cdata[..end].to_owned()));
+            rest = &cdata[end + 3..];
+            continue;
+        }
+        let skip = [("<!--", "-->"), ("<?", "?>"), ("<!", ">")]
+            .iter()
+            .find(|(open, _)| rest.starts_with(open));
+        if let Some((_, close)) = skip {
+            let end = rest
+                .find(close)
+                .ok_or_else(|| String::from("Unterminated declaration"))?;
+            rest = &rest[end + close.len()..];
+            continue;
+        }
+
+        let end = rest
+            .find('>')
+            .ok_or_else(|| String::from("Unterminated tag"))?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            events.push(Event::End {
+                name: name.trim().to_owned(),
+            });
+            continue;
+        }
+
+        let (tag, self_closing) = match tag.strip_suffix('/') {
+            Some(tag) => (tag, true),
+            None => (tag, false),
+        };
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_owned();
+
+        let mut attrs = BTreeMap::new();
+        let mut attr_rest = tag[name_end..].trim_start();
+        while !attr_rest.is_empty() {
+            let eq = attr_rest
+                .find('=')
+                .ok_or_else(|| format!("Invalid attributes in <{name}>"))?;
+            let attr_name = attr_rest[..eq].trim().to_owned();
+            let value_rest = attr_rest[eq + 1..].trim_start();
+            let quote = value_rest
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'')
+                .ok_or_else(|| format!("Unquoted attribute {attr_name} in <{name}>"))?;
+            let value_end = value_rest[1..]
+                .find(quote)
+                .ok_or_else(|| format!("Unterminated attribute {attr_name} in <{name}>"))?;
+            attrs.insert(attr_name, unescape(&value_rest[1..value_end + 1]));
+            attr_rest = value_rest[value_end + 2..].trim_start();
+        }
+
+        events.push(Event::Start {
+            name: name.clone(),
+            attrs,
+        });
+        if self_closing {
+            events.push(Event::End { name });
+        }
+    }
+
+    Ok(events)
+}
@@ -0,0 +1,126 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{fs, path::PathBuf};
+
+use gio::glib;
+
+fn source_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("sub/dir")).unwrap();
+    dir
+}
+
+#[test]
+fn compile_resources() {
+    let dir = source_dir("compile_resources");
+
+    let big = (0..10000u32)
+        .map(|i| format!("line {} {}\n", i % 97, i * 7919 % 10007))
+        .collect::<String>();
+    fs::write(dir.join("plain.txt"), "plain").unwrap();
+    fs::write(dir.join("big.txt"), &big).unwrap();
+    fs::write(dir.join("ui.xml"), "<a>\n  <b> </b>\n  <c>x</c>\n</a>\n").unwrap();
+    fs::write(dir.join("sub/dir/empty"), "").unwrap();
+    fs::write(
+        dir.join("test.gresource.xml"),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<gresources>
+  <gresource prefix="/org/gtk_rs/test">
+    <file>plain.txt</file>
+    <file compressed="true">big.txt</file>
+    <file preprocess="xml-stripblanks" compressed="true">ui.xml</file>
+    <file alias="empty">sub/dir/empty</file>
+  </gresource>
+  <gresource prefix="/org/gtk_rs/other/">
+    <file>sub/dir/empty</file>
+  </gresource>
+</gresources>
+"#,
+    )
+    .unwrap();
+
+    let data =
+        glib_build_tools::compile_resources_to_bytes(&dir, dir.join("test.gresource.xml")).unwrap();
+    let resource = gio::Resource::from_data(&glib::Bytes::from_owned(data)).unwrap();
+    let lookup = |path| {
+        resource
+            .lookup_data(path, gio::ResourceLookupFlags::NONE)
+            .unwrap()
+    };
+
+    assert_eq!(&*lookup("/org/gtk_rs/test/plain.txt"), b"plain");
+    assert_eq!(&*lookup("/org/gtk_rs/test/big.txt"), big.as_bytes());
+    assert_eq!(
+        &*lookup("/org/gtk_rs/test/ui.xml"),
+        b"<a><b> </b><c>x</c></a>"
+    );
+    assert_eq!(&*lookup("/org/gtk_rs/test/empty"), b"");
+    assert_eq!(&*lookup("/org/gtk_rs/other/sub/dir/empty"), b"");
+    assert!(resource
+        .lookup_data("/org/gtk_rs/test/missing", gio::ResourceLookupFlags::NONE)
+        .is_err());
+
+    let (size, flags) = resource
+        .info("/org/gtk_rs/test/big.txt", gio::ResourceLookupFlags::NONE)
+        .unwrap();
+    assert_eq!(size, big.len());
+    // G_RESOURCE_FLAGS_COMPRESSED
+    assert_eq!(flags, 1);
+
+    let children = resource
+        .enumerate_children("/org/gtk_rs/", gio::ResourceLookupFlags::NONE)
+        .unwrap();
+    assert_eq!(children, ["other/", "test/"]);
+    let children = resource
+        .enumerate_children("/org/gtk_rs/test/", gio::ResourceLookupFlags::NONE)
+        .unwrap();
+    assert_eq!(children, ["big.txt", "empty", "plain.txt", "ui.xml"]);
+}
+
+#[test]
+fn generate_resources_xml() {
+    let dir = source_dir("generate_resources_xml");
+    fs::write(dir.join("b.txt"), "b").unwrap();
+    fs::write(dir.join("sub/dir/a&b.txt"), "a").unwrap();
+
+    let manifest = glib_build_tools::generate_resources_xml(&dir, "/org/gtk_rs/test").unwrap();
+    assert_eq!(
+        manifest,
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<gresources>
+  <gresource prefix="/org/gtk_rs/test">
+    <file>b.txt</file>
+    <file>sub/dir/a&amp;b.txt</file>
+  </gresource>
+</gresources>
+"#
+    );
+
+    fs::write(dir.join("test.gresource.xml"), manifest).unwrap();
+    let data =
+        glib_build_tools::compile_resources_to_bytes(&dir, dir.join("test.gresource.xml")).unwrap();
+    let resource = gio::Resource::from_data(&glib::Bytes::from_owned(data)).unwrap();
+    let data = resource
+        .lookup_data(
+            "/org/gtk_rs/test/sub/dir/a&b.txt",
+            gio::ResourceLookupFlags::NONE,
+        )
+        .unwrap();
+    assert_eq!(&*data, b"a");
+}
+
+#[test]
+fn unsupported_preprocess() {
+    let dir = source_dir("unsupported_preprocess");
+    fs::write(dir.join("a.png"), "").unwrap();
+    fs::write(
+        dir.join("test.gresource.xml"),
+        r#"<gresources><gresource prefix="/"><file preprocess="to-pixdata">a.png</file></gresource></gresources>"#,
+    )
+    .unwrap();
+
+    let err = glib_build_tools::compile_resources_to_bytes(&dir, dir.join("test.gresource.xml"))
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+}
@@ -0,0 +1,723 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use heck::ToUpperCamelCase;
+use proc_macro2::{Ident, TokenStream};
+use proc_macro_error::{abort, abort_call_site};
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, FnArg, GenericArgument, ImplItem, Item, ItemImpl, ItemTrait, Lit, Meta, NestedMeta,
+    Pat, PathArguments, ReturnType, Signature, TraitItem, Type,
+};
+
+pub const WRONG_PLACE_MSG: &str =
+    "This macro should be used on a trait definition or on an `impl` block of such a trait";
+
+#[derive(Default)]
+struct MemberAttrs {
+    signal: bool,
+    property: bool,
+    name: Option<String>,
+}
+
+// Parse and remove the `#[dbus(...)]` attributes of a member
+fn take_member_attrs(attrs: &mut Vec<Attribute>) -> MemberAttrs {
+    let mut res = MemberAttrs::default();
+
+    attrs.retain(|attr| {
+        if !attr.path.is_ident("dbus") {
+            return true;
+        }
+
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => abort!(attr, "Expected `#[dbus(...)]`"),
+        };
+        for nested in &list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("signal") => res.signal = true,
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("property") => res.property = true,
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
+                    match &nv.lit {
+                        Lit::Str(s) => res.name = Some(s.value()),
+                        lit => abort!(lit, "Expected a string literal"),
+                    }
+                }
+                nested => abort!(nested, "Unknown D-Bus member attribute"),
+            }
+        }
+
+        false
+    });
+
+    if res.signal && res.property {
+        abort_call_site!("A member can't be both a signal and a property");
+    }
+
+    res
+}
+
+struct Arg {
+    ident: Ident,
+    name: String,
+    ty: Type,
+}
+
+enum Output {
+    Unit,
+    Single(Box<Type>),
+    Tuple(Vec<Type>),
+}
+
+struct Method {
+    ident: Ident,
+    dbus_name: String,
+    args: Vec<Arg>,
+    output: Output,
+    is_async: bool,
+}
+
+struct Signal {
+    ident: Ident,
+    dbus_name: String,
+    args: Vec<Arg>,
+}
+
+struct Property {
+    dbus_name: String,
+    ty: Type,
+    getter: Option<Ident>,
+    setter: Option<Ident>,
+}
+
+fn has_ref_self_receiver(sig: &Signature) -> bool {
+    matches!(sig.inputs.first(), Some(FnArg::Receiver(r)) if r.reference.is_some() && r.mutability.is_none())
+}
+
+fn parse_args(sig: &Signature) -> Vec<Arg> {
+    sig.inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(arg) => Some(arg),
+        })
+        .enumerate()
+        .map(|(i, arg)| {
+            let name = match &*arg.pat {
+                Pat::Ident(p) => p.ident.to_string(),
+                _ => format!("arg{i}"),
+            };
+            Arg {
+                ident: format_ident!("arg{}", i),
+                name,
+                ty: (*arg.ty).clone(),
+            }
+        })
+        .collect()
+}
+
+fn parse_output(sig: &Signature) -> Output {
+    let ty = match &sig.output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => abort!(sig, "D-Bus methods must return `Result<T, glib::Error>`"),
+    };
+
+    let ok_ty = match &**ty {
+        Type::Path(p) => p.path.segments.last().and_then(|s| {
+            if s.ident != "Result" {
+                return None;
+            }
+            match &s.arguments {
+                PathArguments::AngleBracketed(args) => match args.args.first() {
+                    Some(GenericArgument::Type(ty)) => Some(ty),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }),
+        _ => None,
+    };
+
+    match ok_ty {
+        Some(Type::Tuple(t)) if t.elems.is_empty() => Output::Unit,
+        Some(Type::Tuple(t)) => Output::Tuple(t.elems.iter().cloned().collect()),
+        Some(ty) => Output::Single(Box::new(ty.clone())),
+        None => abort!(ty, "D-Bus methods must return `Result<T, glib::Error>`"),
+    }
+}
+
+fn dbus_name(attrs: &MemberAttrs, ident: &Ident) -> String {
+    attrs
+        .name
+        .clone()
+        .unwrap_or_else(|| ident.to_string().to_upper_camel_case())
+}
+
+// Turn `async fn foo(&self) -> T` into `fn foo(&self) -> Pin<Box<dyn Future<Output = T> + '_>>`
+fn desugar_async(sig: &mut Signature) {
+    sig.asyncness = None;
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => quote!(#ty),
+        ReturnType::Default => quote!(()),
+    };
+    sig.output = syn::parse_quote! {
+        -> ::std::pin::Pin<::std::boxed::Box<dyn ::std::future::Future<Output = #output> + '_>>
+    };
+}
+
+pub fn impl_dbus_interface(args: &[NestedMeta], item: Item) -> TokenStream {
+    match item {
+        Item::Trait(input) => impl_interface_trait(args, input),
+        Item::Impl(input) => {
+            if !args.is_empty() {
+                abort_call_site!("No arguments are allowed on `impl` blocks");
+            }
+            impl_interface_impl(input)
+        }
+        _ => abort_call_site!(WRONG_PLACE_MSG),
+    }
+}
+
+fn impl_interface_impl(mut input: ItemImpl) -> TokenStream {
+    for item in &mut input.items {
+        if let ImplItem::Method(method) = item {
+            if method.sig.asyncness.is_some() {
+                desugar_async(&mut method.sig);
+                let block = &method.block;
+                method.block = syn::parse_quote! {
+                    {
+                        ::std::boxed::Box::pin(async move #block)
+                    }
+                };
+            }
+        }
+    }
+
+    quote!(#input)
+}
+
+fn parse_interface_name(args: &[NestedMeta]) -> String {
+    let mut name = None;
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => match &nv.lit {
+                Lit::Str(s) => name = Some(s.value()),
+                lit => abort!(lit, "Expected a string literal"),
+            },
+            arg => abort!(arg, "Unknown D-Bus interface attribute"),
+        }
+    }
+
+    match name {
+        Some(name) => name,
+        None => abort_call_site!(
+            "Missing interface name, e.g. `#[dbus_interface(name = \"org.example.Interface\")]`"
+        ),
+    }
+}
+
+fn impl_interface_trait(args: &[NestedMeta], mut input: ItemTrait) -> TokenStream {
+    let interface_name = parse_interface_name(args);
+
+    if !input.generics.params.is_empty() || input.generics.where_clause.is_some() {
+        abort!(input.generics, "Generic D-Bus interfaces are not supported");
+    }
+
+    let mut methods = Vec::new();
+    let mut signals = Vec::new();
+    let mut properties: Vec<Property> = Vec::new();
+
+    input.items.retain_mut(|item| {
+        let method = match item {
+            TraitItem::Method(method) => method,
+            _ => return true,
+        };
+
+        let attrs = take_member_attrs(&mut method.attrs);
+
+        if attrs.signal {
+            if method.default.is_some() {
+                abort!(method.default, "Signals can't have an implementation");
+            }
+            signals.push(Signal {
+                ident: method.sig.ident.clone(),
+                dbus_name: dbus_name(&attrs, &method.sig.ident),
+                args: parse_args(&method.sig),
+            });
+            // Signals are emitted by the skeleton and are not implemented by the trait
+            return false;
+        }
+
+        if !has_ref_self_receiver(&method.sig) {
+            abort!(method.sig, "D-Bus methods and properties must take `&self`");
+        }
+
+        if attrs.property {
+            if method.sig.asyncness.is_some() {
+                abort!(method.sig, "D-Bus property accessors can't be `async`");
+            }
+
+            let mut args = parse_args(&method.sig);
+            let ident = method.sig.ident.clone();
+            let (prop_ident, ty, is_setter) = match (args.pop(), &method.sig.output) {
+                (None, ReturnType::Type(_, ty)) => (ident.clone(), (**ty).clone(), false),
+                (Some(arg), ReturnType::Default) if args.is_empty() => {
+                    match ident.to_string().strip_prefix("set_") {
+                        Some(name) => (Ident::new(name, ident.span()), arg.ty, true),
+                        None => abort!(ident, "Property setters must be called `set_<name>`"),
+                    }
+                }
+                _ => abort!(
+                    method.sig,
+                    "Properties must be declared as `fn name(&self) -> T` or `fn set_name(&self, value: T)`"
+                ),
+            };
+
+            let name = dbus_name(&attrs, &prop_ident);
+            let idx = match properties.iter().position(|p| p.dbus_name == name) {
+                Some(idx) => idx,
+                None => {
+                    properties.push(Property {
+                        dbus_name: name,
+                        ty,
+                        getter: None,
+                        setter: None,
+                    });
+                    properties.len() - 1
+                }
+            };
+            let property = &mut properties[idx];
+            if is_setter {
+                property.setter = Some(ident);
+            } else {
+                property.getter = Some(ident);
+            }
+        } else {
+            methods.push(Method {
+                ident: method.sig.ident.clone(),
+                dbus_name: dbus_name(&attrs, &method.sig.ident),
+                args: parse_args(&method.sig),
+                output: parse_output(&method.sig),
+                is_async: method.sig.asyncness.is_some(),
+            });
+
+            if method.sig.asyncness.is_some() {
+                desugar_async(&mut method.sig);
+                if let Some(block) = &method.default {
+                    method.default = Some(syn::parse_quote! {
+                        {
+                            ::std::boxed::Box::pin(async move #block)
+                        }
+                    });
+                }
+            }
+        }
+
+        true
+    });
+
+    for property in &properties {
+        if property.getter.is_none() {
+            abort_call_site!(
+                "Property `{}` has a setter but no getter, write-only properties are not supported",
+                property.dbus_name
+            );
+        }
+    }
+
+    let gio = crate::utils::gio_crate_ident_new();
+    let glib = quote!(#gio::glib);
+
+    let trait_ident = &input.ident;
+    let vis = &input.vis;
+    let skeleton = format_ident!("{}Skeleton", trait_ident);
+    let proxy = format_ident!("{}Proxy", trait_ident);
+
+    // Introspection data
+    let method_infos = methods.iter().map(|m| {
+        let name = &m.dbus_name;
+        let in_args = m.args.iter().map(|arg| {
+            let name = &arg.name;
+            let ty = &arg.ty;
+            quote! { .in_arg::<#ty>(#name) }
+        });
+        let out_types = match &m.output {
+            Output::Unit => Vec::new(),
+            Output::Single(ty) => vec![(String::from("result"), &**ty)],
+            Output::Tuple(tys) => tys
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| (format!("result{i}"), ty))
+                .collect(),
+        };
+        let out_args = out_types.iter().map(|(name, ty)| {
+            quote! { .out_arg::<#ty>(#name) }
+        });
+        quote! {
+            .method(
+                #gio::DBusMethodInfo::builder(#name)
+                    #(#in_args)*
+                    #(#out_args)*
+                    .build()
+            )
+        }
+    });
+    let signal_infos = signals.iter().map(|s| {
+        let name = &s.dbus_name;
+        let args = s.args.iter().map(|arg| {
+            let name = &arg.name;
+            let ty = &arg.ty;
+            quote! { .arg::<#ty>(#name) }
+        });
+        quote! {
+            .signal(
+                #gio::DBusSignalInfo::builder(#name)
+                    #(#args)*
+                    .build()
+            )
+        }
+    });
+    let property_infos = properties.iter().map(|p| {
+        let name = &p.dbus_name;
+        let ty = &p.ty;
+        let flags = if p.setter.is_some() {
+            quote! { #gio::DBusPropertyInfoFlags::READABLE | #gio::DBusPropertyInfoFlags::WRITABLE }
+        } else {
+            quote! { #gio::DBusPropertyInfoFlags::READABLE }
+        };
+        quote! {
+            .property(
+                #gio::DBusPropertyInfo::builder::<#ty>(#name)
+                    .flags(#flags)
+                    .build()
+            )
+        }
+    });
+
+    let reply = |output: &Output| {
+        let ok = match output {
+            Output::Unit => quote! {
+                ::std::result::Result::Ok(()) => invocation.return_value(::std::option::Option::None),
+            },
+            Output::Single(_) => quote! {
+                ::std::result::Result::Ok(ret) => invocation.return_value(::std::option::Option::Some(&#glib::ToVariant::to_variant(&(ret,)))),
+            },
+            Output::Tuple(_) => quote! {
+                ::std::result::Result::Ok(ret) => invocation.return_value(::std::option::Option::Some(&#glib::ToVariant::to_variant(&ret))),
+            },
+        };
+        quote! {
+            match res {
+                #ok
+                ::std::result::Result::Err(err) => invocation.return_gerror(err),
+            }
+        }
+    };
+
+    // Server side dispatching
+    let method_arms = methods.iter().map(|m| {
+        let name = &m.dbus_name;
+        let ident = &m.ident;
+        let arg_idents = m.args.iter().map(|a| &a.ident).collect::<Vec<_>>();
+        let arg_tys = m.args.iter().map(|a| &a.ty);
+        let reply = reply(&m.output);
+        let call = if m.is_async {
+            quote! {
+                let imp = ::std::clone::Clone::clone(imp);
+                #glib::MainContext::ref_thread_default().spawn_local(async move {
+                    let res = <T as #trait_ident>::#ident(&imp, #(#arg_idents),*).await;
+                    #reply
+                });
+            }
+        } else {
+            quote! {
+                let res = <T as #trait_ident>::#ident(imp, #(#arg_idents),*);
+                #reply
+            }
+        };
+        quote! {
+            #name => {
+                let (#(#arg_idents,)*) = match parameters.get::<(#(#arg_tys,)*)>() {
+                    ::std::option::Option::Some(args) => args,
+                    ::std::option::Option::None => {
+                        return invocation.return_dbus_error(
+                            "org.freedesktop.DBus.Error.InvalidArgs",
+                            &::std::format!("Invalid arguments for method {}", method_name),
+                        );
+                    }
+                };
+                #call
+            }
+        }
+    });
+    let get_arms = properties.iter().map(|p| {
+        let name = &p.dbus_name;
+        let getter = p.getter.as_ref().unwrap();
+        quote! {
+            #name => #glib::ToVariant::to_variant(&<T as #trait_ident>::#getter(imp)),
+        }
+    });
+    let set_arms = properties.iter().filter_map(|p| {
+        let name = &p.dbus_name;
+        let ty = &p.ty;
+        let setter = p.setter.as_ref()?;
+        Some(quote! {
+            #name => match value.get::<#ty>() {
+                ::std::option::Option::Some(value) => {
+                    <T as #trait_ident>::#setter(imp, value);
+                    true
+                }
+                ::std::option::Option::None => false,
+            },
+        })
+    });
+
+    let emit_fns = signals.iter().map(|s| {
+        let name = &s.dbus_name;
+        let emit_ident = format_ident!("emit_{}", s.ident);
+        let arg_idents = s.args.iter().map(|a| &a.ident).collect::<Vec<_>>();
+        let arg_tys = s.args.iter().map(|a| &a.ty);
+        let doc = format!("Emits the `{name}` signal.");
+        quote! {
+            #[doc = #doc]
+            pub fn #emit_ident(&self, #(#arg_idents: #arg_tys),*) -> ::std::result::Result<(), #glib::Error> {
+                self.0.emit_signal(
+                    #name,
+                    ::std::option::Option::Some(&#glib::ToVariant::to_variant(&(#(#arg_idents,)*))),
+                )
+            }
+        }
+    });
+    let notify_fns = properties.iter().map(|p| {
+        let name = &p.dbus_name;
+        let ty = &p.ty;
+        let notify_ident = format_ident!("notify_{}", p.getter.as_ref().unwrap());
+        let doc = format!("Notifies clients that the `{name}` property changed to `value`.");
+        quote! {
+            #[doc = #doc]
+            pub fn #notify_ident(&self, value: #ty) -> ::std::result::Result<(), #glib::Error> {
+                self.0.emit_property_changed(#name, #glib::ToVariant::to_variant(&value))
+            }
+        }
+    });
+
+    // Client side
+    let map_type_error = quote! {
+        |err| #glib::Error::new(#gio::IOErrorEnum::InvalidData, &::std::string::ToString::to_string(&err))
+    };
+    let proxy_methods = methods.iter().map(|m| {
+        let name = &m.dbus_name;
+        let ident = &m.ident;
+        let arg_idents = m.args.iter().map(|a| &a.ident).collect::<Vec<_>>();
+        let arg_tys = m.args.iter().map(|a| &a.ty);
+        let (ret_ty, decode) = match &m.output {
+            Output::Unit => (
+                quote!(()),
+                quote! {
+                    #glib::Variant::try_get::<()>(&reply).map_err(#map_type_error)
+                },
+            ),
+            Output::Single(ty) => (
+                quote!(#ty),
+                quote! {
+                    #glib::Variant::try_get::<(#ty,)>(&reply).map(|(ret,)| ret).map_err(#map_type_error)
+                },
+            ),
+            Output::Tuple(tys) => (
+                quote!((#(#tys,)*)),
+                quote! {
+                    #glib::Variant::try_get::<(#(#tys,)*)>(&reply).map_err(#map_type_error)
+                },
+            ),
+        };
+        let doc = format!("Calls the `{name}` method.");
+        quote! {
+            #[doc = #doc]
+            pub async fn #ident(&self, #(#arg_idents: #arg_tys),*) -> ::std::result::Result<#ret_ty, #glib::Error> {
+                let reply = #gio::prelude::DBusProxyExt::call_future(
+                    &self.0,
+                    #name,
+                    ::std::option::Option::Some(&#glib::ToVariant::to_variant(&(#(#arg_idents,)*))),
+                    #gio::DBusCallFlags::NONE,
+                    -1,
+                ).await?;
+                #decode
+            }
+        }
+    });
+    let proxy_properties = properties.iter().map(|p| {
+        let name = &p.dbus_name;
+        let ty = &p.ty;
+        let getter = p.getter.as_ref().unwrap();
+        let doc = format!("Returns the cached value of the `{name}` property.");
+        let setter = p.setter.as_ref().map(|setter| {
+            let doc = format!("Sets the `{name}` property.");
+            quote! {
+                #[doc = #doc]
+                pub async fn #setter(&self, value: #ty) -> ::std::result::Result<(), #glib::Error> {
+                    let parameters = #glib::Variant::tuple_from_iter([
+                        #glib::ToVariant::to_variant(#skeleton::INTERFACE_NAME),
+                        #glib::ToVariant::to_variant(#name),
+                        #glib::Variant::from_variant(&#glib::ToVariant::to_variant(&value)),
+                    ]);
+                    #gio::prelude::DBusProxyExt::call_future(
+                        &self.0,
+                        "org.freedesktop.DBus.Properties.Set",
+                        ::std::option::Option::Some(&parameters),
+                        #gio::DBusCallFlags::NONE,
+                        -1,
+                    ).await?;
+                    ::std::result::Result::Ok(())
+                }
+            }
+        });
+        quote! {
+            #[doc = #doc]
+            pub fn #getter(&self) -> ::std::option::Option<#ty> {
+                #gio::prelude::DBusProxyExt::cached_property(&self.0, #name)
+                    .and_then(|value| value.get::<#ty>())
+            }
+
+            #setter
+        }
+    });
+    let proxy_signals = signals.iter().map(|s| {
+        let name = &s.dbus_name;
+        let connect_ident = format_ident!("connect_{}", s.ident);
+        let arg_idents = s.args.iter().map(|a| &a.ident).collect::<Vec<_>>();
+        let arg_tys = s.args.iter().map(|a| &a.ty).collect::<Vec<_>>();
+        let doc = format!("Connects to the `{name}` signal.");
+        quote! {
+            #[doc = #doc]
+            pub fn #connect_ident<F: Fn(&Self, #(#arg_tys),*) + 'static>(&self, f: F) -> #glib::SignalHandlerId {
+                #glib::ObjectExt::connect_local(&self.0, "g-signal", false, move |values| {
+                    let signal_name = values[2].get::<&str>().unwrap();
+                    if signal_name == #name {
+                        let proxy = values[0].get::<#gio::DBusProxy>().unwrap();
+                        let parameters = values[3].get::<#glib::Variant>().unwrap();
+                        if let ::std::option::Option::Some((#(#arg_idents,)*)) = parameters.get::<(#(#arg_tys,)*)>() {
+                            f(&Self(proxy), #(#arg_idents),*);
+                        }
+                    }
+                    ::std::option::Option::None
+                })
+            }
+        }
+    });
+
+    let skeleton_doc = format!(
+        "Server side of the `{interface_name}` D-Bus interface.\n\nExports an implementation of [`{trait_ident}`] on a connection."
+    );
+    let proxy_doc = format!("Client side of the `{interface_name}` D-Bus interface.");
+    quote! {
+        #input
+
+        #[doc = #skeleton_doc]
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        #vis struct #skeleton(#gio::DBusObjectRegistration);
+
+        #[allow(dead_code)]
+        impl #skeleton {
+            pub const INTERFACE_NAME: &'static str = #interface_name;
+
+            /// Returns the introspection data of the interface.
+            pub fn interface_info() -> #gio::DBusInterfaceInfo {
+                #gio::DBusInterfaceInfo::builder(Self::INTERFACE_NAME)
+                    #(#method_infos)*
+                    #(#signal_infos)*
+                    #(#property_infos)*
+                    .build()
+            }
+
+            /// Exports `imp` at `object_path` on `connection`.
+            ///
+            /// The object is unregistered when the returned value is dropped.
+            #[allow(unused_variables)]
+            pub fn register<T: #trait_ident + 'static>(
+                connection: &#gio::DBusConnection,
+                object_path: &str,
+                imp: T,
+            ) -> ::std::result::Result<Self, #glib::Error> {
+                #gio::DBusObjectRegistration::register(
+                    connection,
+                    object_path,
+                    &Self::interface_info(),
+                    imp,
+                    |imp: &::std::rc::Rc<T>, method_name: &str, parameters: #glib::Variant, invocation: #gio::DBusMethodInvocation| {
+                        match method_name {
+                            #(#method_arms)*
+                            _ => invocation.return_dbus_error(
+                                "org.freedesktop.DBus.Error.UnknownMethod",
+                                &::std::format!("Unknown method {}", method_name),
+                            ),
+                        }
+                    },
+                    |imp: &T, property_name: &str| -> #glib::Variant {
+                        match property_name {
+                            #(#get_arms)*
+                            _ => ::std::unreachable!("Unknown property {}", property_name),
+                        }
+                    },
+                    |imp: &T, property_name: &str, value: #glib::Variant| -> bool {
+                        match property_name {
+                            #(#set_arms)*
+                            _ => false,
+                        }
+                    },
+                )
+                .map(Self)
+            }
+
+            /// Returns the underlying registration.
+            pub fn registration(&self) -> &#gio::DBusObjectRegistration {
+                &self.0
+            }
+
+            #(#emit_fns)*
+
+            #(#notify_fns)*
+        }
+
+        #[doc = #proxy_doc]
+        #[derive(Clone, Debug)]
+        #[allow(dead_code)]
+        #vis struct #proxy(#gio::DBusProxy);
+
+        #[allow(dead_code)]
+        impl #proxy {
+            /// Creates a proxy for the object at `object_path` owned by `name` on `connection`.
+            pub async fn new(
+                connection: &#gio::DBusConnection,
+                name: ::std::option::Option<&str>,
+                object_path: &str,
+            ) -> ::std::result::Result<Self, #glib::Error> {
+                let proxy = #gio::DBusProxy::new_future(
+                    connection,
+                    #gio::DBusProxyFlags::NONE,
+                    ::std::option::Option::Some(&#skeleton::interface_info()),
+                    name,
+                    object_path,
+                    #skeleton::INTERFACE_NAME,
+                )
+                .await?;
+                ::std::result::Result::Ok(Self(proxy))
+            }
+
+            /// Wraps an existing proxy for the interface.
+            pub fn from_proxy(proxy: #gio::DBusProxy) -> Self {
+                Self(proxy)
+            }
+
+            /// Returns the underlying proxy.
+            pub fn proxy(&self) -> &#gio::DBusProxy {
+                &self.0
+            }
+
+            #(#proxy_methods)*
+
+            #(#proxy_properties)*
+
+            #(#proxy_signals)*
+        }
+    }
+}
@@ -0,0 +1,125 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// Reader for GVDB files, the hash table format of compiled GSettings schemas. Only lookups are
+// supported: the bloom filter and the hash buckets are skipped and the items searched linearly,
+// which is good enough for the few lookups done at compile time.
+
+const HASH_HEADER_SIZE: usize = 8;
+const HASH_ITEM_SIZE: usize = 24;
+const NO_PARENT: u32 = u32::MAX;
+
+fn u32_at(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| String::from("Truncated GVDB file"))
+}
+
+// The data a pointer of the item at `offset` refers to.
+fn pointer<'a>(data: &'a [u8], table: &[u8], offset: usize) -> Result<&'a [u8], String> {
+    let start = u32_at(table, offset)? as usize;
+    let end = u32_at(table, offset + 4)? as usize;
+    data.get(start..end)
+        .ok_or_else(|| String::from("Invalid pointer in GVDB file"))
+}
+
+#[derive(Clone, Copy)]
+pub struct Table<'a> {
+    data: &'a [u8],
+    items: &'a [u8],
+}
+
+impl<'a> Table<'a> {
+    // The root table of a GVDB file. The header is always little endian, only the GVariant
+    // values are stored in the byte order of the machine that wrote the file.
+    pub fn root(data: &'a [u8]) -> Result<Self, String> {
+        if !data.starts_with(b"GVariant") && !data.starts_with(b"raVGtnai") {
+            return Err(String::from("Not a GVDB file"));
+        }
+        if u32_at(data, 8)? != 0 {
+            return Err(String::from("Unsupported GVDB version"));
+        }
+        Self::new(data, pointer(data, data, 16)?)
+    }
+
+    fn new(data: &'a [u8], table: &'a [u8]) -> Result<Self, String> {
+        // The upper bits of the bloom filter size are the bloom shift
+        let n_bloom_words = u32_at(table, 0)? & ((1 << 27) - 1);
+        let n_buckets = u32_at(table, 4)?;
+        let items_start = HASH_HEADER_SIZE + 4 * (n_bloom_words as usize + n_buckets as usize);
+        let items = table
+            .get(items_start..)
+            .ok_or_else(|| String::from("Truncated GVDB hash table"))?;
+        Ok(Self {
+            data,
+            items: &items[..items.len() - items.len() % HASH_ITEM_SIZE],
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.items.len() / HASH_ITEM_SIZE
+    }
+
+    fn item(&self, index: usize) -> &'a [u8] {
+        &self.items[index * HASH_ITEM_SIZE..(index + 1) * HASH_ITEM_SIZE]
+    }
+
+    // The full key of an item, made of the keys of its parents and its own.
+    fn key(&self, index: usize) -> Result<Vec<u8>, String> {
+        let mut parts = Vec::new();
+        let mut index = index;
+        loop {
+            let item = self.item(index);
+            let start = u32_at(item, 8)? as usize;
+            let size = u16::from_le_bytes([item[12], item[13]]) as usize;
+            parts.push(
+                self.data
+                    .get(start..start + size)
+                    .ok_or_else(|| String::from("Invalid key in GVDB file"))?,
+            );
+
+            match u32_at(item, 4)? {
+                NO_PARENT => break,
+                parent if (parent as usize) < self.len() && parts.len() <= self.len() => {
+                    index = parent as usize
+                }
+                _ => return Err(String::from("Invalid parent in GVDB file")),
+            }
+        }
+        Ok(parts.into_iter().rev().flatten().copied().collect())
+    }
+
+    fn find(&self, key: &str, type_: u8) -> Result<Option<&'a [u8]>, String> {
+        for index in 0..self.len() {
+            let item = self.item(index);
+            if item[14] == type_ && self.key(index)? == key.as_bytes() {
+                return pointer(self.data, item, 16).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    // The nested table stored at `key`.
+    pub fn table(&self, key: &str) -> Result<Option<Table<'a>>, String> {
+        match self.find(key, b'H')? {
+            Some(table) => Self::new(self.data, table).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    // The value stored at `key` as its GVariant type string and serialized data.
+    pub fn value(&self, key: &str) -> Result<Option<(&'a str, &'a [u8])>, String> {
+        // Values are serialized as GVariants of type `v`: the data of the value, a nul byte
+        // and the type string of the value
+        let variant = match self.find(key, b'v')? {
+            Some(variant) => variant,
+            None => return Ok(None),
+        };
+        let nul = variant
+            .iter()
+            .rposition(|b| *b == 0)
+            .ok_or_else(|| format!("Invalid value of {key} in GVDB file"))?;
+        let type_ = std::str::from_utf8(&variant[nul + 1..])
+            .map_err(|_| format!("Invalid value of {key} in GVDB file"))?;
+        Ok(Some((type_, &variant[..nul])))
+    }
+}
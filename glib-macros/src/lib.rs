@@ -3,12 +3,15 @@
 mod boxed_derive;
 mod clone;
 mod closure;
+mod dbus_interface_attribute;
 mod downgrade_derive;
 mod enum_derive;
 mod error_domain_derive;
 mod flags_attribute;
+mod gvdb;
 mod object_interface_attribute;
 mod object_subclass_attribute;
+mod settings_attribute;
 mod shared_boxed_derive;
 mod variant_derive;
 
@@ -16,7 +19,7 @@ mod utils;
 
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
-use syn::{parse_macro_input, DeriveInput, NestedMeta};
+use syn::{parse_macro_input, AttributeArgs, DeriveInput, NestedMeta};
 
 /// Macro for passing variables as strong or weak references into a closure.
 ///
@@ -820,6 +823,143 @@ pub fn variant_derive(input: TokenStream) -> TokenStream {
     variant_derive::impl_variant(input)
 }
 
+/// Attribute macro for defining typed D-Bus interfaces.
+///
+/// This is re-exported by the `gio` crate as `gio::dbus_interface`.
+///
+/// When used on a trait, the trait describes the interface `name` and the macro generates
+///
+/// - `<Trait>Skeleton` for exporting an implementation of the trait on a
+///   `gio::DBusConnection` with `register()`. It also provides `emit_<signal>()` for emitting
+///   the interface's signals and `notify_<property>()` for notifying clients about property
+///   changes.
+/// - `<Trait>Proxy` for calling the interface from a client with typed `async` methods, property
+///   getters and setters and `connect_<signal>()` for handling the interface's signals.
+///
+/// Both provide the introspection data of the interface via `interface_info()` on the skeleton.
+///
+/// The members of the trait are mapped as follows:
+///
+/// - Methods must take `&self` and return `Result<T, glib::Error>`. They can be `async`, in
+///   which case the reply is sent once the future resolved. The arguments of the D-Bus method
+///   are the arguments of the Rust method, a tuple `T` is mapped to multiple return values.
+/// - Properties are declared with `#[dbus(property)]` on a getter `fn name(&self) -> T` and
+///   optionally on a setter `fn set_name(&self, value: T)`.
+/// - Signals are declared with `#[dbus(signal)]` on a method without implementation, e.g.
+///   `fn changed(value: i32);`. They are removed from the trait.
+///
+/// All types must implement [`glib::StaticVariantType`], [`glib::ToVariant`] and
+/// [`glib::FromVariant`]. The D-Bus names of the members are the upper camel case versions of the
+/// Rust names and can be changed with `#[dbus(name = "...")]`.
+///
+/// `async` methods are turned into methods returning a boxed future. When used on an `impl` block
+/// of the trait, the macro does the same for `async` methods there so that they can be
+/// implemented as `async fn`.
+///
+/// # Example
+///
+/// ```no_run
+/// #[gio::dbus_interface(name = "org.gtk_rs.Calculator")]
+/// trait Calculator {
+///     async fn add(&self, a: i32, b: i32) -> Result<i32, glib::Error>;
+///
+///     #[dbus(property)]
+///     fn precision(&self) -> u32;
+///     #[dbus(property)]
+///     fn set_precision(&self, value: u32);
+///
+///     #[dbus(signal)]
+///     fn overflowed(value: i64);
+/// }
+///
+/// struct Calc;
+///
+/// #[gio::dbus_interface]
+/// impl Calculator for Calc {
+///     async fn add(&self, a: i32, b: i32) -> Result<i32, glib::Error> {
+///         a.checked_add(b)
+///             .ok_or_else(|| glib::Error::new(gio::IOErrorEnum::InvalidArgument, "Overflow"))
+///     }
+///
+///     fn precision(&self) -> u32 {
+///         0
+///     }
+///
+///     fn set_precision(&self, _value: u32) {}
+/// }
+///
+/// # async fn run() -> Result<(), glib::Error> {
+/// let connection = gio::bus_get_future(gio::BusType::Session).await?;
+///
+/// let skeleton = CalculatorSkeleton::register(&connection, "/org/gtk_rs/Calculator", Calc)?;
+/// skeleton.emit_overflowed(i64::MAX)?;
+///
+/// let name = connection.unique_name();
+/// let proxy = CalculatorProxy::new(&connection, name.as_deref(), "/org/gtk_rs/Calculator").await?;
+/// assert_eq!(proxy.add(1, 2).await?, 3);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`glib::StaticVariantType`]: ../glib/variant/trait.StaticVariantType.html
+/// [`glib::ToVariant`]: ../glib/variant/trait.ToVariant.html
+/// [`glib::FromVariant`]: ../glib/variant/trait.FromVariant.html
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn dbus_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = parse_macro_input!(attr as AttributeArgs);
+    let item = parse_macro_input!(item as syn::Item);
+    dbus_interface_attribute::impl_dbus_interface(&attr, item).into()
+}
+
+/// Attribute macro for typed access to [`gio::Settings`] of a schema.
+///
+/// The annotated struct is replaced by a newtype around `gio::Settings` with methods for each
+/// field, named after the field:
+///
+/// - `field()` returns the value of the key,
+/// - `set_field(value)` writes the key, failing if it is not writable,
+/// - `reset_field()` resets the key to its default value,
+/// - `bind_field(object, property)` returns a [`gio::BindingBuilder`] for the key,
+/// - `receive_field_changes()` returns a [`gio::SettingsChanges`] stream of new values.
+///
+/// The key of a field defaults to the field name with `_` replaced by `-` and can be overridden
+/// with `#[settings(key = "...")]`. Field types must implement [`glib::FromVariant`],
+/// [`glib::ToVariant`] and [`glib::StaticVariantType`].
+///
+/// If `schema_dir` is given as the directory relative to `OUT_DIR` that a build script compiled
+/// the schemas into, for example with `glib_build_tools::compile_schemas`, the keys and their
+/// types are checked against the compiled schema at compile time. In any case the schema is
+/// checked when the settings are created and a mismatch panics.
+///
+/// ```no_run
+/// #[gio::settings(id = "org.gtk_rs.Example")]
+/// struct ExampleSettings {
+///     window_width: i32,
+///     #[settings(key = "dark-mode")]
+///     dark: bool,
+/// }
+///
+/// let settings = ExampleSettings::new();
+/// settings.set_window_width(800).unwrap();
+/// assert_eq!(settings.window_width(), 800);
+/// let _changes = settings.receive_dark_changes();
+/// ```
+///
+/// [`gio::Settings`]: ../gio/struct.Settings.html
+/// [`gio::BindingBuilder`]: ../gio/struct.BindingBuilder.html
+/// [`gio::SettingsChanges`]: ../gio/struct.SettingsChanges.html
+/// [`glib::StaticVariantType`]: ../glib/variant/trait.StaticVariantType.html
+/// [`glib::ToVariant`]: ../glib/variant/trait.ToVariant.html
+/// [`glib::FromVariant`]: ../glib/variant/trait.FromVariant.html
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn settings(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = parse_macro_input!(attr as AttributeArgs);
+    let item = parse_macro_input!(item as syn::ItemStruct);
+    settings_attribute::impl_settings(&attr, item).into()
+}
+
 #[proc_macro]
 pub fn cstr_bytes(item: TokenStream) -> TokenStream {
     syn::parse::Parser::parse2(
@@ -0,0 +1,384 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use proc_macro_error::{abort, abort_call_site};
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Fields, GenericArgument, ItemStruct, Lit, LitStr, Meta, NestedMeta, PathArguments,
+    Type,
+};
+
+use crate::gvdb;
+
+pub const WRONG_PLACE_MSG: &str = "This macro should be used on a struct with named fields";
+
+struct Args {
+    id: String,
+    schema_dir: Option<LitStr>,
+}
+
+fn parse_args(args: &[NestedMeta]) -> Args {
+    let mut id = None;
+    let mut schema_dir = None;
+
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("id") => match &nv.lit {
+                Lit::Str(s) => id = Some(s.value()),
+                lit => abort!(lit, "Expected a string literal"),
+            },
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("schema_dir") => {
+                match &nv.lit {
+                    Lit::Str(s) => schema_dir = Some(s.clone()),
+                    lit => abort!(lit, "Expected a string literal"),
+                }
+            }
+            arg => abort!(arg, "Unknown settings attribute"),
+        }
+    }
+
+    Args {
+        id: id.unwrap_or_else(|| abort_call_site!("Missing schema `id`")),
+        schema_dir,
+    }
+}
+
+// Parse and remove the `#[settings(key = "...")]` attribute of a field
+fn take_key_attr(attrs: &mut Vec<Attribute>) -> Option<String> {
+    let mut key = None;
+
+    attrs.retain(|attr| {
+        if !attr.path.is_ident("settings") {
+            return true;
+        }
+
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => abort!(attr, "Expected `#[settings(...)]`"),
+        };
+        for nested in &list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("key") => match &nv.lit {
+                    Lit::Str(s) => key = Some(s.value()),
+                    lit => abort!(lit, "Expected a string literal"),
+                },
+                nested => abort!(nested, "Unknown settings key attribute"),
+            }
+        }
+
+        false
+    });
+
+    key
+}
+
+// The length of the first complete type in a GVariant type string.
+fn type_len(type_: &[u8]) -> Option<usize> {
+    match *type_.first()? {
+        b'a' | b'm' => Some(1 + type_len(&type_[1..])?),
+        open @ (b'(' | b'{') => {
+            let close = if open == b'(' { b')' } else { b'}' };
+            let mut len = 1;
+            while *type_.get(len)? != close {
+                len += type_len(&type_[len..])?;
+            }
+            Some(len + 1)
+        }
+        _ => Some(1),
+    }
+}
+
+// Look up the type of `key` in the compiled schema `id`, following `extends`.
+//
+// Keys are stored as a tuple of their default value and optional range, choices etc., the type
+// of the key is the type of the default value.
+fn lookup_key(root: &gvdb::Table, id: &str, key: &str) -> Result<Option<String>, String> {
+    let mut id = id.to_owned();
+    loop {
+        let schema = match root.table(&id)? {
+            Some(schema) => schema,
+            None => return Ok(None),
+        };
+        if let Some((type_, _)) = schema.value(key)? {
+            let len = type_
+                .strip_prefix('(')
+                .and_then(|t| type_len(t.as_bytes()))
+                .ok_or_else(|| format!("Invalid type {type_} of key {key}"))?;
+            return Ok(Some(type_[1..len + 1].to_owned()));
+        }
+        // Compiled schemas can only extend schemas defined before them, so there are no cycles
+        id = match schema.value(".extends")? {
+            Some(("s", data)) => std::str::from_utf8(data)
+                .ok()
+                .and_then(|s| s.strip_suffix('\0'))
+                .ok_or_else(|| format!("Invalid base schema of {id}"))?
+                .to_owned(),
+            _ => return Ok(None),
+        };
+    }
+}
+
+// The GVariant type string of well-known Rust types, `None` if it can only be determined at
+// runtime.
+fn variant_type_string(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Tuple(tuple) => {
+            let mut s = String::from("(");
+            for elem in &tuple.elems {
+                s.push_str(&variant_type_string(elem)?);
+            }
+            s.push(')');
+            Some(s)
+        }
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last()?;
+            let generic = |i: usize| match &segment.arguments {
+                PathArguments::AngleBracketed(args) => match args.args.iter().nth(i) {
+                    Some(GenericArgument::Type(ty)) => variant_type_string(ty),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let s = match segment.ident.to_string().as_str() {
+                "bool" => "b".into(),
+                "u8" => "y".into(),
+                "i16" => "n".into(),
+                "u16" => "q".into(),
+                "i32" => "i".into(),
+                "u32" => "u".into(),
+                "i64" => "x".into(),
+                "u64" => "t".into(),
+                "f64" => "d".into(),
+                "String" | "GString" => "s".into(),
+                "ObjectPath" => "o".into(),
+                "Signature" => "g".into(),
+                "Variant" => "v".into(),
+                "Vec" => format!("a{}", generic(0)?),
+                "StrV" => "as".into(),
+                "Option" => format!("m{}", generic(0)?),
+                "HashMap" | "BTreeMap" => format!("a{{{}{}}}", generic(0)?, generic(1)?),
+                _ => return None,
+            };
+            Some(s)
+        }
+        Type::Paren(paren) => variant_type_string(&paren.elem),
+        Type::Group(group) => variant_type_string(&group.elem),
+        _ => None,
+    }
+}
+
+struct Key {
+    ident: syn::Ident,
+    name: String,
+    ty: Type,
+}
+
+// The types of `keys` in the compiled schema `id`, `None` for keys that aren't in the schema.
+fn key_types(data: &[u8], id: &str, keys: &[Key]) -> Result<Vec<Option<String>>, String> {
+    let root = gvdb::Table::root(data)?;
+    if root.table(id)?.is_none() {
+        return Err(format!("Schema {id} not found"));
+    }
+    keys.iter()
+        .map(|key| lookup_key(&root, id, &key.name))
+        .collect()
+}
+
+pub fn impl_settings(args: &[NestedMeta], input: ItemStruct) -> TokenStream {
+    let args = parse_args(args);
+
+    let mut input = input;
+    let fields = match &mut input.fields {
+        Fields::Named(fields) => &mut fields.named,
+        _ => abort!(input, WRONG_PLACE_MSG),
+    };
+    let keys = fields
+        .iter_mut()
+        .map(|field| {
+            let ident = field.ident.clone().unwrap();
+            let name = take_key_attr(&mut field.attrs)
+                .unwrap_or_else(|| ident.to_string().replace('_', "-"));
+            Key {
+                ident,
+                name,
+                ty: field.ty.clone(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Validate the keys against the compiled schemas at compile time if they were given
+    let include_schema = args.schema_dir.as_ref().map(|schema_dir| {
+        let out_dir = std::env::var("OUT_DIR").unwrap_or_else(|_| {
+            abort!(
+                schema_dir,
+                "OUT_DIR is not set, the schemas must be compiled by a build script"
+            )
+        });
+        let path = PathBuf::from(out_dir)
+            .join(schema_dir.value())
+            .join("gschemas.compiled");
+        let data = std::fs::read(&path)
+            .unwrap_or_else(|err| abort!(schema_dir, "Failed to read {}: {}", path.display(), err));
+        let types = key_types(&data, &args.id, &keys)
+            .unwrap_or_else(|err| abort!(schema_dir, "{}: {}", path.display(), err));
+
+        for (key, type_) in keys.iter().zip(types) {
+            let type_ = match type_ {
+                Some(type_) => type_,
+                None => abort!(key.ident, "Schema {} has no key {}", args.id, key.name),
+            };
+            if let Some(rust_type) = variant_type_string(&key.ty) {
+                if rust_type != type_ {
+                    abort!(
+                        key.ty,
+                        "Key {} has type '{}' but the field has type '{}'",
+                        key.name,
+                        type_,
+                        rust_type
+                    );
+                }
+            }
+        }
+
+        // Recompile when the schemas change
+        let path = path.to_string_lossy().into_owned();
+        quote! {
+            const _: &[u8] = ::std::include_bytes!(#path);
+        }
+    });
+
+    let gio = crate::utils::gio_crate_ident_new();
+    let glib = quote!(#gio::glib);
+
+    let vis = &input.vis;
+    let attrs = &input.attrs;
+    let ident = &input.ident;
+    let id = &args.id;
+
+    let key_checks = keys.iter().map(|key| {
+        let name = &key.name;
+        let ty = &key.ty;
+        quote! {
+            ::std::assert!(
+                schema.has_key(#name),
+                "Schema {} has no key {}", Self::SCHEMA_ID, #name,
+            );
+            let value_type = schema.key(#name).value_type();
+            ::std::assert!(
+                value_type == *<#ty as #glib::StaticVariantType>::static_variant_type(),
+                "Key {} has type '{}' but the field has type '{}'",
+                #name,
+                value_type,
+                <#ty as #glib::StaticVariantType>::static_variant_type(),
+            );
+        }
+    });
+
+    let accessors = keys.iter().map(|key| {
+        let Key { ident, name, ty } = key;
+        // Fields like `type_` get `set_type()` etc.
+        let name_ident = ident.to_string();
+        let name_ident = name_ident.trim_end_matches('_');
+        let setter = format_ident!("set_{}", name_ident);
+        let reset = format_ident!("reset_{}", name_ident);
+        let bind = format_ident!("bind_{}", name_ident);
+        let receive = format_ident!("receive_{}_changes", name_ident);
+        let get_doc = format!("Returns the value of the `{name}` key.");
+        let set_doc = format!("Sets the `{name}` key to `value`.");
+        let reset_doc = format!("Resets the `{name}` key to its default value.");
+        let bind_doc = format!("Binds the `{name}` key to `property` of `object`.");
+        let receive_doc = format!("Returns a stream of the new values of the `{name}` key.");
+        quote! {
+            #[doc = #get_doc]
+            #vis fn #ident(&self) -> #ty {
+                #gio::prelude::SettingsExtManual::get::<#ty>(&self.0, #name)
+            }
+
+            #[doc = #set_doc]
+            #vis fn #setter(&self, value: #ty) -> ::std::result::Result<(), #glib::BoolError> {
+                #gio::prelude::SettingsExtManual::set(
+                    &self.0,
+                    #name,
+                    #glib::ToVariant::to_variant(&value),
+                )
+            }
+
+            #[doc = #reset_doc]
+            #vis fn #reset(&self) {
+                #gio::prelude::SettingsExt::reset(&self.0, #name)
+            }
+
+            #[doc = #bind_doc]
+            #vis fn #bind<'a, P: #glib::prelude::IsA<#glib::Object>>(
+                &'a self,
+                object: &'a P,
+                property: &'a str,
+            ) -> #gio::BindingBuilder<'a> {
+                #gio::prelude::SettingsExtManual::bind(&self.0, #name, object, property)
+            }
+
+            #[doc = #receive_doc]
+            #vis fn #receive(&self) -> #gio::SettingsChanges<#ty> {
+                #gio::prelude::SettingsExtManual::receive_changes::<#ty>(&self.0, #name)
+            }
+        }
+    });
+
+    let struct_doc = format!("Typed access to the settings of the `{id}` schema.");
+
+    quote! {
+        #[doc = #struct_doc]
+        #(#attrs)*
+        #[derive(Clone, Debug)]
+        #vis struct #ident(#gio::Settings);
+
+        #include_schema
+
+        #[allow(dead_code)]
+        impl #ident {
+            /// The id of the schema.
+            pub const SCHEMA_ID: &'static str = #id;
+
+            /// Creates the settings for the schema.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the schema is not installed or does not match the struct.
+            #vis fn new() -> Self {
+                Self::from_settings(#gio::Settings::new(Self::SCHEMA_ID))
+            }
+
+            /// Creates the settings for the relocatable schema at `path`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the schema is not installed or does not match the struct.
+            #vis fn with_path(path: &str) -> Self {
+                Self::from_settings(#gio::Settings::with_path(Self::SCHEMA_ID, path))
+            }
+
+            /// Wraps existing settings of the schema.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the settings are for a different schema or the schema does not match the
+            /// struct.
+            #vis fn from_settings(settings: #gio::Settings) -> Self {
+                let schema = #gio::prelude::SettingsExt::settings_schema(&settings)
+                    .expect("Settings without schema");
+                ::std::assert_eq!(schema.id(), Self::SCHEMA_ID, "Settings for the wrong schema");
+                #(#key_checks)*
+                Self(settings)
+            }
+
+            /// Returns the underlying settings.
+            #vis fn settings(&self) -> &#gio::Settings {
+                &self.0
+            }
+
+            #(#accessors)*
+        }
+    }
+}
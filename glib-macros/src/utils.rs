@@ -110,19 +110,27 @@ pub fn parse_item_attributes(attr_name: &str, attrs: &[Attribute]) -> Result<Vec
 }
 
 pub fn crate_ident_new() -> TokenStream {
+    named_crate_ident_new("glib")
+}
+
+pub fn gio_crate_ident_new() -> TokenStream {
+    named_crate_ident_new("gio")
+}
+
+fn named_crate_ident_new(crate_: &str) -> TokenStream {
     use proc_macro_crate::FoundCrate;
 
-    match crate_name("glib") {
+    match crate_name(crate_) {
         Ok(FoundCrate::Name(name)) => Some(name),
-        Ok(FoundCrate::Itself) => Some("glib".to_string()),
+        Ok(FoundCrate::Itself) => Some(crate_.to_string()),
         Err(_) => None,
     }
     .map(|s| {
-        let glib = Ident::new(&s, Span::call_site());
-        quote!(#glib)
+        let krate = Ident::new(&s, Span::call_site());
+        quote!(#krate)
     })
     .unwrap_or_else(|| {
-        // We couldn't find the glib crate (renamed or not) so let's just hope it's in scope!
+        // We couldn't find the crate (renamed or not) so let's just hope it's in scope!
         //
         // We will be able to have this information once this code is stable:
         //
@@ -132,9 +140,9 @@ pub fn crate_ident_new() -> TokenStream {
         // let file_path = source.path();
         // ```
         //
-        // Then we can use proc_macro to parse the file and check if glib is imported somehow.
-        let glib = Ident::new("glib", Span::call_site());
-        quote!(#glib)
+        // Then we can use proc_macro to parse the file and check if the crate is imported somehow.
+        let krate = Ident::new(crate_, Span::call_site());
+        quote!(#krate)
     })
 }
 
@@ -70,16 +70,6 @@ impl MainContext {
         unsafe { from_glib(ffi::g_main_context_is_owner(self.to_glib_none().0)) }
     }
 
-    #[doc(alias = "g_main_context_iteration")]
-    pub fn iteration(&self, may_block: bool) -> bool {
-        unsafe {
-            from_glib(ffi::g_main_context_iteration(
-                self.to_glib_none().0,
-                may_block.into_glib(),
-            ))
-        }
-    }
-
     #[doc(alias = "g_main_context_pending")]
     pub fn pending(&self) -> bool {
         unsafe { from_glib(ffi::g_main_context_pending(self.to_glib_none().0)) }
@@ -227,7 +227,11 @@ impl Closure {
                 slice::from_raw_parts(param_values as *const _, n_param_values as usize)
             };
             let callback: &F = &*(marshal_data as *mut _);
-            let result = callback(values);
+            let result = match crate::catch_callback_panic(|| callback(values)) {
+                Some(result) => result,
+                // The return value keeps its default value if the callback panicked
+                None => return,
+            };
 
             if return_value.is_null() {
                 assert!(
@@ -10,6 +10,7 @@ mod binding;
 #[cfg_attr(feature = "dox", doc(cfg(feature = "v2_72")))]
 mod binding_group;
 mod flags;
+mod multi_binding;
 #[cfg(any(feature = "v2_74", feature = "dox"))]
 #[cfg_attr(feature = "dox", doc(cfg(feature = "v2_74")))]
 mod signal_group;
@@ -18,5 +19,9 @@ mod signal_group;
 #[cfg_attr(feature = "dox", doc(cfg(feature = "v2_72")))]
 pub use binding_group::BindingGroupBuilder;
 
-pub use self::{auto::*, flags::*};
+pub use self::{
+    auto::*,
+    flags::*,
+    multi_binding::{MultiBinding, MultiBindingBuilder},
+};
 //pub use self::auto::functions::*;
@@ -0,0 +1,583 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+};
+
+use crate::{
+    object::WeakRefNotify, prelude::*, BoolError, Object, ParamFlags, ParamSpec, SignalHandlerId,
+    Value, WeakRef,
+};
+
+type TransformFn = Box<dyn Fn(&[Value]) -> Option<Value> + 'static>;
+
+// rustdoc-stripper-ignore-next
+/// A binding of one target property to the properties of multiple source objects.
+///
+/// Whenever one of the source properties changes, the values of all source properties are
+/// collected in the order the sources were added and passed to the transform function. The value
+/// returned from it is then set on the target property.
+///
+/// All objects are only referenced weakly. If any of the source objects or the target object is
+/// disposed, the binding is automatically unbound.
+///
+/// Contrary to [`Binding`](crate::Binding) this is not thread-safe: the transform function is
+/// called from the thread the source properties are changed on, which must be the thread the
+/// binding was created on.
+///
+/// ```no_run
+/// # use glib::prelude::*;
+/// # fn bind(a: &glib::Object, b: &glib::Object, target: &glib::Object) {
+/// let binding = glib::MultiBinding::builder(target, "visible")
+///     .source(a, "enabled")
+///     .source(b, "count")
+///     .transform(|values| {
+///         let enabled = values[0].get::<bool>().unwrap();
+///         let count = values[1].get::<u32>().unwrap();
+///         Some((enabled && count > 0).to_value())
+///     })
+///     .build();
+///
+/// // Later, remove the binding from all objects at once.
+/// binding.unbind();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MultiBinding {
+    inner: Rc<Inner>,
+}
+
+struct Source {
+    object: WeakRef<Object>,
+    pspec: ParamSpec,
+    handler: RefCell<Option<SignalHandlerId>>,
+    weak_notify: RefCell<Option<WeakRefNotify<Object>>>,
+}
+
+struct Inner {
+    target: WeakRef<Object>,
+    target_pspec: ParamSpec,
+    target_weak_notify: RefCell<Option<WeakRefNotify<Object>>>,
+    sources: Vec<Source>,
+    transform: TransformFn,
+    bound: Cell<bool>,
+    updating: Cell<bool>,
+}
+
+impl fmt::Debug for MultiBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiBinding")
+            .field("target", &self.inner.target.upgrade())
+            .field("target_property", &self.inner.target_pspec.name())
+            .field(
+                "sources",
+                &self
+                    .inner
+                    .sources
+                    .iter()
+                    .map(|s| (s.object.upgrade(), s.pspec.name()))
+                    .collect::<Vec<_>>(),
+            )
+            .field("bound", &self.inner.bound.get())
+            .finish()
+    }
+}
+
+impl MultiBinding {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new builder for a binding to the property `target_property` of `target`.
+    pub fn builder<'a>(
+        target: &'a impl IsA<Object>,
+        target_property: &'a str,
+    ) -> MultiBindingBuilder<'a> {
+        MultiBindingBuilder::new(target.upcast_ref(), target_property)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the target object of the binding, if it is still alive.
+    #[doc(alias = "get_target")]
+    pub fn target(&self) -> Option<Object> {
+        self.inner.target.upgrade()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the name of the target property.
+    #[doc(alias = "get_target_property")]
+    pub fn target_property(&self) -> &str {
+        self.inner.target_pspec.name()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the source objects and property names, in the order they were added.
+    ///
+    /// Sources that were already disposed are returned as `None`.
+    #[doc(alias = "get_sources")]
+    pub fn sources(&self) -> Vec<(Option<Object>, &str)> {
+        self.inner
+            .sources
+            .iter()
+            .map(|s| (s.object.upgrade(), s.pspec.name()))
+            .collect()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if the binding was not unbound yet.
+    pub fn is_bound(&self) -> bool {
+        self.inner.bound.get()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Recomputes the target property from the current values of the source properties.
+    ///
+    /// This does nothing if the binding was already unbound.
+    pub fn update(&self) {
+        self.inner.update();
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes the binding from all source objects and the target object.
+    ///
+    /// After this the target property is not updated anymore. Calling this multiple times is
+    /// allowed.
+    pub fn unbind(&self) {
+        self.inner.unbind();
+    }
+}
+
+impl Inner {
+    fn update(&self) {
+        if !self.bound.get() || self.updating.get() {
+            return;
+        }
+
+        let target = match self.target.upgrade() {
+            Some(target) => target,
+            None => {
+                self.unbind();
+                return;
+            }
+        };
+
+        let mut values = smallvec::SmallVec::<[Value; 4]>::with_capacity(self.sources.len());
+        for source in &self.sources {
+            match source.object.upgrade() {
+                Some(object) => values.push(object.property_value(source.pspec.name())),
+                None => {
+                    self.unbind();
+                    return;
+                }
+            }
+        }
+
+        let res = match (self.transform)(&values[..]) {
+            Some(res) => res,
+            None => return,
+        };
+        assert!(
+            res.type_().is_a(self.target_pspec.value_type()),
+            "Target property {} expected type {} but transform function returned {}",
+            self.target_pspec.name(),
+            self.target_pspec.value_type(),
+            res.type_()
+        );
+
+        // Setting the target property can notify one of the sources again if an object is both
+        // source and target, so guard against recursing into the transform function.
+        self.updating.set(true);
+        target.set_property_from_value(self.target_pspec.name(), &res);
+        self.updating.set(false);
+    }
+
+    fn unbind(&self) {
+        if !self.bound.replace(false) {
+            return;
+        }
+
+        for source in &self.sources {
+            let handler = source.handler.borrow_mut().take();
+            if let (Some(object), Some(handler)) = (source.object.upgrade(), handler) {
+                object.disconnect(handler);
+            }
+            if let Some(weak_notify) = source.weak_notify.borrow_mut().take() {
+                weak_notify.disconnect();
+            }
+        }
+
+        if let Some(weak_notify) = self.target_weak_notify.borrow_mut().take() {
+            weak_notify.disconnect();
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`MultiBinding`].
+#[must_use = "The builder must be built to be used"]
+pub struct MultiBindingBuilder<'a> {
+    target: &'a Object,
+    target_property: &'a str,
+    sources: Vec<(&'a Object, &'a str)>,
+    transform: Option<TransformFn>,
+    sync_create: bool,
+}
+
+impl<'a> fmt::Debug for MultiBindingBuilder<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiBindingBuilder")
+            .field("target", &self.target)
+            .field("target_property", &self.target_property)
+            .field("sources", &self.sources)
+            .field("sync_create", &self.sync_create)
+            .finish()
+    }
+}
+
+impl<'a> MultiBindingBuilder<'a> {
+    fn new(target: &'a Object, target_property: &'a str) -> Self {
+        Self {
+            target,
+            target_property,
+            sources: Vec::new(),
+            transform: None,
+            sync_create: true,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds the property `source_property` of `source` as an input of the binding.
+    ///
+    /// The value of this property is passed to the transform function at the same position as
+    /// this source was added.
+    pub fn source(mut self, source: &'a impl IsA<Object>, source_property: &'a str) -> Self {
+        self.sources.push((source.upcast_ref(), source_property));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Compute the target property value from the source property values with the given closure.
+    ///
+    /// If the closure returns `None` the target property is left unchanged.
+    pub fn transform<F: Fn(&[Value]) -> Option<Value> + 'static>(self, func: F) -> Self {
+        Self {
+            transform: Some(Box::new(func)),
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Whether to compute the target property value right away when building the binding.
+    ///
+    /// This is enabled by default.
+    pub fn sync_create(self, sync_create: bool) -> Self {
+        Self {
+            sync_create,
+            ..self
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Establish the property binding.
+    ///
+    /// This fails if any of the provided properties does not exist, a source property is not
+    /// readable, the target property is not writable, no sources were added or if no transform
+    /// function was provided.
+    pub fn try_build(self) -> Result<MultiBinding, BoolError> {
+        let transform = self
+            .transform
+            .ok_or_else(|| bool_error!("No transform function provided"))?;
+
+        if self.sources.is_empty() {
+            return Err(bool_error!("No source properties provided"));
+        }
+
+        let target_pspec = self
+            .target
+            .find_property(self.target_property)
+            .ok_or_else(|| {
+                bool_error!(
+                    "Target property {} on type {} not found",
+                    self.target_property,
+                    self.target.type_()
+                )
+            })?;
+        if !target_pspec.flags().contains(ParamFlags::WRITABLE)
+            || target_pspec.flags().contains(ParamFlags::CONSTRUCT_ONLY)
+        {
+            return Err(bool_error!(
+                "Target property {} on type {} is not writable",
+                self.target_property,
+                self.target.type_()
+            ));
+        }
+
+        let sources = self
+            .sources
+            .iter()
+            .map(|&(source, source_property)| {
+                let pspec = source.find_property(source_property).ok_or_else(|| {
+                    bool_error!(
+                        "Source property {} on type {} not found",
+                        source_property,
+                        source.type_()
+                    )
+                })?;
+                if !pspec.flags().contains(ParamFlags::READABLE) {
+                    return Err(bool_error!(
+                        "Source property {} on type {} is not readable",
+                        source_property,
+                        source.type_()
+                    ));
+                }
+
+                Ok(Source {
+                    object: source.downgrade(),
+                    pspec,
+                    handler: RefCell::new(None),
+                    weak_notify: RefCell::new(None),
+                })
+            })
+            .collect::<Result<Vec<_>, BoolError>>()?;
+
+        let inner = Rc::new(Inner {
+            target: self.target.downgrade(),
+            target_pspec,
+            target_weak_notify: RefCell::new(None),
+            sources,
+            transform,
+            bound: Cell::new(true),
+            updating: Cell::new(false),
+        });
+
+        for (source, &(object, _)) in inner.sources.iter().zip(self.sources.iter()) {
+            let inner_clone = inner.clone();
+            let handler = object.connect_notify_local(Some(source.pspec.name()), move |_, _| {
+                inner_clone.update();
+            });
+            *source.handler.borrow_mut() = Some(handler);
+
+            let inner_clone = inner.clone();
+            let weak_notify = object.add_weak_ref_notify_local(move || inner_clone.unbind());
+            *source.weak_notify.borrow_mut() = Some(weak_notify);
+        }
+
+        let inner_clone = inner.clone();
+        let weak_notify = self
+            .target
+            .add_weak_ref_notify_local(move || inner_clone.unbind());
+        *inner.target_weak_notify.borrow_mut() = Some(weak_notify);
+
+        if self.sync_create {
+            inner.update();
+        }
+
+        Ok(MultiBinding { inner })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Similar to `try_build` but panics instead of failing.
+    pub fn build(self) -> MultiBinding {
+        self.try_build().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{prelude::*, subclass::prelude::*};
+
+    #[test]
+    fn multi_binding() {
+        let a = TestObject::default();
+        let b = TestObject::default();
+        let target = TestObject::default();
+
+        a.set_name("Hello");
+        b.set_name("World");
+
+        let binding = crate::MultiBinding::builder(&target, "name")
+            .source(&a, "name")
+            .source(&b, "name")
+            .transform(|values| {
+                let a = values[0].get::<&str>().unwrap();
+                let b = values[1].get::<&str>().unwrap();
+                Some(format!("{a} {b}").to_value())
+            })
+            .build();
+        assert!(binding.is_bound());
+        assert_eq!(target.name(), "Hello World");
+
+        a.set_name("Goodbye");
+        assert_eq!(target.name(), "Goodbye World");
+
+        b.set_name("Moon");
+        assert_eq!(target.name(), "Goodbye Moon");
+
+        binding.unbind();
+        assert!(!binding.is_bound());
+
+        a.set_name("Hello");
+        assert_eq!(target.name(), "Goodbye Moon");
+    }
+
+    #[test]
+    fn multi_binding_change_type() {
+        let a = TestObject::default();
+        let b = TestObject::default();
+        let target = TestObject::default();
+
+        crate::MultiBinding::builder(&target, "enabled")
+            .source(&a, "enabled")
+            .source(&b, "name")
+            .transform(|values| {
+                let enabled = values[0].get::<bool>().unwrap();
+                let name = values[1].get::<&str>().unwrap();
+                Some((enabled && !name.is_empty()).to_value())
+            })
+            .build();
+        assert!(!target.enabled());
+
+        a.set_enabled(true);
+        assert!(!target.enabled());
+
+        b.set_name("Hello");
+        assert!(target.enabled());
+
+        a.set_enabled(false);
+        assert!(!target.enabled());
+    }
+
+    #[test]
+    fn multi_binding_source_disposed() {
+        let a = TestObject::default();
+        let b = TestObject::default();
+        let target = TestObject::default();
+
+        let binding = crate::MultiBinding::builder(&target, "name")
+            .source(&a, "name")
+            .source(&b, "name")
+            .transform(|values| {
+                let a = values[0].get::<&str>().unwrap();
+                let b = values[1].get::<&str>().unwrap();
+                Some(format!("{a}{b}").to_value())
+            })
+            .build();
+
+        b.set_name("World");
+        assert_eq!(target.name(), "World");
+
+        drop(b);
+        assert!(!binding.is_bound());
+        assert!(binding.sources()[1].0.is_none());
+
+        a.set_name("Hello");
+        assert_eq!(target.name(), "World");
+    }
+
+    #[test]
+    fn multi_binding_invalid_property() {
+        let a = TestObject::default();
+        let target = TestObject::default();
+
+        assert!(crate::MultiBinding::builder(&target, "name")
+            .source(&a, "does-not-exist")
+            .transform(|_| None)
+            .try_build()
+            .is_err());
+        assert!(crate::MultiBinding::builder(&target, "does-not-exist")
+            .source(&a, "name")
+            .transform(|_| None)
+            .try_build()
+            .is_err());
+        assert!(crate::MultiBinding::builder(&target, "name")
+            .source(&a, "name")
+            .try_build()
+            .is_err());
+    }
+
+    mod imp {
+        use std::cell::RefCell;
+
+        use once_cell::sync::Lazy;
+
+        use super::*;
+        use crate as glib;
+
+        #[derive(Debug, Default)]
+        pub struct TestObject {
+            pub name: RefCell<String>,
+            pub enabled: RefCell<bool>,
+        }
+
+        #[crate::object_subclass]
+        impl ObjectSubclass for TestObject {
+            const NAME: &'static str = "TestMultiBinding";
+            type Type = super::TestObject;
+        }
+
+        impl ObjectImpl for TestObject {
+            fn properties() -> &'static [crate::ParamSpec] {
+                static PROPERTIES: Lazy<Vec<crate::ParamSpec>> = Lazy::new(|| {
+                    vec![
+                        crate::ParamSpecString::builder("name")
+                            .explicit_notify()
+                            .build(),
+                        crate::ParamSpecBoolean::builder("enabled")
+                            .explicit_notify()
+                            .build(),
+                    ]
+                });
+                PROPERTIES.as_ref()
+            }
+
+            fn property(&self, _id: usize, pspec: &crate::ParamSpec) -> crate::Value {
+                let obj = self.obj();
+                match pspec.name() {
+                    "name" => obj.name().to_value(),
+                    "enabled" => obj.enabled().to_value(),
+                    _ => unimplemented!(),
+                }
+            }
+
+            fn set_property(&self, _id: usize, value: &crate::Value, pspec: &crate::ParamSpec) {
+                let obj = self.obj();
+                match pspec.name() {
+                    "name" => obj.set_name(value.get().unwrap()),
+                    "enabled" => obj.set_enabled(value.get().unwrap()),
+                    _ => unimplemented!(),
+                };
+            }
+        }
+    }
+
+    crate::wrapper! {
+        pub struct TestObject(ObjectSubclass<imp::TestObject>);
+    }
+
+    impl Default for TestObject {
+        fn default() -> Self {
+            crate::Object::new(&[])
+        }
+    }
+
+    impl TestObject {
+        fn name(&self) -> String {
+            self.imp().name.borrow().clone()
+        }
+
+        fn set_name(&self, name: &str) {
+            if name != self.imp().name.replace(name.to_string()).as_str() {
+                self.notify("name");
+            }
+        }
+
+        fn enabled(&self) -> bool {
+            *self.imp().enabled.borrow()
+        }
+
+        fn set_enabled(&self, enabled: bool) {
+            if enabled != self.imp().enabled.replace(enabled) {
+                self.notify("enabled");
+            }
+        }
+    }
+}
@@ -28,7 +28,7 @@ pub use self::{
     error::{BoolError, Error},
     object::{
         BorrowedObject, Cast, CastNone, Class, InitiallyUnowned, Interface, IsA, Object, ObjectExt,
-        ObjectType, SendWeakRef, WeakRef,
+        ObjectType, PropertyError, SendWeakRef, WeakRef,
     },
     signal::{
         signal_handler_block, signal_handler_disconnect, signal_handler_unblock,
@@ -133,6 +133,8 @@ mod enums;
 mod functions;
 pub use self::functions::*;
 mod key_file;
+mod mapped_file;
+pub use self::mapped_file::MappedFile;
 pub mod prelude;
 pub mod signal;
 pub mod source;
@@ -145,7 +147,13 @@ mod gstring_builder;
 pub use self::gstring_builder::GStringBuilder;
 pub mod types;
 mod unicollate;
+#[cfg(any(feature = "v2_66", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_66")))]
+mod uri;
 pub use self::unicollate::{CollationKey, FilenameCollationKey};
+#[cfg(any(feature = "v2_66", feature = "dox"))]
+#[cfg_attr(feature = "dox", doc(cfg(feature = "v2_66")))]
+pub use self::uri::{UriBuilder, UriParamsIter};
 mod utils;
 pub use self::utils::*;
 mod main_context;
@@ -200,6 +208,11 @@ pub mod subclass;
 
 mod main_context_futures;
 pub use main_context_futures::{JoinError, JoinHandle};
+mod panic_policy;
+pub use self::panic_policy::{
+    catch_callback_panic, panic_policy, resume_pending_panic, set_panic_policy, take_pending_panic,
+    PanicPolicy,
+};
 mod source_futures;
 pub use self::source_futures::*;
 
@@ -31,6 +31,25 @@ impl MainContext {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Runs a single iteration of the main context.
+    ///
+    /// Returns `true` if events were dispatched.
+    ///
+    /// If a callback panicked during the iteration and the panic was stored because of
+    /// [`PanicPolicy::Reraise`](crate::PanicPolicy::Reraise), the panic is resumed afterwards.
+    #[doc(alias = "g_main_context_iteration")]
+    pub fn iteration(&self, may_block: bool) -> bool {
+        let res = unsafe {
+            from_glib(ffi::g_main_context_iteration(
+                self.to_glib_none().0,
+                may_block.into_glib(),
+            ))
+        };
+        crate::resume_pending_panic();
+        res
+    }
+
     // rustdoc-stripper-ignore-next
     /// Invokes `func` on the main context.
     ///
@@ -529,6 +529,8 @@ impl MainContext {
 
         l.run();
 
+        crate::resume_pending_panic();
+
         match res.unwrap() {
             Ok(v) => v,
             Err(e) => panic::resume_unwind(e),
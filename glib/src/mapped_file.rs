@@ -0,0 +1,216 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+#[cfg(any(unix, feature = "dox"))]
+use std::os::unix::io::AsRawFd;
+use std::{fmt, ops::Deref, path::Path, ptr, slice};
+
+use crate::{translate::*, Bytes, Error};
+
+wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// A file mapped into memory.
+    ///
+    /// The mapping is kept alive as long as the `MappedFile` or any [`Bytes`] returned by
+    /// [`MappedFile::bytes`] are alive, which allows using the file contents without copying
+    /// them, e.g. with [`Variant::from_bytes`](crate::Variant::from_bytes).
+    ///
+    /// ```no_run
+    /// let file = glib::MappedFile::new("data.gvariant", false).unwrap();
+    /// let variant = glib::Variant::from_bytes::<Vec<String>>(&file.bytes());
+    /// ```
+    #[doc(alias = "GMappedFile")]
+    pub struct MappedFile(Shared<ffi::GMappedFile>);
+
+    match fn {
+        ref => |ptr| ffi::g_mapped_file_ref(ptr),
+        unref => |ptr| ffi::g_mapped_file_unref(ptr),
+        type_ => || ffi::g_mapped_file_get_type(),
+    }
+}
+
+impl MappedFile {
+    // rustdoc-stripper-ignore-next
+    /// Maps the file at `filename` into memory.
+    ///
+    /// If `writable` is `true` the mapping is private: modifications of the mapped memory are
+    /// not written back to the file and are not visible to other processes.
+    #[doc(alias = "g_mapped_file_new")]
+    pub fn new(filename: impl AsRef<Path>, writable: bool) -> Result<MappedFile, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::g_mapped_file_new(
+                filename.as_ref().to_glib_none().0,
+                writable.into_glib(),
+                &mut error,
+            );
+            if error.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Maps the file referred to by `fd` into memory.
+    ///
+    /// The file descriptor is not closed and can be closed once this function returned.
+    #[cfg(any(unix, feature = "dox"))]
+    #[cfg_attr(feature = "dox", doc(cfg(unix)))]
+    #[doc(alias = "g_mapped_file_new_from_fd")]
+    pub fn from_fd(fd: &impl AsRawFd, writable: bool) -> Result<MappedFile, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret =
+                ffi::g_mapped_file_new_from_fd(fd.as_raw_fd(), writable.into_glib(), &mut error);
+            if error.is_null() {
+                Ok(from_glib_full(ret))
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the contents of the mapped file.
+    #[doc(alias = "g_mapped_file_get_contents")]
+    #[doc(alias = "get_contents")]
+    pub fn contents(&self) -> &[u8] {
+        unsafe {
+            let ptr = ffi::g_mapped_file_get_contents(self.to_glib_none().0);
+            let len = ffi::g_mapped_file_get_length(self.to_glib_none().0);
+            // Empty files are not mapped and return NULL
+            if ptr.is_null() || len == 0 {
+                &[]
+            } else {
+                slice::from_raw_parts(ptr as *const u8, len)
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the length of the mapped file in bytes.
+    #[doc(alias = "g_mapped_file_get_length")]
+    #[doc(alias = "get_length")]
+    pub fn len(&self) -> usize {
+        unsafe { ffi::g_mapped_file_get_length(self.to_glib_none().0) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if the mapped file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the contents of the mapped file as [`Bytes`] without copying them.
+    ///
+    /// The returned [`Bytes`] keep the mapping alive.
+    #[doc(alias = "g_mapped_file_get_bytes")]
+    #[doc(alias = "get_bytes")]
+    pub fn bytes(&self) -> Bytes {
+        unsafe { from_glib_full(ffi::g_mapped_file_get_bytes(self.to_glib_none().0)) }
+    }
+}
+
+unsafe impl Send for MappedFile {}
+unsafe impl Sync for MappedFile {}
+
+impl fmt::Debug for MappedFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MappedFile")
+            .field("ptr", &ToGlibPtr::<*const _>::to_glib_none(self).0)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl AsRef<[u8]> for MappedFile {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.contents()
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.contents()
+    }
+}
+
+impl From<MappedFile> for Bytes {
+    #[inline]
+    fn from(file: MappedFile) -> Bytes {
+        file.bytes()
+    }
+}
+
+impl From<&MappedFile> for Bytes {
+    #[inline]
+    fn from(file: &MappedFile) -> Bytes {
+        file.bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::{ToVariant, Variant};
+
+    #[test]
+    fn contents() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"Hello World").unwrap();
+        file.flush().unwrap();
+
+        let mapped = MappedFile::new(file.path(), false).unwrap();
+        assert_eq!(mapped.len(), 11);
+        assert_eq!(mapped.contents(), b"Hello World");
+        assert_eq!(&*mapped, b"Hello World");
+
+        let bytes = mapped.bytes();
+        drop(mapped);
+        assert_eq!(bytes, b"Hello World"[..]);
+    }
+
+    #[test]
+    fn empty() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+
+        let mapped = MappedFile::new(file.path(), false).unwrap();
+        assert!(mapped.is_empty());
+        assert_eq!(mapped.contents(), b"");
+        assert!(mapped.bytes().is_empty());
+    }
+
+    #[test]
+    fn not_found() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(MappedFile::new(dir.path().join("does-not-exist"), false).is_err());
+    }
+
+    #[test]
+    fn variant() {
+        let variant = vec!["Hello", "World"].to_variant();
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(variant.data()).unwrap();
+        file.flush().unwrap();
+
+        #[cfg(unix)]
+        let mapped = MappedFile::from_fd(file.as_file(), false).unwrap();
+        #[cfg(not(unix))]
+        let mapped = MappedFile::new(file.path(), false).unwrap();
+        let loaded = Variant::from_bytes::<Vec<String>>(&mapped.bytes());
+        assert_eq!(loaded, variant);
+        assert_eq!(
+            loaded.get::<Vec<String>>().unwrap(),
+            vec![String::from("Hello"), String::from("World")]
+        );
+    }
+}
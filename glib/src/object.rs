@@ -16,6 +16,25 @@ use crate::{
     Closure, IntoGStr, PtrSlice, RustClosure, SignalHandlerId, Type, Value,
 };
 
+// rustdoc-stripper-ignore-next
+/// Error returned when setting a property fails.
+#[derive(thiserror::Error, Clone, PartialEq, Eq, Debug)]
+pub enum PropertyError {
+    #[error("property '{property}' of type '{type_}' not found")]
+    NotFound { type_: Type, property: String },
+    #[error("property '{property}' of type '{type_}' is not writable")]
+    NotWritable { type_: Type, property: String },
+    #[error("property '{property}' of type '{type_}' can't be set from the given type (expected: '{expected}', got: '{actual}')")]
+    WrongType {
+        type_: Type,
+        property: String,
+        expected: Type,
+        actual: Type,
+    },
+    #[error("property '{property}' of type '{type_}' can't be set from given value, it is invalid or out of range")]
+    InvalidValue { type_: Type, property: String },
+}
+
 // rustdoc-stripper-ignore-next
 /// Implemented by types representing `glib::Object` and subclasses of it.
 pub unsafe trait ObjectType:
@@ -1632,6 +1651,34 @@ pub trait ObjectExt: ObjectType {
     #[doc(alias = "g_object_set_property")]
     fn set_property_from_value(&self, property_name: &str, value: &Value);
 
+    // rustdoc-stripper-ignore-next
+    /// Sets the property `property_name` of the object to value `value`.
+    ///
+    /// Contrary to [`set_property`][`ObjectExt::set_property`] this returns an error instead of
+    /// panicking if the property does not exist, the type of the property is different than the
+    /// provided value, the property is not writable, or if the value is rejected by the validation
+    /// of the property's [`ParamSpec`](crate::ParamSpec).
+    #[doc(alias = "g_object_set_property")]
+    fn try_set_property(
+        &self,
+        property_name: &str,
+        value: impl Into<Value>,
+    ) -> Result<(), PropertyError>;
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the property `property_name` of the object to value `value`.
+    ///
+    /// Contrary to [`set_property_from_value`][`ObjectExt::set_property_from_value`] this returns
+    /// an error instead of panicking if the property does not exist, the type of the property is
+    /// different than the provided value, the property is not writable, or if the value is
+    /// rejected by the validation of the property's [`ParamSpec`](crate::ParamSpec).
+    #[doc(alias = "g_object_set_property")]
+    fn try_set_property_from_value(
+        &self,
+        property_name: &str,
+        value: &Value,
+    ) -> Result<(), PropertyError>;
+
     // rustdoc-stripper-ignore-next
     /// Sets multiple properties of the object at once.
     ///
@@ -2277,6 +2324,39 @@ impl<T: ObjectType> ObjectExt for T {
         }
     }
 
+    fn try_set_property(
+        &self,
+        property_name: &str,
+        value: impl Into<Value>,
+    ) -> Result<(), PropertyError> {
+        self.try_set_property_from_value(property_name, &value.into())
+    }
+
+    fn try_set_property_from_value(
+        &self,
+        property_name: &str,
+        value: &Value,
+    ) -> Result<(), PropertyError> {
+        let pspec = self
+            .find_property(property_name)
+            .ok_or_else(|| PropertyError::NotFound {
+                type_: self.type_(),
+                property: property_name.to_owned(),
+            })?;
+
+        let mut property_value = value.clone();
+        try_validate_property_type(self.type_(), false, &pspec, &mut property_value)?;
+        unsafe {
+            gobject_ffi::g_object_set_property(
+                self.as_object_ref().to_glib_none().0,
+                pspec.name().as_ptr() as *const _,
+                property_value.to_glib_none().0,
+            );
+        }
+
+        Ok(())
+    }
+
     #[track_caller]
     fn set_properties(&self, property_values: &[(&str, &dyn ToValue)]) {
         let pspecs = self.list_properties();
@@ -2988,10 +3068,12 @@ impl<T: ObjectType> ObjectExt for T {
             P: ObjectType,
         {
             let f: &F = &*(f as *const F);
-            f(
-                Object::from_glib_borrow(this).unsafe_cast_ref(),
-                &from_glib_borrow(param_spec),
-            )
+            crate::catch_callback_panic(|| {
+                f(
+                    Object::from_glib_borrow(this).unsafe_cast_ref(),
+                    &from_glib_borrow(param_spec),
+                )
+            });
         }
 
         let signal_name = if let Some(name) = name {
@@ -3143,13 +3225,25 @@ fn validate_property_type(
     pspec: &crate::ParamSpec,
     property_value: &mut Value,
 ) {
+    if let Err(err) = try_validate_property_type(type_, allow_construct_only, pspec, property_value)
+    {
+        panic!("{err}");
+    }
+}
+
+fn try_validate_property_type(
+    type_: Type,
+    allow_construct_only: bool,
+    pspec: &crate::ParamSpec,
+    property_value: &mut Value,
+) -> Result<(), PropertyError> {
     if !pspec.flags().contains(crate::ParamFlags::WRITABLE)
         || (!allow_construct_only && pspec.flags().contains(crate::ParamFlags::CONSTRUCT_ONLY))
     {
-        panic!(
-            "property '{}' of type '{type_}' is not writable",
-            pspec.name(),
-        );
+        return Err(PropertyError::NotWritable {
+            type_,
+            property: pspec.name().to_owned(),
+        });
     }
 
     unsafe {
@@ -3164,11 +3258,12 @@ fn validate_property_type(
 
         if !valid_type {
             if let Err(got) = coerce_object_type(property_value, pspec.value_type()) {
-                panic!(
-                        "property '{}' of type '{type_}' can't be set from the given type (expected: '{}', got: '{got}')",
-                        pspec.name(),
-                        pspec.value_type(),
-                    );
+                return Err(PropertyError::WrongType {
+                    type_,
+                    property: pspec.name().to_owned(),
+                    expected: pspec.value_type(),
+                    actual: got,
+                });
             }
         }
 
@@ -3178,12 +3273,14 @@ fn validate_property_type(
         ));
         let change_allowed = pspec.flags().contains(crate::ParamFlags::LAX_VALIDATION);
         if changed && !change_allowed {
-            panic!(
-                "property '{}' of type '{type_}' can't be set from given value, it is invalid or out of range",
-                pspec.name(),
-            );
+            return Err(PropertyError::InvalidValue {
+                type_,
+                property: pspec.name().to_owned(),
+            });
         }
     }
+
+    Ok(())
 }
 
 // If it's not directly a valid type but an object type, we check if the
@@ -0,0 +1,199 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Handling of panics in Rust callbacks that are called from C.
+//!
+//! A panic that unwinds out of a callback called by GLib can't continue unwinding through the C
+//! code and aborts the process. [`set_panic_policy`] allows configuring what happens instead for
+//! callbacks that are invoked through the trampolines of these bindings. This covers
+//!
+//! * closures ([`RustClosure`](crate::RustClosure)), which includes signal handlers connected
+//!   with [`ObjectExt::connect`](crate::object::ObjectExt::connect),
+//!   [`ObjectExt::connect_local`](crate::object::ObjectExt::connect_local) or
+//!   [`ObjectExt::connect_closure`](crate::object::ObjectExt::connect_closure) and the class
+//!   handlers of signals defined in Rust,
+//! * handlers of the `notify` signal connected with
+//!   [`ObjectExt::connect_notify`](crate::object::ObjectExt::connect_notify) and its variants,
+//! * the [`ObjectImpl`](crate::subclass::prelude::ObjectImpl) virtual methods of subclasses,
+//! * main context sources (e.g. [`idle_add`](crate::idle_add) or
+//!   [`timeout_add`](crate::timeout_add)).
+//!
+//! Signal handlers connected through the generated `connect_*` methods of other types and the
+//! virtual methods of other subclassing traits are not covered and still unwind into C.
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Once,
+    },
+};
+
+// rustdoc-stripper-ignore-next
+/// What to do with panics in callbacks that are called from C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum PanicPolicy {
+    // rustdoc-stripper-ignore-next
+    /// Don't catch the panic.
+    ///
+    /// The panic unwinds until it reaches C code, which aborts the process. This is the default.
+    #[default]
+    Unwind,
+    // rustdoc-stripper-ignore-next
+    /// Catch the panic, log it as an error and abort the process.
+    ///
+    /// The panic message and its location are logged via the GLib logging system before aborting,
+    /// together with the backtrace of the panic if the `backtrace` feature is enabled. The
+    /// location and backtrace are captured by a panic hook that is installed when this policy is
+    /// set, so they are missing if the panic hook is replaced afterwards.
+    Abort,
+    // rustdoc-stripper-ignore-next
+    /// Catch the panic, log it as a critical warning and continue.
+    ///
+    /// The value returned to C is the same as if the callback did not exist, e.g. the default
+    /// value for closures or removing the source for main context sources.
+    Critical,
+    // rustdoc-stripper-ignore-next
+    /// Catch the panic, store it and re-raise it later on the same thread.
+    ///
+    /// The panic is resumed by the next call to [`MainContext::iteration`] or
+    /// [`MainContext::block_on`] on the thread the panic happened, or explicitly by calling
+    /// [`resume_pending_panic`]. Until then the value returned to C is the same as for
+    /// [`PanicPolicy::Critical`].
+    ///
+    /// This is mostly useful for tests so that panics in callbacks fail the test cleanly.
+    ///
+    /// [`MainContext::iteration`]: crate::MainContext::iteration
+    /// [`MainContext::block_on`]: crate::MainContext::block_on
+    Reraise,
+}
+
+static POLICY: AtomicU8 = AtomicU8::new(PanicPolicy::Unwind as u8);
+
+static PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    static PENDING_PANIC: RefCell<Option<Box<dyn Any + Send + 'static>>> = RefCell::new(None);
+    // Number of callbacks currently running inside `catch_callback_panic` on this thread
+    static CALLBACK_DEPTH: Cell<usize> = Cell::new(0);
+    // Location and backtrace of the last panic in a callback, captured by the panic hook
+    static PANIC_DETAILS: RefCell<Option<String>> = RefCell::new(None);
+}
+
+// rustdoc-stripper-ignore-next
+/// Sets the global policy for panics in callbacks that are called from C.
+pub fn set_panic_policy(policy: PanicPolicy) {
+    if policy == PanicPolicy::Abort {
+        install_panic_hook();
+    }
+    POLICY.store(policy as u8, Ordering::SeqCst);
+}
+
+// Captures the details of panics in callbacks, which are gone once the panic is caught
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if panic_policy() == PanicPolicy::Abort && CALLBACK_DEPTH.with(|d| d.get()) > 0 {
+                let location = info
+                    .location()
+                    .map(|l| l.to_string())
+                    .unwrap_or_else(|| "<unknown>".into());
+                #[cfg(feature = "backtrace")]
+                let details = format!("at {location}\n{:?}", backtrace::Backtrace::new());
+                #[cfg(not(feature = "backtrace"))]
+                let details = format!("at {location}");
+                PANIC_DETAILS.with(|d| *d.borrow_mut() = Some(details));
+            }
+            previous(info);
+        }));
+    });
+}
+
+// rustdoc-stripper-ignore-next
+/// Returns the global policy for panics in callbacks that are called from C.
+pub fn panic_policy() -> PanicPolicy {
+    match POLICY.load(Ordering::SeqCst) {
+        x if x == PanicPolicy::Abort as u8 => PanicPolicy::Abort,
+        x if x == PanicPolicy::Critical as u8 => PanicPolicy::Critical,
+        x if x == PanicPolicy::Reraise as u8 => PanicPolicy::Reraise,
+        _ => PanicPolicy::Unwind,
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Takes the panic that was stored on this thread by [`PanicPolicy::Reraise`], if any.
+pub fn take_pending_panic() -> Option<Box<dyn Any + Send + 'static>> {
+    PENDING_PANIC.with(|p| p.borrow_mut().take())
+}
+
+// rustdoc-stripper-ignore-next
+/// Resumes the panic that was stored on this thread by [`PanicPolicy::Reraise`], if any.
+pub fn resume_pending_panic() {
+    if let Some(panic) = take_pending_panic() {
+        panic::resume_unwind(panic);
+    }
+}
+
+fn panic_message<'a>(panic: &'a (dyn Any + Send + 'static)) -> &'a str {
+    if let Some(s) = panic.downcast_ref::<&'static str>() {
+        s
+    } else if let Some(s) = panic.downcast_ref::<String>() {
+        s.as_str()
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Calls `f` and handles panics according to the current [`PanicPolicy`].
+///
+/// This is meant to be used by trampolines that call Rust callbacks from C. If a panic was caught
+/// and the policy allows continuing, `None` is returned and the caller has to return a suitable
+/// fallback value to C.
+pub fn catch_callback_panic<R, F: FnOnce() -> R>(f: F) -> Option<R> {
+    let policy = panic_policy();
+    if policy == PanicPolicy::Unwind {
+        return Some(f());
+    }
+
+    CALLBACK_DEPTH.with(|d| d.set(d.get() + 1));
+    let res = panic::catch_unwind(AssertUnwindSafe(f));
+    CALLBACK_DEPTH.with(|d| d.set(d.get() - 1));
+    let panic = match res {
+        Ok(res) => return Some(res),
+        Err(panic) => panic,
+    };
+
+    match policy {
+        PanicPolicy::Abort => {
+            let details = PANIC_DETAILS
+                .with(|d| d.borrow_mut().take())
+                .unwrap_or_else(|| "at an unknown location".into());
+            crate::g_critical!(
+                "GLib-Rust",
+                "Aborting after panic in callback: {} {}",
+                panic_message(&*panic),
+                details
+            );
+            std::process::abort();
+        }
+        PanicPolicy::Reraise => {
+            PENDING_PANIC.with(|p| {
+                let mut p = p.borrow_mut();
+                // Only keep the first panic, everything after it is likely a consequence
+                if p.is_none() {
+                    *p = Some(panic);
+                }
+            });
+        }
+        _ => {
+            crate::g_critical!("GLib-Rust", "Panic in callback: {}", panic_message(&*panic));
+        }
+    }
+
+    None
+}
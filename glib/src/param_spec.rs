@@ -167,6 +167,36 @@ impl ParamSpec {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Validates `value` against this `ParamSpec` and modifies it to be valid if necessary.
+    ///
+    /// Returns `true` if the value was modified.
+    #[doc(alias = "g_param_value_validate")]
+    #[inline]
+    pub fn value_validate(&self, value: &mut Value) -> bool {
+        unsafe {
+            from_glib(gobject_ffi::g_param_value_validate(
+                self.to_glib_none().0,
+                value.to_glib_none_mut().0,
+            ))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Compares two values of the value type of this `ParamSpec`.
+    #[doc(alias = "g_param_values_cmp")]
+    #[inline]
+    pub fn values_cmp(&self, value1: &Value, value2: &Value) -> std::cmp::Ordering {
+        unsafe {
+            gobject_ffi::g_param_values_cmp(
+                self.to_glib_none().0,
+                value1.to_glib_none().0,
+                value2.to_glib_none().0,
+            )
+            .cmp(&0)
+        }
+    }
+
     #[doc(alias = "get_owner_type")]
     #[inline]
     pub fn owner_type(&self) -> crate::Type {
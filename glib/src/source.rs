@@ -104,14 +104,18 @@ unsafe extern "C" fn trampoline<F: FnMut() -> Continue + Send + 'static>(
     func: gpointer,
 ) -> gboolean {
     let func: &RefCell<F> = &*(func as *const RefCell<F>);
-    (*func.borrow_mut())().into_glib()
+    crate::catch_callback_panic(|| (*func.borrow_mut())())
+        .unwrap_or(Continue(false))
+        .into_glib()
 }
 
 unsafe extern "C" fn trampoline_local<F: FnMut() -> Continue + 'static>(
     func: gpointer,
 ) -> gboolean {
     let func: &ThreadGuard<RefCell<F>> = &*(func as *const ThreadGuard<RefCell<F>>);
-    (*func.get_ref().borrow_mut())().into_glib()
+    crate::catch_callback_panic(|| (*func.get_ref().borrow_mut())())
+        .unwrap_or(Continue(false))
+        .into_glib()
 }
 
 unsafe extern "C" fn destroy_closure<F: FnMut() -> Continue + Send + 'static>(ptr: gpointer) {
@@ -138,7 +142,7 @@ unsafe extern "C" fn trampoline_child_watch<F: FnMut(Pid, i32) + Send + 'static>
     func: gpointer,
 ) {
     let func: &RefCell<F> = &*(func as *const RefCell<F>);
-    (*func.borrow_mut())(Pid(pid), status)
+    crate::catch_callback_panic(|| (*func.borrow_mut())(Pid(pid), status));
 }
 
 unsafe extern "C" fn trampoline_child_watch_local<F: FnMut(Pid, i32) + 'static>(
@@ -147,7 +151,7 @@ unsafe extern "C" fn trampoline_child_watch_local<F: FnMut(Pid, i32) + 'static>(
     func: gpointer,
 ) {
     let func: &ThreadGuard<RefCell<F>> = &*(func as *const ThreadGuard<RefCell<F>>);
-    (*func.get_ref().borrow_mut())(Pid(pid), status)
+    crate::catch_callback_panic(|| (*func.get_ref().borrow_mut())(Pid(pid), status));
 }
 
 unsafe extern "C" fn destroy_closure_child_watch<F: FnMut(Pid, i32) + Send + 'static>(
@@ -182,7 +186,9 @@ unsafe extern "C" fn trampoline_unix_fd<
     func: gpointer,
 ) -> gboolean {
     let func: &RefCell<F> = &*(func as *const RefCell<F>);
-    (*func.borrow_mut())(fd, from_glib(condition)).into_glib()
+    crate::catch_callback_panic(|| (*func.borrow_mut())(fd, from_glib(condition)))
+        .unwrap_or(Continue(false))
+        .into_glib()
 }
 
 #[cfg(any(unix, feature = "dox"))]
@@ -195,7 +201,9 @@ unsafe extern "C" fn trampoline_unix_fd_local<
     func: gpointer,
 ) -> gboolean {
     let func: &ThreadGuard<RefCell<F>> = &*(func as *const ThreadGuard<RefCell<F>>);
-    (*func.get_ref().borrow_mut())(fd, from_glib(condition)).into_glib()
+    crate::catch_callback_panic(|| (*func.get_ref().borrow_mut())(fd, from_glib(condition)))
+        .unwrap_or(Continue(false))
+        .into_glib()
 }
 
 #[cfg(any(unix, feature = "dox"))]
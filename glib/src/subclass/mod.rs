@@ -239,6 +239,8 @@ pub mod shared;
 
 pub mod signal;
 
+pub mod param_spec;
+
 mod object_impl_ref;
 pub use object_impl_ref::{ObjectImplRef, ObjectImplWeakRef};
 
@@ -249,6 +251,7 @@ pub mod prelude {
         boxed::BoxedType,
         interface::{ObjectInterface, ObjectInterfaceExt, ObjectInterfaceType},
         object::{ObjectClassSubclassExt, ObjectImpl, ObjectImplExt},
+        param_spec::{ParamSpecImpl, ParamSpecImplExt},
         shared::{RefCounted, SharedType},
         types::{
             ClassStruct, InstanceStruct, InstanceStructExt, IsImplementable, IsSubclassable,
@@ -79,7 +79,11 @@ unsafe extern "C" fn property<T: ObjectImpl>(
     let instance = &*(obj as *mut T::Instance);
     let imp = instance.imp();
 
-    let v = imp.property(id as usize, &from_glib_borrow(pspec));
+    let v =
+        match crate::catch_callback_panic(|| imp.property(id as usize, &from_glib_borrow(pspec))) {
+            Some(v) => v,
+            None => return,
+        };
 
     // We first unset the value we get passed in, in case it contained
     // any previous data. Then we directly overwrite it with our new
@@ -102,25 +106,27 @@ unsafe extern "C" fn set_property<T: ObjectImpl>(
 ) {
     let instance = &*(obj as *mut T::Instance);
     let imp = instance.imp();
-    imp.set_property(
-        id as usize,
-        &*(value as *mut Value),
-        &from_glib_borrow(pspec),
-    );
+    crate::catch_callback_panic(|| {
+        imp.set_property(
+            id as usize,
+            &*(value as *mut Value),
+            &from_glib_borrow(pspec),
+        )
+    });
 }
 
 unsafe extern "C" fn constructed<T: ObjectImpl>(obj: *mut gobject_ffi::GObject) {
     let instance = &*(obj as *mut T::Instance);
     let imp = instance.imp();
 
-    imp.constructed();
+    crate::catch_callback_panic(|| imp.constructed());
 }
 
 unsafe extern "C" fn dispose<T: ObjectImpl>(obj: *mut gobject_ffi::GObject) {
     let instance = &*(obj as *mut T::Instance);
     let imp = instance.imp();
 
-    imp.dispose();
+    crate::catch_callback_panic(|| imp.dispose());
 
     // Chain up to the parent's dispose.
     let data = T::type_data();
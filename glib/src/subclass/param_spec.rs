@@ -0,0 +1,417 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Module for registering custom `ParamSpec` types implemented in Rust.
+//!
+//! This allows defining properties with their own validation, comparison and default value
+//! logic. The validation is applied through the normal property setting path, e.g. by
+//! [`ObjectExt::set_property`](crate::object::ObjectExt::set_property) and
+//! [`ObjectExt::try_set_property`](crate::object::ObjectExt::try_set_property).
+
+use std::{any::TypeId, cmp::Ordering, collections::HashMap, ptr, sync::Mutex};
+
+use once_cell::sync::Lazy;
+
+use crate::{prelude::*, translate::*, utils::is_canonical_pspec_name, ParamSpec, Type, Value};
+
+// rustdoc-stripper-ignore-next
+/// Trait for implementing custom `ParamSpec` types.
+///
+/// An instance of the implementing type is stored inside each `ParamSpec` created with
+/// [`ParamSpecImplExt::builder`] and is available from the virtual methods below. This allows
+/// storing per-property data like ranges.
+///
+/// The `GType` of the `ParamSpec` is registered automatically the first time it is needed.
+pub trait ParamSpecImpl: Send + Sync + Sized + 'static {
+    // rustdoc-stripper-ignore-next
+    /// `ParamSpec` type name.
+    ///
+    /// This must be unique in the whole process.
+    const NAME: &'static str;
+
+    // rustdoc-stripper-ignore-next
+    /// Type of the values stored in properties using this `ParamSpec`.
+    type ValueType: StaticType;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the default value of the property.
+    ///
+    /// The returned value must be of type [`Self::ValueType`].
+    fn default_value(&self) -> Value;
+
+    // rustdoc-stripper-ignore-next
+    /// Validates `value` and modifies it to be valid if necessary.
+    ///
+    /// Returns `true` if the value was modified, i.e. it was not valid.
+    fn validate(&self, _value: &mut Value) -> bool {
+        false
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Compares two values of type [`Self::ValueType`].
+    fn values_cmp(&self, a: &Value, b: &Value) -> Ordering;
+}
+
+#[repr(C)]
+struct ParamSpecInstance<T> {
+    _parent: gobject_ffi::GParamSpec,
+    imp: Option<T>,
+}
+
+static TYPES: Lazy<Mutex<HashMap<TypeId, Type>>> = Lazy::new(Default::default);
+
+// rustdoc-stripper-ignore-next
+/// Extension trait for [`ParamSpecImpl`].
+pub trait ParamSpecImplExt: ParamSpecImpl {
+    // rustdoc-stripper-ignore-next
+    /// Returns the `ParamSpec` type for this implementation, registering it if necessary.
+    fn type_() -> Type;
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a `ParamSpec` named `name` that is using this implementation.
+    fn builder(self, name: &str) -> ParamSpecImplBuilder<Self>;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the implementation of `pspec`.
+    ///
+    /// This returns `None` if `pspec` was not created from this implementation.
+    fn from_param_spec(pspec: &ParamSpec) -> Option<&Self>;
+}
+
+impl<T: ParamSpecImpl> ParamSpecImplExt for T {
+    fn type_() -> Type {
+        let mut types = TYPES.lock().unwrap();
+        *types
+            .entry(TypeId::of::<T>())
+            .or_insert_with(register_param_spec_type::<T>)
+    }
+
+    fn builder(self, name: &str) -> ParamSpecImplBuilder<Self> {
+        ParamSpecImplBuilder::new(name, self)
+    }
+
+    fn from_param_spec(pspec: &ParamSpec) -> Option<&Self> {
+        if !pspec.type_().is_a(T::type_()) {
+            return None;
+        }
+
+        unsafe {
+            let ptr: *const gobject_ffi::GParamSpec = pspec.to_glib_none().0;
+            let instance = ptr as *const ParamSpecInstance<T>;
+            (*instance).imp.as_ref()
+        }
+    }
+}
+
+fn register_param_spec_type<T: ParamSpecImpl>() -> Type {
+    unsafe extern "C" fn instance_init<T: ParamSpecImpl>(pspec: *mut gobject_ffi::GParamSpec) {
+        let instance = pspec as *mut ParamSpecInstance<T>;
+        ptr::write(ptr::addr_of_mut!((*instance).imp), None);
+    }
+
+    unsafe extern "C" fn finalize<T: ParamSpecImpl>(pspec: *mut gobject_ffi::GParamSpec) {
+        let instance = pspec as *mut ParamSpecInstance<T>;
+        ptr::drop_in_place(ptr::addr_of_mut!((*instance).imp));
+
+        let parent_class = gobject_ffi::g_type_class_peek(gobject_ffi::G_TYPE_PARAM)
+            as *const gobject_ffi::GParamSpecClass;
+        if let Some(f) = (*parent_class).finalize {
+            f(pspec);
+        }
+    }
+
+    unsafe extern "C" fn value_set_default<T: ParamSpecImpl>(
+        pspec: *mut gobject_ffi::GParamSpec,
+        value: *mut gobject_ffi::GValue,
+    ) {
+        let imp = imp::<T>(pspec);
+        let default_value = imp.default_value();
+        assert!(
+            default_value.type_().is_a(T::ValueType::static_type()),
+            "ParamSpec {} expected default value of type {} but got {}",
+            T::NAME,
+            T::ValueType::static_type(),
+            default_value.type_()
+        );
+        gobject_ffi::g_value_copy(default_value.to_glib_none().0, value);
+    }
+
+    unsafe extern "C" fn value_validate<T: ParamSpecImpl>(
+        pspec: *mut gobject_ffi::GParamSpec,
+        value: *mut gobject_ffi::GValue,
+    ) -> ffi::gboolean {
+        let imp = imp::<T>(pspec);
+        imp.validate(&mut *(value as *mut Value)).into_glib()
+    }
+
+    unsafe extern "C" fn values_cmp<T: ParamSpecImpl>(
+        pspec: *mut gobject_ffi::GParamSpec,
+        a: *const gobject_ffi::GValue,
+        b: *const gobject_ffi::GValue,
+    ) -> i32 {
+        let imp = imp::<T>(pspec);
+        match imp.values_cmp(&*(a as *const Value), &*(b as *const Value)) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        }
+    }
+
+    unsafe fn imp<'a, T: ParamSpecImpl>(pspec: *mut gobject_ffi::GParamSpec) -> &'a T {
+        let instance = pspec as *const ParamSpecInstance<T>;
+        (*instance)
+            .imp
+            .as_ref()
+            .expect("ParamSpec implementation not initialized yet")
+    }
+
+    unsafe {
+        use std::ffi::CString;
+
+        let type_name = CString::new(T::NAME).unwrap();
+        assert_eq!(
+            gobject_ffi::g_type_from_name(type_name.as_ptr()),
+            gobject_ffi::G_TYPE_INVALID,
+            "Type {} has already been registered",
+            type_name.to_str().unwrap()
+        );
+
+        let instance_size = std::mem::size_of::<ParamSpecInstance<T>>();
+        assert!(
+            instance_size <= u16::MAX as usize,
+            "ParamSpec implementation {} is too big",
+            T::NAME
+        );
+
+        let info = gobject_ffi::GParamSpecTypeInfo {
+            instance_size: instance_size as u16,
+            n_preallocs: 0,
+            instance_init: Some(instance_init::<T>),
+            value_type: T::ValueType::static_type().into_glib(),
+            finalize: Some(finalize::<T>),
+            value_set_default: Some(value_set_default::<T>),
+            value_validate: Some(value_validate::<T>),
+            values_cmp: Some(values_cmp::<T>),
+        };
+
+        let type_ = Type::from_glib(gobject_ffi::g_param_type_register_static(
+            type_name.as_ptr(),
+            &info,
+        ));
+        assert!(type_.is_valid());
+
+        type_
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for `ParamSpec`s using a [`ParamSpecImpl`].
+#[must_use]
+pub struct ParamSpecImplBuilder<'a, T: ParamSpecImpl> {
+    name: &'a str,
+    nick: Option<&'a str>,
+    blurb: Option<&'a str>,
+    flags: crate::ParamFlags,
+    imp: T,
+}
+
+impl<'a, T: ParamSpecImpl> ParamSpecImplBuilder<'a, T> {
+    fn new(name: &'a str, imp: T) -> Self {
+        Self {
+            name,
+            nick: None,
+            blurb: None,
+            flags: crate::ParamFlags::default(),
+            imp,
+        }
+    }
+
+    #[must_use]
+    pub fn build(self) -> ParamSpec {
+        assert!(
+            is_canonical_pspec_name(self.name),
+            "{} is not a valid canonical parameter name",
+            self.name
+        );
+
+        unsafe {
+            let pspec = gobject_ffi::g_param_spec_internal(
+                T::type_().into_glib(),
+                self.name.to_glib_none().0,
+                self.nick.to_glib_none().0,
+                self.blurb.to_glib_none().0,
+                self.flags.into_glib(),
+            );
+            let instance = pspec as *mut ParamSpecInstance<T>;
+            (*instance).imp = Some(self.imp);
+
+            from_glib_none(pspec)
+        }
+    }
+}
+
+impl<'a, T: ParamSpecImpl> crate::prelude::ParamSpecBuilderExt<'a> for ParamSpecImplBuilder<'a, T> {
+    fn set_nick(&mut self, nick: Option<&'a str>) {
+        self.nick = nick;
+    }
+    fn set_blurb(&mut self, blurb: Option<&'a str>) {
+        self.blurb = blurb;
+    }
+    fn set_flags(&mut self, flags: crate::ParamFlags) {
+        self.flags = flags;
+    }
+    fn current_flags(&self) -> crate::ParamFlags {
+        self.flags
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+
+    use once_cell::sync::Lazy;
+
+    // We rename the current crate as glib, since the macros in glib-macros
+    // generate the glib namespace through the crate_ident_new utility,
+    // and that returns `glib` (and not `crate`) when called inside the glib crate
+    use crate as glib;
+    use crate::{prelude::*, subclass::prelude::*, PropertyError};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, glib::Boxed)]
+    #[boxed_type(name = "TestParamSpecRange")]
+    struct Range {
+        min: i32,
+        max: i32,
+    }
+
+    struct RangeParamSpec {
+        limit: i32,
+    }
+
+    impl ParamSpecImpl for RangeParamSpec {
+        const NAME: &'static str = "TestRangeParamSpec";
+        type ValueType = Range;
+
+        fn default_value(&self) -> glib::Value {
+            Range { min: 0, max: 0 }.to_value()
+        }
+
+        fn validate(&self, value: &mut glib::Value) -> bool {
+            let range = value.get::<Range>().unwrap();
+            let valid = Range {
+                min: range.min.clamp(-self.limit, self.limit),
+                max: range.max.clamp(range.min, self.limit),
+            };
+            if valid != range {
+                *value = valid.to_value();
+                true
+            } else {
+                false
+            }
+        }
+
+        fn values_cmp(&self, a: &glib::Value, b: &glib::Value) -> std::cmp::Ordering {
+            let a = a.get::<Range>().unwrap();
+            let b = b.get::<Range>().unwrap();
+            (a.min, a.max).cmp(&(b.min, b.max))
+        }
+    }
+
+    mod imp {
+        use super::*;
+
+        #[derive(Default)]
+        pub struct TestObject {
+            pub range: RefCell<Option<Range>>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for TestObject {
+            const NAME: &'static str = "TestParamSpecImplObject";
+            type Type = super::TestObject;
+        }
+
+        impl ObjectImpl for TestObject {
+            fn properties() -> &'static [glib::ParamSpec] {
+                static PROPERTIES: Lazy<Vec<glib::ParamSpec>> = Lazy::new(|| {
+                    vec![
+                        RangeParamSpec { limit: 10 }.builder("range").build(),
+                        RangeParamSpec { limit: 10 }
+                            .builder("lax-range")
+                            .lax_validation()
+                            .build(),
+                    ]
+                });
+                PROPERTIES.as_ref()
+            }
+
+            fn set_property(&self, _id: usize, value: &glib::Value, _pspec: &glib::ParamSpec) {
+                *self.range.borrow_mut() = Some(value.get().unwrap());
+            }
+
+            fn property(&self, _id: usize, pspec: &glib::ParamSpec) -> glib::Value {
+                match *self.range.borrow() {
+                    Some(range) => range.to_value(),
+                    None => pspec.default_value().clone(),
+                }
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct TestObject(ObjectSubclass<imp::TestObject>);
+    }
+
+    #[test]
+    fn test_param_spec_impl() {
+        let pspec = RangeParamSpec { limit: 5 }
+            .builder("range")
+            .nick("Range")
+            .build();
+
+        assert_eq!(pspec.name(), "range");
+        assert_eq!(pspec.nick(), "Range");
+        assert_eq!(pspec.type_(), RangeParamSpec::type_());
+        assert_eq!(pspec.value_type(), Range::static_type());
+        assert_eq!(
+            pspec.default_value().get::<Range>().unwrap(),
+            Range { min: 0, max: 0 }
+        );
+        assert_eq!(RangeParamSpec::from_param_spec(&pspec).unwrap().limit, 5);
+
+        let other = glib::ParamSpecInt::builder("int").build();
+        assert!(RangeParamSpec::from_param_spec(&other).is_none());
+
+        let mut value = Range { min: 1, max: 2 }.to_value();
+        assert!(!pspec.value_validate(&mut value));
+        let mut value = Range { min: 3, max: 2 }.to_value();
+        assert!(pspec.value_validate(&mut value));
+        assert_eq!(value.get::<Range>().unwrap(), Range { min: 3, max: 3 });
+
+        assert_eq!(
+            pspec.values_cmp(
+                &Range { min: 1, max: 2 }.to_value(),
+                &Range { min: 1, max: 3 }.to_value()
+            ),
+            std::cmp::Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_param_spec_impl_set_property() {
+        let obj = glib::Object::new::<TestObject>(&[]);
+
+        obj.set_property("range", Range { min: 1, max: 2 });
+        assert_eq!(obj.property::<Range>("range"), Range { min: 1, max: 2 });
+
+        let err = obj
+            .try_set_property("range", Range { min: 3, max: 2 })
+            .unwrap_err();
+        assert!(matches!(err, PropertyError::InvalidValue { .. }));
+        assert_eq!(obj.property::<Range>("range"), Range { min: 1, max: 2 });
+
+        obj.try_set_property("lax-range", Range { min: 3, max: 2 })
+            .unwrap();
+        assert_eq!(obj.property::<Range>("lax-range"), Range { min: 3, max: 3 });
+    }
+}
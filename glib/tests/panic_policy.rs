@@ -0,0 +1,137 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// The panic policy is global for the whole process, so these tests are in their own test binary
+// and serialized with `POLICY_LOCK`.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    process::Command,
+    sync::Mutex,
+};
+
+use glib::{prelude::*, subclass::prelude::*, Continue, MainContext, PanicPolicy};
+
+static POLICY_LOCK: Mutex<()> = Mutex::new(());
+
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&'static str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap()
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Default)]
+    pub struct Panicky;
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Panicky {
+        const NAME: &'static str = "PanicPolicyPanicky";
+        type Type = super::Panicky;
+    }
+
+    impl ObjectImpl for Panicky {
+        fn properties() -> &'static [glib::ParamSpec] {
+            use once_cell::sync::Lazy;
+            static PROPERTIES: Lazy<Vec<glib::ParamSpec>> =
+                Lazy::new(|| vec![glib::ParamSpecInt::builder("number").read_only().build()]);
+            PROPERTIES.as_ref()
+        }
+
+        fn property(&self, _id: usize, _pspec: &glib::ParamSpec) -> glib::Value {
+            panic!("property panic")
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct Panicky(ObjectSubclass<imp::Panicky>);
+}
+
+#[test]
+fn reraise() {
+    let _lock = POLICY_LOCK.lock().unwrap();
+    assert_eq!(glib::catch_callback_panic(|| 1), Some(1));
+
+    glib::set_panic_policy(PanicPolicy::Reraise);
+
+    let res = glib::catch_callback_panic(|| -> Continue { panic!("caught") });
+    assert!(res.is_none());
+    let panic = glib::take_pending_panic().unwrap();
+    assert_eq!(panic_message(&*panic), "caught");
+
+    let c = MainContext::new();
+    let source = glib::idle_source_new(None, glib::PRIORITY_DEFAULT, || -> Continue {
+        panic!("idle panic")
+    });
+    source.attach(Some(&c));
+    let res = panic::catch_unwind(AssertUnwindSafe(|| while c.iteration(false) {}));
+
+    glib::set_panic_policy(PanicPolicy::Unwind);
+
+    let panic = res.unwrap_err();
+    assert_eq!(panic_message(&*panic), "idle panic");
+    assert!(glib::take_pending_panic().is_none());
+}
+
+#[test]
+fn critical() {
+    let _lock = POLICY_LOCK.lock().unwrap();
+    glib::set_panic_policy(PanicPolicy::Critical);
+
+    // Signal handler, the emission continues with the other handlers
+    let obj = glib::Object::new::<Panicky>(&[]);
+    obj.connect_notify(None, |_, _| panic!("notify panic"));
+    let notified = std::rc::Rc::new(std::cell::Cell::new(false));
+    obj.connect_notify_local(None, {
+        let notified = notified.clone();
+        move |_, _| notified.set(true)
+    });
+    obj.notify("number");
+    assert!(notified.get());
+
+    // Virtual method, the value keeps its default
+    assert_eq!(obj.property::<i32>("number"), 0);
+
+    glib::set_panic_policy(PanicPolicy::Unwind);
+    assert!(glib::take_pending_panic().is_none());
+}
+
+// Run in a child process by `abort`
+#[test]
+#[ignore]
+fn abort_child() {
+    glib::set_panic_policy(PanicPolicy::Abort);
+    let c = MainContext::new();
+    let source = glib::idle_source_new(None, glib::PRIORITY_DEFAULT, || -> Continue {
+        panic!("idle panic")
+    });
+    source.attach(Some(&c));
+    c.iteration(false);
+}
+
+#[test]
+fn abort() {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["--ignored", "--exact", "abort_child", "--nocapture"])
+        .env_remove("G_DEBUG")
+        .env_remove("RUST_BACKTRACE")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // The location of the panic is logged, and its backtrace if enabled
+    assert!(
+        stderr.contains(&format!(
+            "Aborting after panic in callback: idle panic at {}",
+            file!()
+        )),
+        "{stderr}"
+    );
+    #[cfg(feature = "backtrace")]
+    assert!(stderr.contains("panic_policy::abort_child"), "{stderr}");
+}
@@ -0,0 +1,5 @@
+fn main() {
+    glib_build_tools::compile_schemas("schemas", "schemas").expect("Failed to compile schemas");
+    glib_build_tools::generate_settings("schemas", "settings.rs")
+        .expect("Failed to generate settings");
+}
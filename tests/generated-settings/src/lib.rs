@@ -0,0 +1,58 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+include!(concat!(env!("OUT_DIR"), "/settings.rs"));
+
+// Checked against the schemas compiled by the build script
+#[gio::settings(id = "com.github.gtk-rs.generated", schema_dir = "schemas")]
+pub struct CheckedSettings {
+    name: String,
+    #[settings(key = "level")]
+    current_level: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::set_var, sync::Once};
+
+    use gio::prelude::*;
+
+    use super::*;
+
+    static INIT: Once = Once::new();
+
+    // Each test uses its own memory backend so that they don't see each other's changes
+    fn settings(id: &str) -> gio::Settings {
+        INIT.call_once(|| {
+            set_var("GSETTINGS_SCHEMA_DIR", concat!(env!("OUT_DIR"), "/schemas"));
+        });
+        gio::Settings::with_backend(id, &gio::memory_settings_backend_new())
+    }
+
+    #[test]
+    fn generated_settings() {
+        use com_github_gtk_rs_generated::Settings;
+
+        let settings = Settings::from_settings(settings(Settings::SCHEMA_ID));
+        assert_eq!(Settings::SCHEMA_ID, "com.github.gtk-rs.generated");
+        assert_eq!(settings.name(), "Default");
+        assert_eq!(settings.level(), 3);
+        assert_eq!(Settings::LEVEL_RANGE, 1..=5);
+
+        assert_eq!(settings.color(), Color::Red);
+        settings.set_color(Color::LightBlue).unwrap();
+        assert_eq!(settings.settings().string("color"), "light-blue");
+        assert_eq!(settings.color(), Color::LightBlue);
+        settings.reset_color();
+        assert_eq!(settings.color(), Color::Red);
+    }
+
+    #[test]
+    fn checked_settings() {
+        let settings = CheckedSettings::from_settings(settings(CheckedSettings::SCHEMA_ID));
+        assert_eq!(settings.current_level(), 3);
+        settings.set_current_level(4).unwrap();
+        assert_eq!(settings.settings().uint("level"), 4);
+        settings.set_name("Changed".into()).unwrap();
+        assert_eq!(settings.name(), "Changed");
+    }
+}